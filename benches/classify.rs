@@ -0,0 +1,65 @@
+//! Compares the by-value classification functions with their `*_ref` and branchless versions, and the
+//! `has_*_overlap` functions with a full classification, using criterion. Run with `cargo bench --bench classify`.
+//!
+//! For `Copy` primitives the by-value and `*_ref` functions should take the same time, since the `*_ref`
+//! functions are inlined into the same comparisons. For `String`, the by-value functions need clones of
+//! the end points, which the `*_ref` versions avoid.
+use std::hint::black_box;
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion};
+
+use range_overlap::{
+    excl_classify, excl_classify_branchless, excl_classify_ref, has_excl_overlap, has_incl_overlap, has_incl_overlap_ref,
+    incl_classify,
+};
+
+mod common;
+use common::end_points;
+
+/// Time `f` over every element of `inputs`
+fn bench<I, R>(group: &mut BenchmarkGroup<WallTime>, name: &str, inputs: &[I], f: impl Fn(&I) -> R) {
+    group.bench_function(BenchmarkId::new(name, inputs.len()), |b| {
+        b.iter(|| {
+            for input in inputs {
+                black_box(f(black_box(input)));
+            }
+        })
+    });
+}
+
+fn bench_ints(c: &mut Criterion) {
+    let ints = end_points(10_000);
+    let mut group = c.benchmark_group("i64");
+    bench(&mut group, "excl_classify", &ints, |&[a, b, c, d]| excl_classify(a, b, c, d));
+    bench(&mut group, "excl_classify_ref", &ints, |[a, b, c, d]| excl_classify_ref(a, b, c, d));
+    bench(&mut group, "excl_classify_branchless", &ints, |&[a, b, c, d]| excl_classify_branchless(a, b, c, d));
+    bench(&mut group, "has_incl_overlap", &ints, |&[a, b, c, d]| has_incl_overlap(a, b, c, d));
+    bench(&mut group, "incl_classify(..).has_overlap()", &ints, |&[a, b, c, d]| incl_classify(a, b, c, d).has_overlap());
+    bench(&mut group, "has_incl_overlap_ref", &ints, |[a, b, c, d]| has_incl_overlap_ref(a, b, c, d));
+    group.finish();
+
+    // Both versions must agree, or the timings mean nothing
+    assert!(ints.iter().all(|&[a, b, c, d]| excl_classify(a, b, c, d) == excl_classify_ref(&a, &b, &c, &d)));
+    assert!(ints.iter().all(|&[a, b, c, d]| excl_classify(a, b, c, d) == excl_classify_branchless(a, b, c, d)));
+}
+
+fn bench_strings(c: &mut Criterion) {
+    let strings: Vec<[String; 4]> = end_points(10_000).iter().map(|e| e.map(|v| format!("{v:03}"))).collect();
+    let mut group = c.benchmark_group("String");
+    bench(&mut group, "excl_classify (cloned)", &strings, |[a, b, c, d]| excl_classify(a.clone(), b.clone(), c.clone(), d.clone()));
+    bench(&mut group, "excl_classify_ref", &strings, |[a, b, c, d]| excl_classify_ref(a, b, c, d));
+    group.finish();
+}
+
+fn bench_schedule(c: &mut Criterion) {
+    // Sorted, mostly disjoint ranges, as when checking a new booking against a schedule
+    let schedule: Vec<[i64; 4]> = (0..10_000).map(|i| [i * 10, i * 10 + 8, 50_000, 50_005]).collect();
+    let mut group = c.benchmark_group("schedule");
+    bench(&mut group, "has_excl_overlap", &schedule, |&[a, b, c, d]| has_excl_overlap(a, b, c, d));
+    bench(&mut group, "excl_classify(..).has_overlap()", &schedule, |&[a, b, c, d]| excl_classify(a, b, c, d).has_overlap());
+    group.finish();
+}
+
+criterion_group!(benches, bench_ints, bench_strings, bench_schedule);
+criterion_main!(benches);
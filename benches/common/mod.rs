@@ -0,0 +1,12 @@
+//! Inputs shared by the benchmarks.
+
+/// Pseudo-random end points, so that every kind of overlap turns up
+pub fn end_points(n: usize) -> Vec<[i64; 4]> {
+    let mut state = 1u64;
+    (0..n).map(|_| {
+        [(); 4].map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) as i64 % 100
+        })
+    }).collect()
+}
@@ -0,0 +1,44 @@
+//! Compares [`classify_ord`], which calls `Ord::cmp` once per pair of end points, with the operator-based
+//! classifiers, using criterion. Run with `cargo bench --bench ord`.
+//!
+//! For strings with a long shared prefix, `classify_ord` should be faster, since each comparison is
+//! expensive and it makes fewer of them. For `i64`, the operator-based version should be faster, since the
+//! compiler can evaluate its comparisons without branching.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use range_overlap::{classify_ord, excl_classify, excl_classify_ref};
+
+mod common;
+use common::end_points;
+
+fn bench_ints(c: &mut Criterion) {
+    let ints = end_points(10_000);
+    let mut group = c.benchmark_group("i64");
+    group.bench_function(BenchmarkId::new("excl_classify", ints.len()), |b| {
+        b.iter(|| ints.iter().map(|&[w, x, y, z]| excl_classify(w, x, y, z) as u8 as u64).sum::<u64>())
+    });
+    group.bench_function(BenchmarkId::new("classify_ord", ints.len()), |b| {
+        b.iter(|| ints.iter().map(|&[w, x, y, z]| classify_ord(w, x, y, z, false) as u8 as u64).sum::<u64>())
+    });
+    group.finish();
+}
+
+fn bench_strings(c: &mut Criterion) {
+    // A long shared prefix makes every comparison walk most of the string, as with paths under one directory
+    let prefix = "/data/archive/instruments/site-042/level2/daily/";
+    let strings: Vec<[String; 4]> = end_points(10_000).iter().map(|e| e.map(|v| format!("{prefix}{v:03}"))).collect();
+    let mut group = c.benchmark_group("String");
+    group.bench_function(BenchmarkId::new("excl_classify_ref", strings.len()), |b| {
+        b.iter(|| strings.iter().map(|[w, x, y, z]| excl_classify_ref(w, x, y, z) as u8 as u64).sum::<u64>())
+    });
+    group.bench_function(BenchmarkId::new("classify_ord", strings.len()), |b| {
+        b.iter(|| strings.iter().map(|[w, x, y, z]| classify_ord(w, x, y, z, false) as u8 as u64).sum::<u64>())
+    });
+    group.finish();
+
+    // Both versions must agree, or the timings mean nothing
+    assert!(strings.iter().all(|[w, x, y, z]| excl_classify_ref(w, x, y, z) == classify_ord(w, x, y, z, false)));
+}
+
+criterion_group!(benches, bench_ints, bench_strings);
+criterion_main!(benches);
@@ -0,0 +1,424 @@
+//! Allen's interval algebra: the 13 ways two intervals can be related.
+//!
+//! [`RangeOverlap`] deliberately groups some of these together, e.g. [`RangeOverlap::AInsideB`]
+//! does not say whether `A` shares a start or end with `B`, and [`RangeOverlap::None`] does not
+//! say which interval comes first. [`AllenRelation`] keeps those distinctions, and converts into
+//! a [`RangeOverlap`] when the coarser answer is all that is needed.
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::{Interval, RangeOverlap};
+
+/// One of the 13 relations between two intervals `A` and `B` described by Allen's interval algebra.
+///
+/// Because the intervals in this crate can include or exclude their ends, "sharing" an end point
+/// means both ends are at the same value *and* are included or excluded in the same way. Two intervals
+/// [meet](AllenRelation::Meets) when one ends at the value the other starts at but they have no values
+/// in common, e.g. `[1, 5)` and `[5, 9)`. If they have that one value in common (as `[1, 5]` and `[5, 9]` do),
+/// they [overlap](AllenRelation::Overlaps), and if neither includes it (as with `(1, 5)` and `(5, 9)`) there
+/// is a gap and A is [before](AllenRelation::Before) B.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum AllenRelation {
+    /// `A` ends before `B` starts, with a gap between them.
+    Before,
+
+    /// `A` ends where `B` starts, with no gap but no values in common.
+    Meets,
+
+    /// `A` starts before `B` and ends inside it.
+    Overlaps,
+
+    /// `A` and `B` share a start, but `A` ends first.
+    Starts,
+
+    /// `A` starts after and ends before `B`.
+    During,
+
+    /// `A` and `B` share an end, but `A` starts later.
+    Finishes,
+
+    /// `A` and `B` have the same start and end.
+    Equals,
+
+    /// `A` and `B` share an end, but `A` starts earlier.
+    FinishedBy,
+
+    /// `A` starts before and ends after `B`.
+    Contains,
+
+    /// `A` and `B` share a start, but `A` ends later.
+    StartedBy,
+
+    /// `A` starts inside `B` and ends after it.
+    OverlappedBy,
+
+    /// `A` starts where `B` ends, with no gap but no values in common.
+    MetBy,
+
+    /// `A` starts after `B` ends, with a gap between them.
+    After,
+}
+
+impl AllenRelation {
+    /// All 13 relations, in order from `A` entirely before `B` to `A` entirely after `B`.
+    pub const ALL: [AllenRelation; 13] = [
+        Self::Before, Self::Meets, Self::Overlaps, Self::Starts, Self::During, Self::Finishes, Self::Equals,
+        Self::FinishedBy, Self::Contains, Self::StartedBy, Self::OverlappedBy, Self::MetBy, Self::After,
+    ];
+
+    /// The relation of `B` to `A`, given that this is the relation of `A` to `B`.
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Before => Self::After,
+            Self::Meets => Self::MetBy,
+            Self::Overlaps => Self::OverlappedBy,
+            Self::Starts => Self::StartedBy,
+            Self::During => Self::Contains,
+            Self::Finishes => Self::FinishedBy,
+            Self::Equals => Self::Equals,
+            Self::FinishedBy => Self::Finishes,
+            Self::Contains => Self::During,
+            Self::StartedBy => Self::Starts,
+            Self::OverlappedBy => Self::Overlaps,
+            Self::MetBy => Self::Meets,
+            Self::After => Self::Before,
+        }
+    }
+
+    /// For relations where `A` and `B` share no values, which side of `B` that `A` is on: `Less` for
+    /// [`Before`](Self::Before) and [`Meets`](Self::Meets), `Greater` for [`MetBy`](Self::MetBy) and
+    /// [`After`](Self::After). For all other relations, `None`.
+    pub fn disjoint_cmp(&self) -> Option<Ordering> {
+        match self {
+            Self::Before | Self::Meets => Some(Ordering::Less),
+            Self::MetBy | Self::After => Some(Ordering::Greater),
+            _ => None,
+        }
+    }
+}
+
+/// Which side of range `B` a range `A` that does not overlap it is on. Returned by [`Interval::side_of`]
+/// and [`range_side`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Side {
+    /// `A` ends before `B` starts.
+    Before,
+
+    /// `A` starts after `B` ends.
+    After,
+}
+
+impl From<AllenRelation> for RangeOverlap {
+    fn from(value: AllenRelation) -> Self {
+        match value {
+            AllenRelation::Before | AllenRelation::Meets | AllenRelation::MetBy | AllenRelation::After => RangeOverlap::None,
+            AllenRelation::Overlaps => RangeOverlap::AEndsInB,
+            AllenRelation::Starts | AllenRelation::During | AllenRelation::Finishes => RangeOverlap::AInsideB,
+            AllenRelation::Equals => RangeOverlap::AEqualsB,
+            AllenRelation::FinishedBy | AllenRelation::Contains | AllenRelation::StartedBy => RangeOverlap::AContainsB,
+            AllenRelation::OverlappedBy => RangeOverlap::AStartsInB,
+        }
+    }
+}
+
+impl RangeOverlap {
+    /// The [`AllenRelation`]s that this classification could have come from. Converting any of
+    /// these relations into a `RangeOverlap` gives back `self`.
+    ///
+    /// ```
+    /// # use range_overlap::{AllenRelation, RangeOverlap};
+    /// assert_eq!(RangeOverlap::AEndsInB.possible_allen_relations(), &[AllenRelation::Overlaps]);
+    /// assert!(RangeOverlap::None.possible_allen_relations().contains(&AllenRelation::Meets));
+    /// ```
+    pub fn possible_allen_relations(&self) -> &'static [AllenRelation] {
+        match self {
+            RangeOverlap::AContainsB => &[AllenRelation::FinishedBy, AllenRelation::Contains, AllenRelation::StartedBy],
+            RangeOverlap::AInsideB => &[AllenRelation::Starts, AllenRelation::During, AllenRelation::Finishes],
+            RangeOverlap::AEndsInB => &[AllenRelation::Overlaps],
+            RangeOverlap::AStartsInB => &[AllenRelation::OverlappedBy],
+            RangeOverlap::AEqualsB => &[AllenRelation::Equals],
+            RangeOverlap::None => &[AllenRelation::Before, AllenRelation::Meets, AllenRelation::MetBy, AllenRelation::After],
+        }
+    }
+}
+
+/// Returns `Some(true)` if an end bound and a start bound are at the same value, with
+/// exactly one of them including it, so there is no gap between them.
+fn touches<T: PartialOrd>(end: Bound<&T>, start: Bound<&T>) -> Option<bool> {
+    match (end, start) {
+        (Bound::Included(e), Bound::Excluded(s)) | (Bound::Excluded(e), Bound::Included(s)) => e.partial_cmp(s).map(|o| o == Ordering::Equal),
+        _ => Some(false),
+    }
+}
+
+pub(crate) fn allen_bound_refs<T: PartialOrd>(
+    a_start: Bound<&T>, a_end: Bound<&T>, b_start: Bound<&T>, b_end: Bound<&T>
+) -> Option<AllenRelation> {
+    if bounds::try_is_empty_ref(a_start, a_end)? || bounds::try_is_empty_ref(b_start, b_end)? {
+        return None;
+    }
+
+    if bounds::ends_before(a_end, b_start)? {
+        return Some(if touches(a_end, b_start)? { AllenRelation::Meets } else { AllenRelation::Before });
+    }
+    if bounds::ends_before(b_end, a_start)? {
+        return Some(if touches(b_end, a_start)? { AllenRelation::MetBy } else { AllenRelation::After });
+    }
+
+    let relation = match (bounds::cmp_starts(a_start, b_start)?, bounds::cmp_ends(a_end, b_end)?) {
+        (Ordering::Less, Ordering::Less) => AllenRelation::Overlaps,
+        (Ordering::Equal, Ordering::Less) => AllenRelation::Starts,
+        (Ordering::Greater, Ordering::Less) => AllenRelation::During,
+        (Ordering::Greater, Ordering::Equal) => AllenRelation::Finishes,
+        (Ordering::Equal, Ordering::Equal) => AllenRelation::Equals,
+        (Ordering::Less, Ordering::Equal) => AllenRelation::FinishedBy,
+        (Ordering::Less, Ordering::Greater) => AllenRelation::Contains,
+        (Ordering::Equal, Ordering::Greater) => AllenRelation::StartedBy,
+        (Ordering::Greater, Ordering::Greater) => AllenRelation::OverlappedBy,
+    };
+    Some(relation)
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// Determine the [`AllenRelation`] of this interval (as `A`) to `other` (as `B`).
+    ///
+    /// Returns `None` if either interval is empty, since an empty interval has no position
+    /// to relate, or if any end points cannot be compared.
+    ///
+    /// ```
+    /// # use range_overlap::{AllenRelation, Interval};
+    /// assert_eq!(Interval::excl(1, 5).allen_relation(&Interval::excl(5, 9)), Some(AllenRelation::Meets));
+    /// assert_eq!(Interval::incl(1, 5).allen_relation(&Interval::incl(5, 9)), Some(AllenRelation::Overlaps));
+    /// assert_eq!(Interval::excl(1, 5).allen_relation(&Interval::excl(6, 9)), Some(AllenRelation::Before));
+    /// ```
+    pub fn allen_relation(&self, other: &Interval<T>) -> Option<AllenRelation> {
+        allen_bound_refs(self.start(), self.end(), other.start(), other.end())
+    }
+
+    /// Returns `true` if this interval and `other` touch at an end point without sharing any values,
+    /// i.e. one [meets](AllenRelation::Meets) the other.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert!(Interval::excl(1, 5).is_adjacent_to(&Interval::excl(5, 9)));
+    /// assert!(Interval::excl(5, 9).is_adjacent_to(&Interval::excl(1, 5)));
+    /// // These share the value 5, so overlap rather than touch
+    /// assert!(!Interval::incl(1, 5).is_adjacent_to(&Interval::incl(5, 9)));
+    /// ```
+    pub fn is_adjacent_to(&self, other: &Interval<T>) -> bool {
+        matches!(self.allen_relation(other), Some(AllenRelation::Meets | AllenRelation::MetBy))
+    }
+
+    /// If this interval and `other` share no values, returns `Less` if this interval comes before `other`
+    /// or `Greater` if it comes after. Returns `None` if they overlap, or under the same conditions as
+    /// [`Interval::allen_relation`]. This saves comparing end points again after a classification gives
+    /// [`RangeOverlap::None`].
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!(Interval::excl(1, 5).disjoint_cmp(&Interval::excl(5, 9)), Some(Ordering::Less));
+    /// assert_eq!(Interval::excl(5, 9).disjoint_cmp(&Interval::excl(1, 5)), Some(Ordering::Greater));
+    /// assert_eq!(Interval::incl(1, 5).disjoint_cmp(&Interval::incl(5, 9)), None);
+    /// ```
+    pub fn disjoint_cmp(&self, other: &Interval<T>) -> Option<Ordering> {
+        self.allen_relation(other)?.disjoint_cmp()
+    }
+
+    /// If this interval and `other` share no values, returns which side of `other` this interval is on.
+    /// Returns `None` if they overlap, or under the same conditions as [`Interval::allen_relation`].
+    /// This is [`Interval::disjoint_cmp`] with a more descriptive result.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, Side};
+    /// let booked = Interval::excl(9, 11);
+    /// assert_eq!(Interval::excl(7, 9).side_of(&booked), Some(Side::Before));
+    /// assert_eq!(Interval::excl(12, 13).side_of(&booked), Some(Side::After));
+    /// assert_eq!(Interval::excl(10, 12).side_of(&booked), None);
+    /// ```
+    pub fn side_of(&self, other: &Interval<T>) -> Option<Side> {
+        match self.disjoint_cmp(other)? {
+            Ordering::Less => Some(Side::Before),
+            Ordering::Greater => Some(Side::After),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+/// Returns `true` if the two ranges touch at an end point without sharing any values, e.g. `1..5`
+/// and `5..9`. As with [`classify_any`](crate::classify_any), `None` indicates an open side and
+/// `inclusive` indicates whether `a_end` and `b_end` are part of their ranges.
+///
+/// Since the start of a range is always included, two ranges can only touch without sharing a
+/// value if the touching end is excluded, so with `inclusive = true` this is always `false`.
+/// Use [`Interval::is_adjacent_to`] for finer control over which ends are included.
+///
+/// ```
+/// # use range_overlap::are_adjacent;
+/// assert!(are_adjacent(Some(1), Some(5), Some(5), Some(9), false));
+/// assert!(are_adjacent(None, Some(5), Some(5), None, false));
+/// assert!(!are_adjacent(Some(1), Some(5), Some(6), Some(9), false));
+/// assert!(!are_adjacent(Some(1), Some(5), Some(5), Some(9), true));
+/// ```
+pub fn are_adjacent<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> bool {
+    let a = Interval::new_unchecked(a_start, a_end, inclusive);
+    let b = Interval::new_unchecked(b_start, b_end, inclusive);
+    a.is_adjacent_to(&b)
+}
+
+/// If the two ranges share no values, returns `Less` if range A comes before range B or `Greater` if it
+/// comes after; otherwise returns `None`. The arguments are the same as for [`classify_any`](crate::classify_any).
+/// See [`Interval::disjoint_cmp`].
+///
+/// ```
+/// # use range_overlap::disjoint_cmp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(disjoint_cmp(Some(5), None, Some(1), Some(5), false), Some(Ordering::Greater));
+/// assert_eq!(disjoint_cmp(Some(5), None, Some(1), Some(5), true), None);
+/// ```
+pub fn disjoint_cmp<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> Option<Ordering> {
+    let a = Interval::new_unchecked(a_start, a_end, inclusive);
+    let b = Interval::new_unchecked(b_start, b_end, inclusive);
+    a.disjoint_cmp(&b)
+}
+
+/// If the two ranges share no values, returns which side of range B that range A is on. The arguments
+/// are the same as for [`classify_any`](crate::classify_any). See [`Interval::side_of`].
+///
+/// ```
+/// # use range_overlap::{range_side, Side};
+/// assert_eq!(range_side(Some(1), Some(5), Some(5), Some(9), false), Some(Side::Before));
+/// assert_eq!(range_side(Some(1), Some(5), Some(5), Some(9), true), None);
+/// ```
+pub fn range_side<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> Option<Side> {
+    let a = Interval::new_unchecked(a_start, a_end, inclusive);
+    let b = Interval::new_unchecked(b_start, b_end, inclusive);
+    a.side_of(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::sample_intervals;
+
+    #[test]
+    fn test_consistent_with_range_overlap() {
+        let intervals = sample_intervals::<i32>(4);
+        for a in &intervals {
+            for b in &intervals {
+                let overlap = a.classify_against(b);
+                match a.allen_relation(b) {
+                    Some(relation) => {
+                        assert_eq!(RangeOverlap::from(relation), overlap, "{a:?} vs {b:?}");
+                        assert!(overlap.possible_allen_relations().contains(&relation));
+                        assert_eq!(b.allen_relation(a), Some(relation.inverse()));
+                    },
+                    None => assert!(a.is_empty() || b.is_empty(), "{a:?} vs {b:?}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_possible_relations_partition() {
+        // Every relation maps to exactly one RangeOverlap, and is listed by it
+        for relation in AllenRelation::ALL {
+            let overlap = RangeOverlap::from(relation);
+            assert!(overlap.possible_allen_relations().contains(&relation));
+        }
+        let total: usize = [
+            RangeOverlap::AContainsB, RangeOverlap::AInsideB, RangeOverlap::AEndsInB,
+            RangeOverlap::AStartsInB, RangeOverlap::AEqualsB, RangeOverlap::None,
+        ].iter().map(|o| o.possible_allen_relations().len()).sum();
+        assert_eq!(total, AllenRelation::ALL.len());
+    }
+
+    #[test]
+    fn test_relations() {
+        use AllenRelation::*;
+        let b = Interval::excl(3, 6);
+        for (a, expected) in [
+            (Interval::excl(0, 2), Before),
+            (Interval::excl(0, 3), Meets),
+            (Interval::incl(0, 3), Overlaps),
+            (Interval::excl(0, 4), Overlaps),
+            (Interval::excl(3, 4), Starts),
+            (Interval::excl(4, 5), During),
+            (Interval::excl(4, 6), Finishes),
+            (Interval::excl(3, 6), Equals),
+            (Interval::excl(0, 6), FinishedBy),
+            (Interval::incl(0, 6), Contains),
+            (Interval::incl(3, 6), StartedBy),
+            (Interval::excl(4, 7), OverlappedBy),
+            (Interval::excl(6, 7), MetBy),
+            (Interval::excl(7, 8), After),
+        ] {
+            assert_eq!(a.allen_relation(&b), Some(expected), "{a:?}");
+        }
+
+        assert_eq!(Interval::excl(1, 1).allen_relation(&b), None);
+    }
+
+    #[test]
+    fn test_adjacency() {
+        assert!(Interval::excl(1, 5).is_adjacent_to(&Interval::excl(5, 9)));
+        assert!(Interval::incl(1, 5).is_adjacent_to(&Interval::greater_than(5)));
+        assert!(Interval::less_than(5).is_adjacent_to(&Interval::at_least(5)));
+        assert!(!Interval::less_than(5).is_adjacent_to(&Interval::greater_than(5)));
+        assert!(!Interval::excl(1, 5).is_adjacent_to(&Interval::excl(4, 9)));
+        assert!(!Interval::excl(1, 5).is_adjacent_to(&Interval::excl(1, 5)));
+        // Empty intervals aren't adjacent to anything
+        assert!(!Interval::excl(5, 5).is_adjacent_to(&Interval::excl(5, 9)));
+
+        assert!(are_adjacent(Some(5), Some(9), Some(1), Some(5), false));
+        assert!(are_adjacent(Some(5), None, None, Some(5), false));
+        assert!(!are_adjacent(Some(5), None, None, Some(5), true));
+        assert!(!are_adjacent(Some(1), Some(5), Some(5), Some(9), true));
+    }
+
+    #[test]
+    fn test_disjoint_cmp() {
+        let intervals = sample_intervals::<i32>(4);
+        for a in &intervals {
+            for b in &intervals {
+                let ordering = a.disjoint_cmp(b);
+                if a.is_empty() || b.is_empty() || a.has_overlap(b) {
+                    assert_eq!(ordering, None, "{a:?} vs {b:?}");
+                } else {
+                    // Disjoint, non-empty intervals are always on one side of each other
+                    assert!(ordering.is_some(), "{a:?} vs {b:?}");
+                    assert_eq!(b.disjoint_cmp(a), ordering.map(Ordering::reverse));
+                }
+            }
+        }
+
+        assert_eq!(Interval::less_than(0).disjoint_cmp(&Interval::excl(0, 1)), Some(Ordering::Less));
+        assert_eq!(Interval::greater_than(1).disjoint_cmp(&Interval::excl(0, 1)), Some(Ordering::Greater));
+        assert_eq!(disjoint_cmp(Some(1), Some(5), Some(6), None, true), Some(Ordering::Less));
+        assert_eq!(disjoint_cmp(None, None, Some(6), None, true), None);
+    }
+
+    #[test]
+    fn test_side() {
+        let intervals = sample_intervals::<i32>(4);
+        for a in &intervals {
+            for b in &intervals {
+                let expected = match a.disjoint_cmp(b) {
+                    Some(Ordering::Less) => Some(Side::Before),
+                    Some(Ordering::Greater) => Some(Side::After),
+                    _ => None,
+                };
+                assert_eq!(a.side_of(b), expected, "{a:?} vs {b:?}");
+            }
+        }
+
+        assert_eq!(range_side(None, Some(0), Some(0), None, false), Some(Side::Before));
+        assert_eq!(range_side(Some(0), None, None, Some(0), false), Some(Side::After));
+        assert_eq!(range_side(Some(0), None, None, Some(0), true), None);
+    }
+}
@@ -0,0 +1,296 @@
+//! An interval tree whose nodes all live in one vector, linked by index rather than by pointer.
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::Interval;
+
+#[derive(Debug, Clone)]
+struct ArenaNode<T, V> {
+    interval: Interval<T>,
+    value: V,
+    /// The latest end of any interval in this subtree
+    max_end: Bound<T>,
+    height: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// An [`IntervalTree`](crate::IntervalTree) whose nodes are kept together in one `Vec`, with children
+/// referred to by their index in it, rather than each allocated in its own `Box`. Building the tree then
+/// takes one growing allocation rather than one per interval, and [`clear`](ArenaIntervalTree::clear)
+/// empties it without freeing that allocation, so a tree rebuilt for each batch of intervals stops
+/// allocating once it has grown to the size of the largest batch.
+///
+/// The queries are the same as the boxed tree's, and return the same results in the same order.
+///
+/// ```
+/// # use range_overlap::{ArenaIntervalTree, Interval};
+/// let mut tree = ArenaIntervalTree::with_capacity(3);
+/// let mut at_18 = Vec::new();
+/// for batch in [[(0, 100), (50, 150), (200, 300)], [(10, 20), (15, 25), (30, 40)]] {
+///     tree.clear();
+///     for (i, (start, end)) in batch.into_iter().enumerate() {
+///         tree.insert(Interval::excl(start, end), i);
+///     }
+///     at_18.push(tree.query_point(&18).into_iter().map(|(_, &i)| i).collect::<Vec<_>>());
+/// }
+/// assert_eq!(at_18, [vec![0], vec![0, 1]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArenaIntervalTree<T, V> {
+    nodes: Vec<ArenaNode<T, V>>,
+    root: Option<usize>,
+}
+
+impl<T, V> ArenaIntervalTree<T, V> {
+    /// Create an empty tree.
+    pub fn new() -> Self {
+        Self { nodes: Vec::new(), root: None }
+    }
+
+    /// Create an empty tree with room for `capacity` intervals before it needs to allocate again.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { nodes: Vec::with_capacity(capacity), root: None }
+    }
+
+    /// Returns `true` if no intervals are stored in this tree.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The number of intervals stored in this tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The number of intervals this tree can hold without allocating again.
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Remove every interval from this tree, keeping the memory it used for the next ones.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    fn height(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |i| self.nodes[i].height)
+    }
+}
+
+impl<T, V> Default for ArenaIntervalTree<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone, V> ArenaIntervalTree<T, V> {
+    /// Store `interval` with `value`. An empty interval (including one whose end points cannot be
+    /// compared) would never be found by a query, so it is not stored.
+    pub fn insert(&mut self, interval: Interval<T>, value: V) {
+        if interval.is_empty() {
+            return;
+        }
+        let max_end = interval.end().cloned();
+        self.nodes.push(ArenaNode { interval, value, max_end, height: 1, left: None, right: None });
+        self.root = Some(self.insert_below(self.root, self.nodes.len() - 1));
+    }
+
+    /// Link the node at `new` into the subtree rooted at `node`, returning the subtree's new root.
+    fn insert_below(&mut self, node: Option<usize>, new: usize) -> usize {
+        let Some(node) = node else { return new };
+        if self.nodes[new].interval.partial_cmp(&self.nodes[node].interval) == Some(Ordering::Less) {
+            let left = self.insert_below(self.nodes[node].left, new);
+            self.nodes[node].left = Some(left);
+        } else {
+            let right = self.insert_below(self.nodes[node].right, new);
+            self.nodes[node].right = Some(right);
+        }
+        self.rebalance(node)
+    }
+
+    /// Recompute a node's height and maximum end from its children.
+    fn update(&mut self, i: usize) {
+        let node = &self.nodes[i];
+        let mut max_end = node.interval.end();
+        for child in [node.left, node.right].into_iter().flatten() {
+            // Stored intervals are never empty, so their end points can always be compared
+            max_end = bounds::later_end(max_end, self.nodes[child].max_end.as_ref()).unwrap_or(max_end);
+        }
+        let max_end = max_end.cloned();
+        let height = 1 + self.height(node.left).max(self.height(node.right));
+        let node = &mut self.nodes[i];
+        node.max_end = max_end;
+        node.height = height;
+    }
+
+    fn rotate_left(&mut self, i: usize) -> usize {
+        let right = self.nodes[i].right.expect("rotating left needs a right child");
+        self.nodes[i].right = self.nodes[right].left;
+        self.update(i);
+        self.nodes[right].left = Some(i);
+        self.update(right);
+        right
+    }
+
+    fn rotate_right(&mut self, i: usize) -> usize {
+        let left = self.nodes[i].left.expect("rotating right needs a left child");
+        self.nodes[i].left = self.nodes[left].right;
+        self.update(i);
+        self.nodes[left].right = Some(i);
+        self.update(left);
+        left
+    }
+
+    /// Restore the AVL balance of a node after one of its subtrees grew by one level, returning the
+    /// index of the node now at its place in the tree.
+    fn rebalance(&mut self, i: usize) -> usize {
+        self.update(i);
+        let (left, right) = (self.nodes[i].left, self.nodes[i].right);
+        if self.height(left) > self.height(right) + 1 {
+            let child = left.unwrap();
+            if self.height(self.nodes[child].left) < self.height(self.nodes[child].right) {
+                self.nodes[i].left = Some(self.rotate_left(child));
+            }
+            self.rotate_right(i)
+        } else if self.height(right) > self.height(left) + 1 {
+            let child = right.unwrap();
+            if self.height(self.nodes[child].right) < self.height(self.nodes[child].left) {
+                self.nodes[i].right = Some(self.rotate_right(child));
+            }
+            self.rotate_left(i)
+        } else {
+            i
+        }
+    }
+
+    fn find_overlapping<'a>(&'a self, i: usize, query: &Interval<T>, found: &mut Vec<(&'a Interval<T>, &'a V)>) {
+        let node = &self.nodes[i];
+        // Nothing in this subtree ends late enough to reach the query
+        if bounds::ends_before(node.max_end.as_ref(), query.start()) != Some(false) {
+            return;
+        }
+        if let Some(left) = node.left {
+            self.find_overlapping(left, query, found);
+        }
+        if node.interval.has_overlap(query) {
+            found.push((&node.interval, &node.value));
+        }
+        // Everything to the right starts no earlier than this node, so if the query ends before
+        // this node starts, it ends before all of them too
+        if bounds::ends_before(query.end(), node.interval.start()) == Some(false) {
+            if let Some(right) = node.right {
+                self.find_overlapping(right, query, found);
+            }
+        }
+    }
+
+    fn collect<'a>(&'a self, i: usize, found: &mut Vec<(&'a Interval<T>, &'a V)>) {
+        let node = &self.nodes[i];
+        if let Some(left) = node.left {
+            self.collect(left, found);
+        }
+        found.push((&node.interval, &node.value));
+        if let Some(right) = node.right {
+            self.collect(right, found);
+        }
+    }
+
+    /// The stored intervals that overlap `interval`, with their values, ordered by interval.
+    pub fn query_overlapping(&self, interval: &Interval<T>) -> Vec<(&Interval<T>, &V)> {
+        let mut found = Vec::new();
+        if let Some(root) = self.root {
+            self.find_overlapping(root, interval, &mut found);
+        }
+        found
+    }
+
+    /// The stored intervals that contain `point`, with their values, ordered by interval.
+    pub fn query_point(&self, point: &T) -> Vec<(&Interval<T>, &V)> {
+        self.query_overlapping(&Interval::point(point.clone()))
+    }
+
+    /// The intervals in the arena that contain `point`, ordered by interval: the same as [`query_point`](Self::query_point).
+    pub fn stab(&self, point: &T) -> Vec<(&Interval<T>, &V)> {
+        self.query_point(point)
+    }
+
+    /// All of the stored intervals with their values, ordered by interval.
+    pub fn to_vec(&self) -> Vec<(&Interval<T>, &V)> {
+        let mut found = Vec::with_capacity(self.len());
+        if let Some(root) = self.root {
+            self.collect(root, &mut found);
+        }
+        found
+    }
+}
+
+impl<T: PartialOrd + Clone, V> FromIterator<(Interval<T>, V)> for ArenaIntervalTree<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, V)>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut tree = Self::with_capacity(iter.size_hint().0);
+        for (interval, value) in iter {
+            tree.insert(interval, value);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval, sample_intervals};
+    use crate::IntervalTree;
+
+    /// Check the AVL balance of every node, returning the subtree's height
+    fn check_node(tree: &ArenaIntervalTree<i32, usize>, node: Option<usize>) -> usize {
+        let Some(i) = node else { return 0 };
+        let node = &tree.nodes[i];
+        let (left, right) = (check_node(tree, node.left), check_node(tree, node.right));
+        assert!(left.abs_diff(right) <= 1);
+        assert_eq!(node.height, 1 + left.max(right));
+        node.height
+    }
+
+    #[test]
+    fn test_matches_boxed_tree() {
+        let mut rng = Lcg::new(24680);
+        let mut arena = ArenaIntervalTree::new();
+        for round in 0..4 {
+            let intervals: Vec<Interval<i32>> = (0..100 * round + 50).map(|_| random_interval(&mut rng, 200, 20)).collect();
+
+            let capacity = arena.capacity();
+            arena.clear();
+            assert!(arena.is_empty());
+            assert_eq!(arena.capacity(), capacity);
+            for (i, interval) in intervals.iter().enumerate() {
+                arena.insert(*interval, i);
+            }
+            let boxed: IntervalTree<i32, usize> = intervals.iter().copied().zip(0..).collect();
+            assert_eq!(arena.len(), boxed.len());
+            check_node(&arena, arena.root);
+
+            assert_eq!(arena.to_vec(), boxed.to_vec());
+            for start in -5..230 {
+                assert_eq!(arena.query_point(&start), boxed.query_point(&start), "at {start}");
+                for query in [Interval::excl(start, start + 7), Interval::greater_than(start), Interval::at_most(start)] {
+                    assert_eq!(arena.query_overlapping(&query), boxed.query_overlapping(&query), "{query:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stab() {
+        let stored: Vec<(Interval<i32>, usize)> = sample_intervals(5).into_iter().zip(0..).collect();
+        let arena: ArenaIntervalTree<i32, usize> = stored.iter().copied().collect();
+        for point in -1..=5 {
+            let expected: Vec<usize> = stored.iter().filter(|(iv, _)| iv.contains_point(&point)).map(|(_, v)| *v).collect();
+            let mut found: Vec<usize> = arena.stab(&point).into_iter().map(|(_, &v)| v).collect();
+            found.sort();
+            assert_eq!(found, expected, "at {point}");
+        }
+    }
+}
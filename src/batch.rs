@@ -0,0 +1,126 @@
+//! Classifying many pairs of ranges at once, from columns of end points.
+use crate::{excl_classify, incl_classify, RangeOverlap};
+
+/// Classify many pairs of closed ranges at once, given as columns of end points (structure-of-arrays),
+/// such as columns pulled out of a data frame. Element `i` of the result is how the range from
+/// `a_starts[i]` to `a_ends[i]` overlaps the range from `b_starts[i]` to `b_ends[i]`, classified with
+/// [`incl_classify`] if `inclusive` is `true` and [`excl_classify`] otherwise. The choice of classifier is
+/// made once for the whole batch rather than for every pair.
+///
+/// Panics if the four slices are not all the same length.
+///
+/// ```
+/// # use range_overlap::{classify_slices, RangeOverlap};
+/// let a_starts = [0, 0, 5];
+/// let a_ends = [10, 5, 8];
+/// let b_starts = [2, 5, 0];
+/// let b_ends = [4, 9, 6];
+/// assert_eq!(classify_slices(&a_starts, &a_ends, &b_starts, &b_ends, false), [RangeOverlap::AContainsB, RangeOverlap::None, RangeOverlap::AStartsInB]);
+/// assert_eq!(classify_slices(&a_starts, &a_ends, &b_starts, &b_ends, true)[1], RangeOverlap::AEndsInB);
+/// ```
+pub fn classify_slices<T: PartialOrd>(a_starts: &[T], a_ends: &[T], b_starts: &[T], b_ends: &[T], inclusive: bool) -> Vec<RangeOverlap> {
+    let n = a_starts.len();
+    assert!(a_ends.len() == n && b_starts.len() == n && b_ends.len() == n, "the columns of end points to classify must all be the same length");
+    if inclusive {
+        classify_columns(a_starts, a_ends, b_starts, b_ends, incl_classify)
+    } else {
+        classify_columns(a_starts, a_ends, b_starts, b_ends, excl_classify)
+    }
+}
+
+/// One pair of closed ranges, laid out like the C struct
+///
+/// ```c
+/// struct RangePair { T a_start; T a_end; T b_start; T b_end; };
+/// ```
+///
+/// so that an array of pairs built in another language can be classified in place, without copying each
+/// end point into Rust values. See [`classify_pairs_into`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RangePair<T> {
+    pub a_start: T,
+    pub a_end: T,
+    pub b_start: T,
+    pub b_end: T,
+}
+
+/// Classify each pair of closed ranges in `pairs`, writing how the ranges in `pairs[i]` overlap to
+/// `out[i]`, with [`incl_classify`] if `inclusive` is `true` and [`excl_classify`] otherwise. Both slices
+/// are plain contiguous arrays ([`RangeOverlap`] is a single byte), so a large job can be split into
+/// chunks with `chunks` and `chunks_mut` and each handed to a different thread, or to code in another
+/// language, without allocating or converting anything per pair.
+///
+/// Panics if `out` is not the same length as `pairs`.
+///
+/// ```
+/// # use range_overlap::{classify_pairs_into, RangeOverlap, RangePair};
+/// let pairs = [
+///     RangePair { a_start: 0, a_end: 10, b_start: 2, b_end: 4 },
+///     RangePair { a_start: 0, a_end: 5, b_start: 5, b_end: 9 },
+/// ];
+/// let mut out = [RangeOverlap::None; 2];
+/// classify_pairs_into(&pairs, true, &mut out);
+/// assert_eq!(out, [RangeOverlap::AContainsB, RangeOverlap::AEndsInB]);
+/// ```
+pub fn classify_pairs_into<T: PartialOrd>(pairs: &[RangePair<T>], inclusive: bool, out: &mut [RangeOverlap]) {
+    assert_eq!(pairs.len(), out.len(), "there must be one output slot for each pair of ranges");
+    let classifier = if inclusive { incl_classify::<&T> } else { excl_classify::<&T> };
+    for (pair, out) in pairs.iter().zip(out) {
+        *out = classifier(&pair.a_start, &pair.a_end, &pair.b_start, &pair.b_end);
+    }
+}
+
+fn classify_columns<'a, T, F>(a_starts: &'a [T], a_ends: &'a [T], b_starts: &'a [T], b_ends: &'a [T], classifier: F) -> Vec<RangeOverlap>
+where
+    F: Fn(&'a T, &'a T, &'a T, &'a T) -> RangeOverlap,
+{
+    (0..a_starts.len()).map(|i| classifier(&a_starts[i], &a_ends[i], &b_starts[i], &b_ends[i])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Lcg;
+
+    #[test]
+    fn test_matches_elementwise() {
+        let mut rng = Lcg::new(2468);
+        let columns: Vec<Vec<i32>> = (0..4).map(|_| (0..500).map(|_| rng.below(10)).collect()).collect();
+        let [a_starts, a_ends, b_starts, b_ends] = [&columns[0], &columns[1], &columns[2], &columns[3]];
+        for inclusive in [false, true] {
+            let found = classify_slices(a_starts, a_ends, b_starts, b_ends, inclusive);
+            for i in 0..500 {
+                let expected = if inclusive {
+                    incl_classify(a_starts[i], a_ends[i], b_starts[i], b_ends[i])
+                } else {
+                    excl_classify(a_starts[i], a_ends[i], b_starts[i], b_ends[i])
+                };
+                assert_eq!(found[i], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pairs_match_columns() {
+        let mut rng = Lcg::new(1357);
+        let pairs: Vec<RangePair<i32>> = (0..500).map(|_| RangePair { a_start: rng.below(10), a_end: rng.below(10), b_start: rng.below(10), b_end: rng.below(10) }).collect();
+        let column = |f: fn(&RangePair<i32>) -> i32| pairs.iter().map(f).collect::<Vec<_>>();
+        let [a_starts, a_ends, b_starts, b_ends] = [column(|p| p.a_start), column(|p| p.a_end), column(|p| p.b_start), column(|p| p.b_end)];
+        for inclusive in [false, true] {
+            let mut out = vec![RangeOverlap::None; pairs.len()];
+            // In uneven chunks, as a caller sharing the work out would
+            for (pairs, out) in pairs.chunks(64).zip(out.chunks_mut(64)) {
+                classify_pairs_into(pairs, inclusive, out);
+            }
+            assert_eq!(out, classify_slices(&a_starts, &a_ends, &b_starts, &b_ends, inclusive));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_lengths() {
+        classify_slices(&[0, 1], &[2, 3], &[0], &[5], false);
+    }
+}
@@ -0,0 +1,116 @@
+//! Branch-free batch checks of many pairs of primitive ranges for overlap.
+//!
+//! The columns are walked in fixed-size chunks, and each pair is checked with non-short-circuiting `&`
+//! and `|`, so the loop has no data-dependent branches to mispredict. This is plain scalar code: it does
+//! not use `std::simd` or platform intrinsics, and whether the compiler vectorizes it depends on the
+//! type and target.
+
+/// How many pairs are checked together in each step.
+const CHUNK: usize = 8;
+
+/// [`has_excl_overlap`](crate::has_excl_overlap) without branches.
+#[inline(always)]
+fn excl_pair<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> bool {
+    // As in `has_excl_overlap`, incomparable end points are not apart, and equal or containing ranges
+    // overlap even when empty
+    let apart = (a_start >= b_end) | (b_start >= a_end);
+    !apart | ((a_start == b_start) & (a_end == b_end)) | ((a_start <= b_start) & (a_end >= b_end))
+}
+
+/// [`has_incl_overlap`](crate::has_incl_overlap) without branches.
+#[inline(always)]
+fn incl_pair<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> bool {
+    let apart = (a_start > b_end) | (b_start > a_end);
+    !apart | ((a_start == b_start) & (a_end == b_end)) | ((a_start <= b_start) & (a_end >= b_end))
+}
+
+#[inline(always)]
+fn overlap_batch<T: Copy + PartialOrd>(a_starts: &[T], a_ends: &[T], b_starts: &[T], b_ends: &[T], check: impl Fn(T, T, T, T) -> bool) -> Vec<bool> {
+    let n = a_starts.len();
+    assert!(a_ends.len() == n && b_starts.len() == n && b_ends.len() == n, "the columns of end points to check must all be the same length");
+    let mut overlaps = vec![false; n];
+    let mut out = overlaps.chunks_exact_mut(CHUNK);
+    let mut columns = [a_starts, a_ends, b_starts, b_ends].map(|c| c.chunks_exact(CHUNK));
+    for out in &mut out {
+        let [a_start, a_end, b_start, b_end] = columns.each_mut().map(|c| c.next().expect("every column has as many chunks as the output"));
+        for i in 0..CHUNK {
+            out[i] = check(a_start[i], a_end[i], b_start[i], b_end[i]);
+        }
+    }
+
+    let [a_start, a_end, b_start, b_end] = columns.map(|c| c.remainder());
+    for (i, out) in out.into_remainder().iter_mut().enumerate() {
+        *out = check(a_start[i], a_end[i], b_start[i], b_end[i]);
+    }
+    overlaps
+}
+
+/// For each `i`, whether the range from `a_starts[i]` to `a_ends[i]` overlaps the range from `b_starts[i]`
+/// to `b_ends[i]`, with the ends not included, exactly as [`has_excl_overlap`](crate::has_excl_overlap)
+/// would say. This is meant for primitive numbers such as `i32`, `i64`, `f32`, and `f64`: each pair is
+/// checked without branching on the end points, which avoids mispredictions on unpredictable data.
+/// Only available with the `batch-overlap` feature.
+///
+/// Panics if the four slices are not all the same length.
+///
+/// ```
+/// # use range_overlap::has_excl_overlap_batch;
+/// let a_starts = [0.0, 0.0, 5.0];
+/// let a_ends = [10.0, 5.0, 8.0];
+/// let b_starts = [2.0, 5.0, 0.0];
+/// let b_ends = [4.0, 9.0, 6.0];
+/// assert_eq!(has_excl_overlap_batch(&a_starts, &a_ends, &b_starts, &b_ends), [true, false, true]);
+/// ```
+pub fn has_excl_overlap_batch<T: Copy + PartialOrd>(a_starts: &[T], a_ends: &[T], b_starts: &[T], b_ends: &[T]) -> Vec<bool> {
+    overlap_batch(a_starts, a_ends, b_starts, b_ends, excl_pair)
+}
+
+/// Like [`has_excl_overlap_batch`], but with the ends included in the ranges, as
+/// [`has_incl_overlap`](crate::has_incl_overlap) would say. Only available with the `batch-overlap` feature.
+///
+/// Panics if the four slices are not all the same length.
+pub fn has_incl_overlap_batch<T: Copy + PartialOrd>(a_starts: &[T], a_ends: &[T], b_starts: &[T], b_ends: &[T]) -> Vec<bool> {
+    overlap_batch(a_starts, a_ends, b_starts, b_ends, incl_pair)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Lcg;
+    use crate::{has_excl_overlap, has_incl_overlap};
+
+    /// Check the batch functions against the scalar ones on `columns`, for every length up to a few chunks
+    fn check_matches_scalar<T: Copy + PartialOrd + std::fmt::Debug>(columns: &[Vec<T>; 4]) {
+        for n in 0..=3 * CHUNK + 1 {
+            let [a_starts, a_ends, b_starts, b_ends] = columns.each_ref().map(|c| &c[..n]);
+            let excl = has_excl_overlap_batch(a_starts, a_ends, b_starts, b_ends);
+            let incl = has_incl_overlap_batch(a_starts, a_ends, b_starts, b_ends);
+            for i in 0..n {
+                let ends = (a_starts[i], a_ends[i], b_starts[i], b_ends[i]);
+                assert_eq!(excl[i], has_excl_overlap(ends.0, ends.1, ends.2, ends.3), "{ends:?}");
+                assert_eq!(incl[i], has_incl_overlap(ends.0, ends.1, ends.2, ends.3), "{ends:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_scalar() {
+        let mut rng = Lcg::new(97531);
+        // Small values so that equal, touching, and backwards ranges are common
+        for _ in 0..200 {
+            let ints: [Vec<i32>; 4] = std::array::from_fn(|_| (0..3 * CHUNK + 1).map(|_| rng.below(6)).collect());
+            check_matches_scalar(&ints);
+            check_matches_scalar(&ints.each_ref().map(|c| c.iter().map(|&v| v as i64 - 3).collect::<Vec<_>>()));
+            check_matches_scalar(&ints.each_ref().map(|c| c.iter().map(|&v| v as f32).collect::<Vec<_>>()));
+            // Replace the largest value with NaN, which compares false with everything
+            let floats = ints.each_ref().map(|c| c.iter().map(|&v| if v == 5 { f64::NAN } else { v as f64 }).collect::<Vec<_>>());
+            check_matches_scalar(&floats);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_lengths() {
+        has_excl_overlap_batch(&[0, 1], &[2, 3], &[0], &[5]);
+    }
+}
@@ -0,0 +1,504 @@
+//! Comparisons between [`Bound`]s, used to classify ranges whose ends may each be
+//! included, excluded, or unbounded.
+//!
+//! Everything in here works on `Bound<&T>` so that callers holding owned bounds can
+//! classify without giving up (or cloning) their end points. All comparisons return
+//! `Option`s; a `None` means that two end points could not be compared (e.g. one was
+//! a NaN float).
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+use crate::RangeOverlap;
+
+/// Compare two start bounds. `Less` means that `a` lets in values earlier than `b`.
+pub(crate) fn cmp_starts<T: PartialOrd>(a: Bound<&T>, b: Bound<&T>) -> Option<Ordering> {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Some(Ordering::Equal),
+        (Bound::Unbounded, _) => Some(Ordering::Less),
+        (_, Bound::Unbounded) => Some(Ordering::Greater),
+        (Bound::Included(x), Bound::Included(y)) | (Bound::Excluded(x), Bound::Excluded(y)) => x.partial_cmp(y),
+        // An included start at the same value as an excluded one lets in that value,
+        // so it starts "earlier".
+        (Bound::Included(x), Bound::Excluded(y)) => x.partial_cmp(y).map(|o| o.then(Ordering::Less)),
+        (Bound::Excluded(x), Bound::Included(y)) => x.partial_cmp(y).map(|o| o.then(Ordering::Greater)),
+    }
+}
+
+/// Compare two end bounds. `Less` means that `a` stops letting in values before `b` does.
+pub(crate) fn cmp_ends<T: PartialOrd>(a: Bound<&T>, b: Bound<&T>) -> Option<Ordering> {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Some(Ordering::Equal),
+        (Bound::Unbounded, _) => Some(Ordering::Greater),
+        (_, Bound::Unbounded) => Some(Ordering::Less),
+        (Bound::Included(x), Bound::Included(y)) | (Bound::Excluded(x), Bound::Excluded(y)) => x.partial_cmp(y),
+        // An included end at the same value as an excluded one lets in that value,
+        // so it ends "later".
+        (Bound::Included(x), Bound::Excluded(y)) => x.partial_cmp(y).map(|o| o.then(Ordering::Greater)),
+        (Bound::Excluded(x), Bound::Included(y)) => x.partial_cmp(y).map(|o| o.then(Ordering::Less)),
+    }
+}
+
+/// Of two start bounds, the one that lets in values later.
+pub(crate) fn later_start<'a, T: PartialOrd>(a: Bound<&'a T>, b: Bound<&'a T>) -> Option<Bound<&'a T>> {
+    Some(if cmp_starts(a, b)? == Ordering::Less { b } else { a })
+}
+
+/// Of two end bounds, the one that stops letting in values first.
+pub(crate) fn earlier_end<'a, T: PartialOrd>(a: Bound<&'a T>, b: Bound<&'a T>) -> Option<Bound<&'a T>> {
+    Some(if cmp_ends(a, b)? == Ordering::Greater { b } else { a })
+}
+
+/// Of two start bounds, the one that lets in values earlier.
+pub(crate) fn earlier_start<'a, T: PartialOrd>(a: Bound<&'a T>, b: Bound<&'a T>) -> Option<Bound<&'a T>> {
+    Some(if cmp_starts(a, b)? == Ordering::Greater { b } else { a })
+}
+
+/// Of two end bounds, the one that stops letting in values last.
+pub(crate) fn later_end<'a, T: PartialOrd>(a: Bound<&'a T>, b: Bound<&'a T>) -> Option<Bound<&'a T>> {
+    Some(if cmp_ends(a, b)? == Ordering::Less { b } else { a })
+}
+
+/// Returns `Some(true)` if a range ending at `end` finishes before a range beginning at
+/// `start` begins, i.e. there is no value that could be in both.
+pub(crate) fn ends_before<T: PartialOrd>(end: Bound<&T>, start: Bound<&T>) -> Option<bool> {
+    match (end, start) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => Some(false),
+        (Bound::Included(e), Bound::Included(s)) => e.partial_cmp(s).map(|o| o == Ordering::Less),
+        (Bound::Included(e), Bound::Excluded(s))
+        | (Bound::Excluded(e), Bound::Included(s))
+        | (Bound::Excluded(e), Bound::Excluded(s)) => e.partial_cmp(s).map(|o| o != Ordering::Greater),
+    }
+}
+
+/// Returns `Some(true)` if `point` is on the allowed side of the start bound `start`.
+pub(crate) fn after_start<T: PartialOrd>(start: Bound<&T>, point: &T) -> Option<bool> {
+    match start {
+        Bound::Unbounded => Some(true),
+        Bound::Included(s) => s.partial_cmp(point).map(|o| o != Ordering::Greater),
+        Bound::Excluded(s) => s.partial_cmp(point).map(|o| o == Ordering::Less),
+    }
+}
+
+/// Returns `Some(true)` if `point` is on the allowed side of the end bound `end`.
+pub(crate) fn before_end<T: PartialOrd>(end: Bound<&T>, point: &T) -> Option<bool> {
+    match end {
+        Bound::Unbounded => Some(true),
+        Bound::Included(e) => e.partial_cmp(point).map(|o| o != Ordering::Less),
+        Bound::Excluded(e) => e.partial_cmp(point).map(|o| o == Ordering::Greater),
+    }
+}
+
+/// Returns `Some(true)` if no value can lie between `start` and `end`, i.e. the start is
+/// after the end, or they are equal and at least one of them is excluded.
+pub(crate) fn try_is_empty_ref<T: PartialOrd>(start: Bound<&T>, end: Bound<&T>) -> Option<bool> {
+    match (start, end) {
+        (Bound::Unbounded, Bound::Unbounded) => Some(false),
+        // A value not comparable to itself (i.e. NaN) can't tell us which values are in the range
+        (Bound::Included(v) | Bound::Excluded(v), Bound::Unbounded)
+        | (Bound::Unbounded, Bound::Included(v) | Bound::Excluded(v)) => v.partial_cmp(v).map(|_| false),
+        (Bound::Included(s), Bound::Included(e)) => s.partial_cmp(e).map(|o| o == Ordering::Greater),
+        (Bound::Included(s), Bound::Excluded(e))
+        | (Bound::Excluded(s), Bound::Included(e))
+        | (Bound::Excluded(s), Bound::Excluded(e)) => s.partial_cmp(e).map(|o| o != Ordering::Less),
+    }
+}
+
+/// Returns `true` if no value can lie between `start` and `end`. End points that cannot be
+/// compared are treated as empty, since no value can be shown to lie between them.
+pub(crate) fn is_empty_ref<T: PartialOrd>(start: Bound<&T>, end: Bound<&T>) -> bool {
+    try_is_empty_ref(start, end).unwrap_or(true)
+}
+
+/// Classify two ranges given as pairs of bounds, returning `None` if any of the
+/// end points needed could not be compared.
+///
+/// A range with no points in it (e.g. `[5, 5)`) has no overlap with anything,
+/// so if either range is empty, the result is [`RangeOverlap::None`].
+pub(crate) fn try_classify_bound_refs<T: PartialOrd>(
+    a_start: Bound<&T>, a_end: Bound<&T>, b_start: Bound<&T>, b_end: Bound<&T>
+) -> Option<RangeOverlap> {
+    let starts = cmp_starts(a_start, b_start)?;
+    let ends = cmp_ends(a_end, b_end)?;
+    if try_is_empty_ref(a_start, a_end)? || try_is_empty_ref(b_start, b_end)? {
+        return Some(RangeOverlap::None);
+    }
+
+    let overlap = match (starts, ends) {
+        (Ordering::Equal, Ordering::Equal) => RangeOverlap::AEqualsB,
+        (Ordering::Less | Ordering::Equal, Ordering::Greater | Ordering::Equal) => RangeOverlap::AContainsB,
+        (Ordering::Greater | Ordering::Equal, Ordering::Less | Ordering::Equal) => RangeOverlap::AInsideB,
+        _ if ends_before(a_end, b_start)? || ends_before(b_end, a_start)? => RangeOverlap::None,
+        (Ordering::Less, _) => RangeOverlap::AEndsInB,
+        (Ordering::Greater, _) => RangeOverlap::AStartsInB,
+    };
+    Some(overlap)
+}
+
+/// Classify two ranges given as pairs of bounds. If any of the end points cannot be
+/// compared, there is no point that can be shown to be in both ranges, so this returns
+/// [`RangeOverlap::None`].
+pub(crate) fn classify_bound_refs<T: PartialOrd>(
+    a_start: Bound<&T>, a_end: Bound<&T>, b_start: Bound<&T>, b_end: Bound<&T>
+) -> RangeOverlap {
+    try_classify_bound_refs(a_start, a_end, b_start, b_end).unwrap_or(RangeOverlap::None)
+}
+
+/// Classify the kind of overlap between two ranges given as `(start, end)` pairs of [`Bound`]s.
+///
+/// Unlike [`crate::classify_any`], which only lets you choose whether the ends of both ranges
+/// are included, this respects [`Bound::Included`], [`Bound::Excluded`], and [`Bound::Unbounded`]
+/// on every end point independently. If any of the end points cannot be compared (e.g. a NaN),
+/// or either range is empty (e.g. `[5, 5)`), the result is [`RangeOverlap::None`].
+///
+/// ```
+/// # use range_overlap::{classify_bounds, RangeOverlap};
+/// use std::ops::Bound::*;
+///
+/// // [1, 5) vs. (2, 8]
+/// assert_eq!(classify_bounds((Included(1), Excluded(5)), (Excluded(2), Included(8))), RangeOverlap::AEndsInB);
+///
+/// // [1, 5] vs. (5, 8] share no points, but [1, 5] vs. [5, 8] share 5.
+/// assert_eq!(classify_bounds((Included(1), Included(5)), (Excluded(5), Included(8))), RangeOverlap::None);
+/// assert_eq!(classify_bounds((Included(1), Included(5)), (Included(5), Included(8))), RangeOverlap::AEndsInB);
+/// ```
+pub fn classify_bounds<T: PartialOrd>(a: (Bound<T>, Bound<T>), b: (Bound<T>, Bound<T>)) -> RangeOverlap {
+    classify_bound_refs(a.0.as_ref(), a.1.as_ref(), b.0.as_ref(), b.1.as_ref())
+}
+
+/// Classify the kind of overlap between any two std range types, or anything else that implements
+/// [`RangeBounds`]. Like [`classify_bounds`], each end of each range may be included, excluded, or
+/// unbounded, and empty ranges or incomparable end points give [`RangeOverlap::None`]. The two ranges
+/// may be different types, so this one function covers what [`excl_classify`](crate::excl_classify),
+/// [`incl_classify`](crate::incl_classify), and [`classify_any`](crate::classify_any) do, without
+/// having to pick between them.
+///
+/// ```
+/// # use range_overlap::{classify, RangeOverlap};
+/// use std::ops::Bound;
+///
+/// assert_eq!(classify(1..5, ..=10), RangeOverlap::AInsideB);
+/// assert_eq!(classify(1..5, 5..), RangeOverlap::None);
+/// assert_eq!(classify(1..=5, (Bound::Excluded(2), Bound::Unbounded)), RangeOverlap::AEndsInB);
+/// assert_eq!(classify((Bound::Excluded(1), Bound::Excluded(5)), 1..5), RangeOverlap::AInsideB);
+/// ```
+pub fn classify<T: PartialOrd, A: RangeBounds<T>, B: RangeBounds<T>>(a: A, b: B) -> RangeOverlap {
+    classify_range_bounds(&a, &b)
+}
+
+/// Like [`classify`], but borrowing the two ranges, so that ranges of types that are expensive to clone
+/// can be used again afterwards.
+///
+/// ```
+/// # use range_overlap::{classify_range_bounds, RangeOverlap};
+/// let (a, b) = ("apple".to_string().."cherry".to_string(), "banana".to_string()..);
+/// assert_eq!(classify_range_bounds(&a, &b), RangeOverlap::AEndsInB);
+/// assert_eq!(classify_range_bounds(&b, &a), RangeOverlap::AStartsInB);
+/// ```
+pub fn classify_range_bounds<T: PartialOrd, A: RangeBounds<T>, B: RangeBounds<T>>(a: &A, b: &B) -> RangeOverlap {
+    classify_bound_refs(a.start_bound(), a.end_bound(), b.start_bound(), b.end_bound())
+}
+
+/// Adds [`overlaps`](Overlaps::overlaps) and [`classify`](Overlaps::classify) methods to the std range
+/// types (`Range`, `RangeInclusive`, `RangeFrom`, `RangeTo`, `RangeToInclusive`, `RangeFull`, and pairs of
+/// [`Bound`]s), and anything else that implements [`RangeBounds`]. The two ranges need not be the same
+/// type, so e.g. a `Range` can be checked against a `RangeInclusive`.
+///
+/// ```
+/// # use range_overlap::{Overlaps, RangeOverlap};
+/// assert!((1..5).overlaps(&(3..=8)));
+/// assert!(!(1..5).overlaps(&(5..)));
+/// assert_eq!((1..5).classify(&(3..8)), RangeOverlap::AEndsInB);
+/// assert_eq!((..).classify(&(3..8)), RangeOverlap::AContainsB);
+/// ```
+pub trait Overlaps<T: PartialOrd>: RangeBounds<T> {
+    /// Returns `true` if this range and `other` have any values in common. Empty ranges, and ranges
+    /// whose end points cannot be compared, overlap nothing.
+    fn overlaps<R: RangeBounds<T> + ?Sized>(&self, other: &R) -> bool {
+        self.classify(other).has_overlap()
+    }
+
+    /// Classify how this range overlaps `other`, as the free function [`classify`](crate::classify) does.
+    fn classify<R: RangeBounds<T> + ?Sized>(&self, other: &R) -> RangeOverlap {
+        classify_bound_refs(self.start_bound(), self.end_bound(), other.start_bound(), other.end_bound())
+    }
+}
+
+impl<T: PartialOrd, R: RangeBounds<T> + ?Sized> Overlaps<T> for R {}
+
+/// Which ends of a closed range are part of it, for use with [`classify_with_inclusivity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Inclusivity {
+    /// Whether the start value is part of the range.
+    pub start: bool,
+
+    /// Whether the end value is part of the range.
+    pub end: bool,
+}
+
+impl Inclusivity {
+    /// Both ends are included, like `[start, end]`.
+    pub const BOTH: Self = Self { start: true, end: true };
+
+    /// Only the start is included, like `[start, end)`. This is what [`crate::excl_classify`] assumes.
+    pub const START_ONLY: Self = Self { start: true, end: false };
+
+    /// Only the end is included, like `(start, end]`.
+    pub const END_ONLY: Self = Self { start: false, end: true };
+
+    /// Neither end is included, like `(start, end)`.
+    pub const NEITHER: Self = Self { start: false, end: false };
+
+    fn bounds<T>(self, start: T, end: T) -> (Bound<T>, Bound<T>) {
+        let start = if self.start { Bound::Included(start) } else { Bound::Excluded(start) };
+        let end = if self.end { Bound::Included(end) } else { Bound::Excluded(end) };
+        (start, end)
+    }
+}
+
+/// Classify the kind of overlap between two closed ranges, choosing separately whether
+/// each of the four end points is part of its range.
+///
+/// ```
+/// # use range_overlap::{classify_with_inclusivity, Inclusivity, RangeOverlap};
+/// // [1, 5) vs. (5, 8]
+/// assert_eq!(classify_with_inclusivity(1, 5, Inclusivity::START_ONLY, 5, 8, Inclusivity::END_ONLY), RangeOverlap::None);
+/// // [1, 5] vs. [5, 8)
+/// assert_eq!(classify_with_inclusivity(1, 5, Inclusivity::BOTH, 5, 8, Inclusivity::START_ONLY), RangeOverlap::AEndsInB);
+/// ```
+pub fn classify_with_inclusivity<T: PartialOrd>(a_start: T, a_end: T, a_incl: Inclusivity, b_start: T, b_end: T, b_incl: Inclusivity) -> RangeOverlap {
+    classify_bounds(a_incl.bounds(a_start, a_end), b_incl.bounds(b_start, b_end))
+}
+
+/// Returns `true` if `point` lies between `start` and `end`. Incomparable values are
+/// treated as not contained.
+pub(crate) fn contains_point_ref<T: PartialOrd>(start: Bound<&T>, end: Bound<&T>, point: &T) -> bool {
+    after_start(start, point).unwrap_or(false) && before_end(end, point).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{excl_classify, incl_classify};
+
+    #[test]
+    fn test_matches_closed_classifiers() {
+        // Every pair of non-degenerate ranges drawn from 0..6 must classify the same
+        // way as the original closed-range functions.
+        for a_start in 0..6 {
+            for a_end in (a_start + 1)..6 {
+                for b_start in 0..6 {
+                    for b_end in (b_start + 1)..6 {
+                        let excl = classify_bound_refs(
+                            Bound::Included(&a_start), Bound::Excluded(&a_end),
+                            Bound::Included(&b_start), Bound::Excluded(&b_end)
+                        );
+                        assert_eq!(excl, excl_classify(a_start, a_end, b_start, b_end), "[{a_start}, {a_end}) vs [{b_start}, {b_end})");
+
+                        let incl = classify_bound_refs(
+                            Bound::Included(&a_start), Bound::Included(&a_end),
+                            Bound::Included(&b_start), Bound::Included(&b_end)
+                        );
+                        assert_eq!(incl, incl_classify(a_start, a_end, b_start, b_end), "[{a_start}, {a_end}] vs [{b_start}, {b_end}]");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify_bounds() {
+        use Bound::*;
+
+        // Excluded starts
+        assert_eq!(classify_bounds((Excluded(1), Excluded(5)), (Included(1), Excluded(5))), RangeOverlap::AInsideB);
+        assert_eq!(classify_bounds((Included(1), Excluded(5)), (Excluded(1), Excluded(5))), RangeOverlap::AContainsB);
+        assert_eq!(classify_bounds((Excluded(1), Included(5)), (Excluded(1), Included(5))), RangeOverlap::AEqualsB);
+        assert_eq!(classify_bounds((Excluded(1), Included(5)), (Included(1), Excluded(5))), RangeOverlap::AStartsInB);
+        assert_eq!(classify_bounds((Included(1), Excluded(5)), (Excluded(1), Included(5))), RangeOverlap::AEndsInB);
+
+        // Touching end points
+        assert_eq!(classify_bounds((Included(1), Excluded(5)), (Included(5), Included(8))), RangeOverlap::None);
+        assert_eq!(classify_bounds((Included(1), Included(5)), (Excluded(5), Included(8))), RangeOverlap::None);
+        assert_eq!(classify_bounds((Included(1), Included(5)), (Included(5), Included(8))), RangeOverlap::AEndsInB);
+        assert_eq!(classify_bounds((Included(5), Included(8)), (Included(1), Included(5))), RangeOverlap::AStartsInB);
+        assert_eq!(classify_bounds((Excluded(5), Included(8)), (Included(1), Included(5))), RangeOverlap::None);
+
+        // Unbounded sides
+        assert_eq!(classify_bounds((Unbounded, Excluded(5)), (Excluded(2), Unbounded)), RangeOverlap::AEndsInB);
+        assert_eq!(classify_bounds((Unbounded, Excluded(5)), (Included(5), Unbounded)), RangeOverlap::None);
+        assert_eq!(classify_bounds((Unbounded, Included(5)), (Included(5), Unbounded)), RangeOverlap::AEndsInB);
+        assert_eq!(classify_bounds::<i32>((Unbounded, Unbounded), (Unbounded, Unbounded)), RangeOverlap::AEqualsB);
+        assert_eq!(classify_bounds((Unbounded, Unbounded), (Excluded(2), Included(3))), RangeOverlap::AContainsB);
+
+        // Empty ranges
+        assert_eq!(classify_bounds((Included(5), Excluded(5)), (Included(1), Included(10))), RangeOverlap::None);
+        assert_eq!(classify_bounds((Excluded(5), Included(5)), (Included(5), Included(5))), RangeOverlap::None);
+        assert_eq!(classify_bounds((Included(1), Included(10)), (Excluded(5), Excluded(5))), RangeOverlap::None);
+        assert_eq!(classify_bounds((Included(5), Included(5)), (Included(5), Included(5))), RangeOverlap::AEqualsB);
+        assert_eq!(classify_bounds((Unbounded, Unbounded), (Included(6), Included(5))), RangeOverlap::None);
+    }
+
+    /// Classify two ranges by checking which of a set of sample points are in each.
+    /// The samples must include every end point and a value between each pair of end points.
+    fn classify_by_sampling(a: (Bound<f64>, Bound<f64>), b: (Bound<f64>, Bound<f64>), samples: &[f64]) -> RangeOverlap {
+        let in_a: Vec<bool> = samples.iter().map(|p| contains_point_ref(a.0.as_ref(), a.1.as_ref(), p)).collect();
+        let in_b: Vec<bool> = samples.iter().map(|p| contains_point_ref(b.0.as_ref(), b.1.as_ref(), p)).collect();
+        let a_sub_b = in_a.iter().zip(&in_b).all(|(&a, &b)| !a || b);
+        let b_sub_a = in_a.iter().zip(&in_b).all(|(&a, &b)| !b || a);
+        let disjoint = in_a.iter().zip(&in_b).all(|(&a, &b)| !(a && b));
+
+        if a_sub_b && b_sub_a {
+            RangeOverlap::AEqualsB
+        } else if b_sub_a {
+            RangeOverlap::AContainsB
+        } else if a_sub_b {
+            RangeOverlap::AInsideB
+        } else if disjoint {
+            RangeOverlap::None
+        } else if in_a.iter().position(|&x| x) < in_b.iter().position(|&x| x) {
+            RangeOverlap::AEndsInB
+        } else {
+            RangeOverlap::AStartsInB
+        }
+    }
+
+    #[test]
+    fn test_inclusivity_exhaustive() {
+        let kinds = [Inclusivity::BOTH, Inclusivity::START_ONLY, Inclusivity::END_ONLY, Inclusivity::NEITHER];
+        let samples: Vec<f64> = (0..=10).map(|i| i as f64 * 0.5).collect();
+
+        for a_incl in kinds {
+            for b_incl in kinds {
+                for a_start in 0..5 {
+                    for a_end in (a_start + 1)..5 {
+                        for b_start in 0..5 {
+                            for b_end in (b_start + 1)..5 {
+                                let (a_start, a_end, b_start, b_end) = (a_start as f64, a_end as f64, b_start as f64, b_end as f64);
+                                let expected = classify_by_sampling(a_incl.bounds(a_start, a_end), b_incl.bounds(b_start, b_end), &samples);
+                                assert_eq!(
+                                    classify_with_inclusivity(a_start, a_end, a_incl, b_start, b_end, b_incl), expected,
+                                    "{a_start}, {a_end}, {a_incl:?} vs. {b_start}, {b_end}, {b_incl:?}"
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify_every_bound_kind() {
+        // Every start and end bound drawn from 0..4, with samples reaching past them for the unbounded ends
+        let mut ends = vec![Bound::Unbounded];
+        ends.extend((0..4).flat_map(|v| [Bound::Included(v as f64), Bound::Excluded(v as f64)]));
+        let samples: Vec<f64> = (-2..=8).map(|i| i as f64 * 0.5).collect();
+        let value = |b: &Bound<f64>| match b {
+            Bound::Included(v) | Bound::Excluded(v) => Some(*v),
+            Bound::Unbounded => None,
+        };
+        // Empty ranges overlap nothing, unlike the sampled classification, so leave them out
+        let ranges: Vec<(Bound<f64>, Bound<f64>)> = ends.iter().flat_map(|&start| ends.iter().map(move |&end| (start, end)))
+            .filter(|(start, end)| match (value(start), value(end)) {
+                (Some(s), Some(e)) => s < e,
+                _ => true,
+            })
+            .collect();
+        for &a in &ranges {
+            for &b in &ranges {
+                assert_eq!(classify(a, b), classify_by_sampling(a, b, &samples), "{a:?} vs. {b:?}");
+                assert_eq!(classify_range_bounds(&a, &b), classify(a, b), "{a:?} vs. {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_inclusivity_touching() {
+        use Inclusivity as I;
+
+        // A's end touching B's start only overlaps if both are included
+        for a_incl in [I::BOTH, I::START_ONLY, I::END_ONLY, I::NEITHER] {
+            for b_incl in [I::BOTH, I::START_ONLY, I::END_ONLY, I::NEITHER] {
+                let expected = if a_incl.end && b_incl.start { RangeOverlap::AEndsInB } else { RangeOverlap::None };
+                assert_eq!(classify_with_inclusivity(1, 5, a_incl, 5, 9, b_incl), expected);
+
+                let expected = if a_incl.start && b_incl.end { RangeOverlap::AStartsInB } else { RangeOverlap::None };
+                assert_eq!(classify_with_inclusivity(5, 9, a_incl, 1, 5, b_incl), expected);
+            }
+        }
+
+        // Shared start, different ends
+        assert_eq!(classify_with_inclusivity(1, 5, I::BOTH, 1, 9, I::END_ONLY), RangeOverlap::AEndsInB);
+        assert_eq!(classify_with_inclusivity(1, 5, I::END_ONLY, 1, 9, I::BOTH), RangeOverlap::AInsideB);
+        assert_eq!(classify_with_inclusivity(1, 9, I::END_ONLY, 1, 5, I::BOTH), RangeOverlap::AStartsInB);
+
+        // Shared end, different starts
+        assert_eq!(classify_with_inclusivity(1, 9, I::BOTH, 5, 9, I::START_ONLY), RangeOverlap::AContainsB);
+        assert_eq!(classify_with_inclusivity(1, 9, I::START_ONLY, 5, 9, I::BOTH), RangeOverlap::AEndsInB);
+
+        // Same values
+        assert_eq!(classify_with_inclusivity(1, 9, I::NEITHER, 1, 9, I::NEITHER), RangeOverlap::AEqualsB);
+        assert_eq!(classify_with_inclusivity(1, 9, I::NEITHER, 1, 9, I::START_ONLY), RangeOverlap::AInsideB);
+        assert_eq!(classify_with_inclusivity(1, 9, I::START_ONLY, 1, 9, I::END_ONLY), RangeOverlap::AEndsInB);
+        assert_eq!(classify_with_inclusivity(1, 9, I::END_ONLY, 1, 9, I::START_ONLY), RangeOverlap::AStartsInB);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert!(is_empty_ref(Bound::Included(&5), Bound::Excluded(&5)));
+        assert!(is_empty_ref(Bound::Excluded(&5), Bound::Excluded(&5)));
+        assert!(is_empty_ref(Bound::Included(&6), Bound::Included(&5)));
+        assert!(!is_empty_ref(Bound::Included(&5), Bound::Included(&5)));
+        assert!(!is_empty_ref(Bound::Unbounded, Bound::Excluded(&5)));
+        assert!(!is_empty_ref::<i32>(Bound::Unbounded, Bound::Unbounded));
+        assert!(is_empty_ref(Bound::Included(&f64::NAN), Bound::Unbounded));
+        assert!(is_empty_ref(Bound::Included(&f64::NAN), Bound::Included(&1.0)));
+    }
+
+    #[test]
+    fn test_incomparable() {
+        let nan = f64::NAN;
+        assert_eq!(try_classify_bound_refs(Bound::Included(&nan), Bound::Included(&1.0), Bound::Included(&0.0), Bound::Included(&1.0)), None);
+        assert_eq!(classify_bound_refs(Bound::Included(&nan), Bound::Included(&1.0), Bound::Included(&0.0), Bound::Included(&1.0)), RangeOverlap::None);
+        assert!(!contains_point_ref(Bound::Included(&0.0), Bound::Unbounded, &nan));
+    }
+
+    #[test]
+    fn test_classify_std_ranges() {
+        assert_eq!(classify(1..5, 1..5), RangeOverlap::AEqualsB);
+        assert_eq!(classify(1..5, 1..=5), RangeOverlap::AInsideB);
+        assert_eq!(classify(.., 1..5), RangeOverlap::AContainsB);
+        assert_eq!(classify(..5, 4..), RangeOverlap::AEndsInB);
+        assert_eq!(classify(..=5, 5..), RangeOverlap::AEndsInB);
+        assert_eq!(classify(5.., ..5), RangeOverlap::None);
+        assert_eq!(classify(5..5, ..), RangeOverlap::None);
+        assert_eq!(classify((Bound::Excluded(1), Bound::Included(5)), 0..2), RangeOverlap::AStartsInB);
+
+        assert_eq!(classify(1.0..2.0, 1.5..), RangeOverlap::AEndsInB);
+        assert_eq!(classify(1.0..2.0, f64::NAN..), RangeOverlap::None);
+
+        // Anything implementing RangeBounds works, including this crate's own types
+        assert_eq!(classify(crate::Interval::incl(1, 5), 5..), RangeOverlap::AEndsInB);
+    }
+
+    #[test]
+    fn test_overlaps_trait() {
+        assert!((1..5).overlaps(&(4..)));
+        assert!((..=5).overlaps(&(5..)));
+        assert!(!(..5).overlaps(&(5..)));
+        assert!(!(5..5).overlaps(&(..)));
+        assert!(!(1.0..2.0).overlaps(&(f64::NAN..)));
+        assert_eq!((1..=5).classify(&(1..=5)), RangeOverlap::AEqualsB);
+        assert_eq!((Bound::Excluded(1), Bound::Included(5)).classify(&(0..2)), RangeOverlap::AStartsInB);
+        assert_eq!((1..5).classify(&crate::Interval::incl(1, 5)), RangeOverlap::AInsideB);
+
+        // The method and the free function agree everywhere
+        for a in [0..3, 2..4, 4..4] {
+            for b in [1..=2, 3..=5, std::ops::RangeInclusive::new(3, 2)] {
+                assert_eq!(a.classify(&b), classify(a.clone(), b.clone()), "{a:?} {b:?}");
+                assert_eq!(a.overlaps(&b), classify(a.clone(), b.clone()).has_overlap(), "{a:?} {b:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Versions of the closed-range classifiers for integer end points that compute the answer without
+//! branching.
+//!
+//! [`excl_classify`](crate::excl_classify) and [`incl_classify`](crate::incl_classify) test one case
+//! after another, so how fast they are depends on how well the processor guesses which case comes next.
+//! When the kinds of overlap in a loop follow no pattern, the guesses are often wrong. The functions here
+//! instead work out every test at once, as bits of one number, and pick the first that holds by counting
+//! its trailing zeros, which takes the same few instructions whatever the answer is.
+use crate::RangeOverlap;
+
+/// The answer for each test, in the order the branching classifiers make them, with the answer when none
+/// of them hold last.
+const BY_FIRST_TEST: [RangeOverlap; 6] = [
+    RangeOverlap::AEqualsB,
+    RangeOverlap::AContainsB,
+    RangeOverlap::AEndsInB,
+    RangeOverlap::AStartsInB,
+    RangeOverlap::None,
+    RangeOverlap::AInsideB,
+];
+
+/// Pick the answer for the first of the tests that holds, given as bits in the order of [`BY_FIRST_TEST`].
+#[inline(always)]
+fn first_test(tests: [bool; 5]) -> RangeOverlap {
+    let mut bits = 1u8 << 5;
+    for (i, test) in tests.into_iter().enumerate() {
+        bits |= (test as u8) << i;
+    }
+    BY_FIRST_TEST[bits.trailing_zeros() as usize]
+}
+
+/// Gives the same answer as [`excl_classify`](crate::excl_classify), but without branches, for use in tight
+/// loops over unpredictable ranges. This is meant for primitive integers; for other types it is correct,
+/// but unlikely to be faster.
+///
+/// ```
+/// # use range_overlap::{excl_classify_branchless, RangeOverlap};
+/// assert_eq!(excl_classify_branchless(0, 10, 5, 15), RangeOverlap::AEndsInB);
+/// assert_eq!(excl_classify_branchless(0, 10, 10, 15), RangeOverlap::None);
+/// ```
+#[inline]
+pub fn excl_classify_branchless<T: Ord + Copy>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
+    first_test([
+        (a_start == b_start) & (a_end == b_end),
+        (a_start <= b_start) & (a_end >= b_end),
+        (a_start < b_start) & (a_end > b_start) & (a_end <= b_end),
+        (a_start > b_start) & (a_start < b_end) & (a_end > b_end),
+        (a_start >= b_end) | (b_start >= a_end),
+    ])
+}
+
+/// Gives the same answer as [`incl_classify`](crate::incl_classify), but without branches, for use in tight
+/// loops over unpredictable ranges. This is meant for primitive integers; for other types it is correct,
+/// but unlikely to be faster.
+///
+/// ```
+/// # use range_overlap::{incl_classify_branchless, RangeOverlap};
+/// assert_eq!(incl_classify_branchless(0, 10, 10, 15), RangeOverlap::AEndsInB);
+/// assert_eq!(incl_classify_branchless(2, 3, 0, 10), RangeOverlap::AInsideB);
+/// ```
+#[inline]
+pub fn incl_classify_branchless<T: Ord + Copy>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
+    first_test([
+        (a_start == b_start) & (a_end == b_end),
+        (a_start <= b_start) & (a_end >= b_end),
+        (a_start < b_start) & (a_end >= b_start) & (a_end <= b_end),
+        (a_start > b_start) & (a_start <= b_end) & (a_end > b_end),
+        (a_start > b_end) | (b_start > a_end),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{excl_classify, incl_classify};
+
+    #[test]
+    fn test_matches_branching() {
+        // Every combination of small end points, including empty and backwards ranges
+        for a_start in 0..5u8 {
+            for a_end in 0..5 {
+                for b_start in 0..5 {
+                    for b_end in 0..5 {
+                        let ends = (a_start, a_end, b_start, b_end);
+                        assert_eq!(excl_classify_branchless(a_start, a_end, b_start, b_end), excl_classify(a_start, a_end, b_start, b_end), "{ends:?}");
+                        assert_eq!(incl_classify_branchless(a_start, a_end, b_start, b_end), incl_classify(a_start, a_end, b_start, b_end), "{ends:?}");
+                        let [a_start, a_end, b_start, b_end] = [a_start, a_end, b_start, b_end].map(|v| v as i64 - 2);
+                        assert_eq!(excl_classify_branchless(a_start, a_end, b_start, b_end), excl_classify(a_start, a_end, b_start, b_end), "{ends:?}");
+                        assert_eq!(incl_classify_branchless(a_start, a_end, b_start, b_end), incl_classify(a_start, a_end, b_start, b_end), "{ends:?}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! A builder for [`Interval`]s that are assembled one end at a time.
+use std::ops::Bound;
+
+use crate::{Interval, RangeError};
+
+/// Builds an [`Interval`] one end at a time. Any end that is not set is open.
+///
+/// This is created by [`Interval::builder`]. Calling [`IntervalBuilder::build`] checks that
+/// the start is not after the end, the same as [`Interval::new`].
+///
+/// ```
+/// # use range_overlap::Interval;
+/// let interval = Interval::builder().start(1).end_exclusive(10).build().unwrap();
+/// assert_eq!(interval, Interval::excl(1, 10));
+///
+/// // Ends that are never set are open
+/// let interval = Interval::builder().start_exclusive(1).build().unwrap();
+/// assert_eq!(interval, Interval::greater_than(1));
+///
+/// assert!(Interval::builder().start(10).end_inclusive(1).build().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalBuilder<T> {
+    start: Bound<T>,
+    end: Bound<T>,
+}
+
+impl<T> Default for IntervalBuilder<T> {
+    fn default() -> Self {
+        Self { start: Bound::Unbounded, end: Bound::Unbounded }
+    }
+}
+
+impl<T> IntervalBuilder<T> {
+    /// Create a builder for an interval that is open on both ends.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start the interval at `value`, including it.
+    pub fn start(mut self, value: T) -> Self {
+        self.start = Bound::Included(value);
+        self
+    }
+
+    /// Start the interval just after `value`, excluding it.
+    pub fn start_exclusive(mut self, value: T) -> Self {
+        self.start = Bound::Excluded(value);
+        self
+    }
+
+    /// Make the start of the interval open.
+    pub fn unbounded_start(mut self) -> Self {
+        self.start = Bound::Unbounded;
+        self
+    }
+
+    /// End the interval at `value`, including it.
+    pub fn end_inclusive(mut self, value: T) -> Self {
+        self.end = Bound::Included(value);
+        self
+    }
+
+    /// End the interval just before `value`, excluding it.
+    pub fn end_exclusive(mut self, value: T) -> Self {
+        self.end = Bound::Excluded(value);
+        self
+    }
+
+    /// Make the end of the interval open.
+    pub fn unbounded_end(mut self) -> Self {
+        self.end = Bound::Unbounded;
+        self
+    }
+
+    /// Build the interval without checking that its start is not after its end.
+    pub fn build_unchecked(self) -> Interval<T> {
+        Interval::from_bounds(self.start, self.end)
+    }
+}
+
+impl<T: PartialOrd> IntervalBuilder<T> {
+    /// Build the interval, returning an error if its start is after its end or
+    /// its end points cannot be compared. See [`Interval::validate`].
+    pub fn build(self) -> Result<Interval<T>, RangeError> {
+        let interval = self.build_unchecked();
+        interval.validate()?;
+        Ok(interval)
+    }
+}
+
+impl<T> Interval<T> {
+    /// Start building an interval one end at a time. See [`IntervalBuilder`].
+    pub fn builder() -> IntervalBuilder<T> {
+        IntervalBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WhichRange;
+
+    #[test]
+    fn test_builder() {
+        assert_eq!(Interval::<i32>::builder().build(), Ok(Interval::all()));
+        assert_eq!(Interval::builder().start(1).build(), Ok(Interval::at_least(1)));
+        assert_eq!(Interval::builder().end_inclusive(1).build(), Ok(Interval::at_most(1)));
+        assert_eq!(Interval::builder().end_exclusive(1).build(), Ok(Interval::less_than(1)));
+        assert_eq!(Interval::builder().start(1).end_inclusive(5).build(), Ok(Interval::incl(1, 5)));
+        assert_eq!(
+            Interval::builder().start_exclusive(1).end_exclusive(5).build(),
+            Ok(Interval::from_bounds(Bound::Excluded(1), Bound::Excluded(5)))
+        );
+
+        // Later calls replace earlier ones
+        assert_eq!(Interval::builder().start(1).end_inclusive(5).unbounded_end().start(2).build(), Ok(Interval::at_least(2)));
+        assert_eq!(Interval::builder().start(1).unbounded_start().build_unchecked(), Interval::<i32>::all());
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        assert_eq!(Interval::builder().start(5).end_exclusive(1).build(), Err(RangeError::StartAfterEnd { which: WhichRange::A }));
+        assert_eq!(Interval::builder().start(f64::NAN).build(), Err(RangeError::IncomparableEndpoints));
+        assert_eq!(Interval::builder().start(5).end_exclusive(1).build_unchecked(), Interval::excl(5, 1));
+    }
+}
@@ -0,0 +1,166 @@
+//! Versions of the classification functions that report incomparable end points and malformed ranges as errors.
+//!
+//! The plain classification functions assume every pair of end points can be ordered. For
+//! floats, a NaN end point breaks that assumption: every comparison with it is `false`, so
+//! it falls through the comparison chain and produces a variant that looks reasonable but
+//! means nothing. The `try_*` functions here check that every end point can be compared with
+//! every other one first, and return [`RangeError::IncomparableEndpoints`] if not. [`classify_strict`]
+//! additionally rejects ranges whose start is after their end.
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::{classify_any, excl_classify, incl_classify, Interval, RangeError, RangeOverlap, WhichRange};
+
+/// Returns an error if any two of `values` (including a value and itself) cannot be compared.
+fn check_comparable<T: PartialOrd>(values: &[Option<&T>]) -> Result<(), RangeError> {
+    for (i, a) in values.iter().enumerate() {
+        let Some(a) = a else { continue };
+        if values[i..].iter().flatten().any(|b| a.partial_cmp(b).is_none()) {
+            return Err(RangeError::IncomparableEndpoints);
+        }
+    }
+    Ok(())
+}
+
+fn bound_value<T>(bound: Bound<&T>) -> Option<&T> {
+    match bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(v),
+        Bound::Unbounded => None,
+    }
+}
+
+/// Like [`excl_classify`], but returns [`RangeError::IncomparableEndpoints`] if any of the
+/// end points cannot be compared to the others.
+///
+/// ```
+/// # use range_overlap::{try_excl_classify, RangeError, RangeOverlap};
+/// assert_eq!(try_excl_classify(1.0, 5.0, 2.0, 8.0), Ok(RangeOverlap::AEndsInB));
+/// assert_eq!(try_excl_classify(1.0, f64::NAN, 2.0, 8.0), Err(RangeError::IncomparableEndpoints));
+/// ```
+pub fn try_excl_classify<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> Result<RangeOverlap, RangeError> {
+    check_comparable(&[Some(&a_start), Some(&a_end), Some(&b_start), Some(&b_end)])?;
+    Ok(excl_classify(a_start, a_end, b_start, b_end))
+}
+
+/// Like [`incl_classify`], but returns [`RangeError::IncomparableEndpoints`] if any of the
+/// end points cannot be compared to the others.
+pub fn try_incl_classify<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> Result<RangeOverlap, RangeError> {
+    check_comparable(&[Some(&a_start), Some(&a_end), Some(&b_start), Some(&b_end)])?;
+    Ok(incl_classify(a_start, a_end, b_start, b_end))
+}
+
+/// Like [`classify_any`], but returns [`RangeError::IncomparableEndpoints`] if any of the
+/// end points given cannot be compared to the others. Open sides are never incomparable.
+pub fn try_classify_any<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> Result<RangeOverlap, RangeError> {
+    check_comparable(&[a_start.as_ref(), a_end.as_ref(), b_start.as_ref(), b_end.as_ref()])?;
+    Ok(classify_any(a_start, a_end, b_start, b_end, inclusive))
+}
+
+/// Like [`classify_bounds`](crate::classify_bounds), but returns [`RangeError::IncomparableEndpoints`]
+/// if any of the end points cannot be compared to the others, rather than [`RangeOverlap::None`].
+pub fn try_classify_bounds<T: PartialOrd>(a: (Bound<T>, Bound<T>), b: (Bound<T>, Bound<T>)) -> Result<RangeOverlap, RangeError> {
+    let [a_start, a_end, b_start, b_end] = [a.0.as_ref(), a.1.as_ref(), b.0.as_ref(), b.1.as_ref()];
+    check_comparable(&[a_start, a_end, b_start, b_end].map(bound_value))?;
+    Ok(bounds::classify_bound_refs(a_start, a_end, b_start, b_end))
+}
+
+/// Returns `Ok(true)` if both ends are given and the start is after the end.
+fn is_reversed<T: PartialOrd>(start: &Option<T>, end: &Option<T>) -> Result<bool, RangeError> {
+    match (start, end) {
+        (Some(s), Some(e)) => s.partial_cmp(e).map(|o| o == Ordering::Greater).ok_or(RangeError::IncomparableEndpoints),
+        _ => Ok(false),
+    }
+}
+
+/// Like [`try_classify_any`], but also returns [`RangeError::StartAfterEnd`] if either range has its start
+/// after its end, rather than classifying it anyway. A start equal to its end is allowed.
+///
+/// ```
+/// # use range_overlap::{classify_any, classify_strict, RangeError, RangeOverlap, WhichRange};
+/// // classify_any gives an answer for a reversed range, but it means nothing
+/// assert_eq!(classify_any(Some(1), Some(5), Some(9), Some(2), false), RangeOverlap::AContainsB);
+/// assert_eq!(
+///     classify_strict(Some(1), Some(5), Some(9), Some(2), false),
+///     Err(RangeError::StartAfterEnd { which: WhichRange::B })
+/// );
+/// assert_eq!(classify_strict(Some(1), Some(5), Some(2), None, false), Ok(RangeOverlap::AEndsInB));
+/// ```
+pub fn classify_strict<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> Result<RangeOverlap, RangeError> {
+    check_comparable(&[a_start.as_ref(), a_end.as_ref(), b_start.as_ref(), b_end.as_ref()])?;
+    match (is_reversed(&a_start, &a_end)?, is_reversed(&b_start, &b_end)?) {
+        (true, true) => Err(RangeError::StartAfterEnd { which: WhichRange::Both }),
+        (true, false) => Err(RangeError::StartAfterEnd { which: WhichRange::A }),
+        (false, true) => Err(RangeError::StartAfterEnd { which: WhichRange::B }),
+        (false, false) => Ok(classify_any(a_start, a_end, b_start, b_end, inclusive)),
+    }
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// Like [`Interval::classify_against`], but returns [`RangeError::IncomparableEndpoints`] if any
+    /// of the end points of either interval cannot be compared to the others.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeError};
+    /// let a = Interval::at_least(f64::NAN);
+    /// assert_eq!(a.try_classify_against(&Interval::all()), Err(RangeError::IncomparableEndpoints));
+    /// ```
+    pub fn try_classify_against(&self, other: &Interval<T>) -> Result<RangeOverlap, RangeError> {
+        check_comparable(&[self.start(), self.end(), other.start(), other.end()].map(bound_value))?;
+        Ok(self.classify_against(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_unchecked() {
+        for a_start in 0..5 {
+            for a_end in 0..5 {
+                for b_start in 0..5 {
+                    for b_end in 0..5 {
+                        assert_eq!(try_excl_classify(a_start, a_end, b_start, b_end), Ok(excl_classify(a_start, a_end, b_start, b_end)));
+                        assert_eq!(try_incl_classify(a_start, a_end, b_start, b_end), Ok(incl_classify(a_start, a_end, b_start, b_end)));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(try_classify_any(None, Some(5), Some(5), None, true), Ok(RangeOverlap::AEndsInB));
+        assert_eq!(try_classify_bounds((Bound::Included(1), Bound::Excluded(5)), (Bound::Excluded(5), Bound::Unbounded)), Ok(RangeOverlap::None));
+        assert_eq!(Interval::excl(1, 5).try_classify_against(&Interval::incl(2, 3)), Ok(RangeOverlap::AContainsB));
+    }
+
+    #[test]
+    fn test_nan() {
+        let nan = f64::NAN;
+        let err = Err(RangeError::IncomparableEndpoints);
+
+        // A NaN anywhere is caught, even where the unchecked version gives a plausible answer
+        assert_eq!(excl_classify(nan, 5.0, 1.0, 8.0), RangeOverlap::AInsideB);
+        assert_eq!(try_excl_classify(nan, 5.0, 1.0, 8.0), err);
+        assert_eq!(try_excl_classify(1.0, 5.0, 1.0, nan), err);
+        assert_eq!(try_incl_classify(1.0, 5.0, nan, 8.0), err);
+
+        assert_eq!(try_classify_any(None, None, None, Some(nan), false), err);
+        assert_eq!(try_classify_any(None, Some(1.0), None, None, false), Ok(RangeOverlap::AInsideB));
+        assert_eq!(try_classify_bounds((Bound::Unbounded, Bound::Unbounded), (Bound::Excluded(nan), Bound::Unbounded)), err);
+        assert_eq!(Interval::incl(0.0, 1.0).try_classify_against(&Interval::less_than(nan)), err);
+    }
+
+    #[test]
+    fn test_strict() {
+        let reversed = |which| Err(RangeError::StartAfterEnd { which });
+        assert_eq!(classify_strict(Some(5), Some(1), Some(2), Some(8), true), reversed(WhichRange::A));
+        assert_eq!(classify_strict(Some(1), Some(5), Some(8), Some(2), true), reversed(WhichRange::B));
+        assert_eq!(classify_strict(Some(5), Some(1), Some(8), Some(2), true), reversed(WhichRange::Both));
+        assert_eq!(classify_strict(Some(5.0), Some(1.0), Some(f64::NAN), None, true), Err(RangeError::IncomparableEndpoints));
+
+        for inclusive in [false, true] {
+            assert_eq!(classify_strict(Some(5), Some(5), Some(2), Some(8), inclusive), Ok(classify_any(Some(5), Some(5), Some(2), Some(8), inclusive)));
+            assert_eq!(classify_strict(Some(5), None, None, Some(1), inclusive), Ok(RangeOverlap::None));
+        }
+    }
+}
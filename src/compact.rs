@@ -0,0 +1,153 @@
+//! A compact form of [`IntervalSet`] for storing or sending sets with many intervals.
+use std::ops::Bound;
+
+use crate::{Interval, IntervalSet};
+
+/// An [`IntervalSet`] stored as the sorted list of values where membership changes, rather than as a list
+/// of intervals. Walking up from the lowest values, each boundary switches from outside the set to inside
+/// or back, so only one value per boundary is needed, plus one bit saying whether the boundary value is
+/// itself in the set. This takes about half the memory of the intervals, and its parts are simple to write
+/// out and read back.
+///
+/// ```
+/// # use range_overlap::{CompactIntervalSet, Interval, IntervalSet};
+/// let set: IntervalSet<_> = [Interval::excl(1, 3), Interval::incl(5, 8), Interval::greater_than(10)].into_iter().collect();
+/// let compact = CompactIntervalSet::from(&set);
+/// assert_eq!(compact.boundaries(), [1, 3, 5, 8, 10]);
+/// assert!(!compact.is_included(1) && compact.is_included(3) && !compact.is_included(4));
+///
+/// let (starts_inside, boundaries, included) = compact.into_parts();
+/// let restored = IntervalSet::from(CompactIntervalSet::from_parts(starts_inside, boundaries, included));
+/// assert_eq!(restored, set);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompactIntervalSet<T> {
+    /// `true` if the set includes every value below the first boundary
+    starts_inside: bool,
+    boundaries: Vec<T>,
+    /// Bit `i` (counting from the lowest bit of the first word) is set if boundary `i` is in the set
+    included: Vec<u64>,
+}
+
+impl<T> CompactIntervalSet<T> {
+    /// Rebuild a compact set from the parts returned by [`CompactIntervalSet::into_parts`]. The parts are not
+    /// checked here; if the boundaries are out of order, converting to an [`IntervalSet`] still gives a valid
+    /// set, but not necessarily the one intended. Missing bits of `included` count as not set.
+    pub fn from_parts(starts_inside: bool, boundaries: Vec<T>, included: Vec<u64>) -> Self {
+        Self { starts_inside, boundaries, included }
+    }
+
+    /// Split this into whether the set starts inside, the boundary values, and the bits saying which
+    /// boundaries are in the set, 64 per word.
+    pub fn into_parts(self) -> (bool, Vec<T>, Vec<u64>) {
+        (self.starts_inside, self.boundaries, self.included)
+    }
+
+    /// Returns `true` if the set includes every value below the first boundary, i.e. its first interval
+    /// has an open start. A set with no boundaries is then every value, and otherwise none.
+    pub fn starts_inside(&self) -> bool {
+        self.starts_inside
+    }
+
+    /// The values where membership in the set changes, in order.
+    pub fn boundaries(&self) -> &[T] {
+        &self.boundaries
+    }
+
+    /// Returns `true` if the value of boundary `i` is itself in the set.
+    pub fn is_included(&self, i: usize) -> bool {
+        self.included.get(i / 64).is_some_and(|word| word >> (i % 64) & 1 == 1)
+    }
+
+    fn push(&mut self, value: T, included: bool) {
+        let i = self.boundaries.len();
+        self.boundaries.push(value);
+        if i.is_multiple_of(64) {
+            self.included.push(0);
+        }
+        if included {
+            self.included[i / 64] |= 1 << (i % 64);
+        }
+    }
+}
+
+impl<T: Clone> From<&IntervalSet<T>> for CompactIntervalSet<T> {
+    fn from(set: &IntervalSet<T>) -> Self {
+        let mut compact = Self::from_parts(false, Vec::with_capacity(2 * set.len()), Vec::new());
+        for interval in set {
+            match interval.start() {
+                Bound::Included(v) => compact.push(v.clone(), true),
+                Bound::Excluded(v) => compact.push(v.clone(), false),
+                Bound::Unbounded => compact.starts_inside = true,
+            }
+            match interval.end() {
+                Bound::Included(v) => compact.push(v.clone(), true),
+                Bound::Excluded(v) => compact.push(v.clone(), false),
+                Bound::Unbounded => {},
+            }
+        }
+        compact
+    }
+}
+
+impl<T: Clone> From<IntervalSet<T>> for CompactIntervalSet<T> {
+    fn from(set: IntervalSet<T>) -> Self {
+        Self::from(&set)
+    }
+}
+
+impl<T: PartialOrd + Clone> From<CompactIntervalSet<T>> for IntervalSet<T> {
+    fn from(compact: CompactIntervalSet<T>) -> Self {
+        let mut start = compact.starts_inside.then_some(Bound::Unbounded);
+        let mut intervals = Vec::with_capacity(compact.boundaries.len() / 2 + 1);
+        for (i, value) in compact.boundaries.iter().enumerate() {
+            let bound = if compact.is_included(i) { Bound::Included(value.clone()) } else { Bound::Excluded(value.clone()) };
+            match start.take() {
+                Some(s) => intervals.push(Interval::from_bounds(s, bound)),
+                None => start = Some(bound),
+            }
+        }
+        if let Some(s) = start {
+            intervals.push(Interval::from_bounds(s, Bound::Unbounded));
+        }
+        intervals.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Lcg;
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = Lcg::new(1357);
+        for _ in 0..300 {
+            let set: IntervalSet<i32> = (0..rng.below(60)).map(|_| {
+                let start = rng.below(400);
+                let end = start + rng.below(10);
+                match rng.below(12) {
+                    0 => Interval::at_most(start),
+                    1 => Interval::greater_than(start),
+                    2 => Interval::incl(start, end),
+                    3 => Interval::from_bounds(Bound::Excluded(start), Bound::Included(end)),
+                    _ => Interval::excl(start, end),
+                }
+            }).collect();
+            let compact = CompactIntervalSet::from(&set);
+            assert!(compact.boundaries().windows(2).all(|w| w[0] <= w[1]));
+            assert_eq!(compact.included.len(), compact.boundaries().len().div_ceil(64));
+            assert_eq!(IntervalSet::from(compact), set);
+        }
+
+        assert_eq!(IntervalSet::from(CompactIntervalSet::<i32>::from_parts(true, Vec::new(), Vec::new())), IntervalSet::from(Interval::all()));
+        assert!(IntervalSet::from(CompactIntervalSet::<i32>::from_parts(false, Vec::new(), Vec::new())).is_empty());
+    }
+
+    #[test]
+    fn test_unchecked_parts() {
+        // Boundaries out of order still give a valid set: here (5, 1], which is empty, [3, 8), and [2, inf)
+        let set = IntervalSet::from(CompactIntervalSet::from_parts(false, vec![5, 1, 3, 8, 2], vec![0b10110]));
+        assert_eq!(set.as_slice(), [Interval::at_least(2)]);
+    }
+}
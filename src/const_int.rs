@@ -0,0 +1,81 @@
+//! `const fn` versions of the closed-range classifiers for integer end points.
+use crate::RangeOverlap;
+
+macro_rules! const_classifiers {
+    ($($int:ty => $excl:ident, $incl:ident);*) => {
+        $(
+            #[doc = concat!("Like [`excl_classify`](crate::excl_classify) for `", stringify!($int), "` end points, but usable in")]
+            /// constant expressions, so tables of overlaps can be worked out at compile time.
+            pub const fn $excl(a_start: $int, a_end: $int, b_start: $int, b_end: $int) -> RangeOverlap {
+                if a_start == b_start && a_end == b_end {
+                    RangeOverlap::AEqualsB
+                } else if a_start <= b_start && a_end >= b_end {
+                    RangeOverlap::AContainsB
+                } else if a_start < b_start && a_end > b_start && a_end <= b_end {
+                    RangeOverlap::AEndsInB
+                } else if a_start > b_start && a_start < b_end && a_end > b_end {
+                    RangeOverlap::AStartsInB
+                } else if a_start >= b_end || b_start >= a_end {
+                    RangeOverlap::None
+                } else {
+                    RangeOverlap::AInsideB
+                }
+            }
+
+            #[doc = concat!("Like [`incl_classify`](crate::incl_classify) for `", stringify!($int), "` end points, but usable in")]
+            /// constant expressions, so tables of overlaps can be worked out at compile time.
+            pub const fn $incl(a_start: $int, a_end: $int, b_start: $int, b_end: $int) -> RangeOverlap {
+                if a_start == b_start && a_end == b_end {
+                    RangeOverlap::AEqualsB
+                } else if a_start <= b_start && a_end >= b_end {
+                    RangeOverlap::AContainsB
+                } else if a_start < b_start && a_end >= b_start && a_end <= b_end {
+                    RangeOverlap::AEndsInB
+                } else if a_start > b_start && a_start <= b_end && a_end > b_end {
+                    RangeOverlap::AStartsInB
+                } else if a_start > b_end || b_start > a_end {
+                    RangeOverlap::None
+                } else {
+                    RangeOverlap::AInsideB
+                }
+            }
+        )*
+    };
+}
+
+const_classifiers!(
+    i64 => excl_classify_i64, incl_classify_i64;
+    u64 => excl_classify_u64, incl_classify_u64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{excl_classify, incl_classify};
+
+    /// Worked out at compile time; this would fail to build if the functions stopped being `const`
+    const TABLE: [RangeOverlap; 4] = [
+        excl_classify_i64(0, 10, 5, 15),
+        incl_classify_i64(0, 10, 10, 15),
+        excl_classify_u64(0, 10, 10, 15),
+        incl_classify_u64(2, 3, 0, 10),
+    ];
+
+    #[test]
+    fn test_matches_generic() {
+        assert_eq!(TABLE, [RangeOverlap::AEndsInB, RangeOverlap::AEndsInB, RangeOverlap::None, RangeOverlap::AInsideB]);
+        // Every combination of small end points, including empty and backwards ranges
+        for a_start in 0..5 {
+            for a_end in 0..5 {
+                for b_start in 0..5 {
+                    for b_end in 0..5 {
+                        let expected = (excl_classify(a_start, a_end, b_start, b_end), incl_classify(a_start, a_end, b_start, b_end));
+                        assert_eq!((excl_classify_u64(a_start, a_end, b_start, b_end), incl_classify_u64(a_start, a_end, b_start, b_end)), expected);
+                        let [a_start, a_end, b_start, b_end] = [a_start, a_end, b_start, b_end].map(|v| v as i64 - 2);
+                        assert_eq!((excl_classify_i64(a_start, a_end, b_start, b_end), incl_classify_i64(a_start, a_end, b_start, b_end)), expected);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Conveniences for intervals of `chrono` dates and times.
+use std::ops::{Bound, Sub};
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+use crate::Interval;
+
+/// An interval between two instants in time zone `Tz` (UTC unless given). Only available with the
+/// `chrono` feature.
+///
+/// ```
+/// # use range_overlap::{Interval, TimeRange};
+/// use chrono::{Duration, TimeZone, Utc};
+///
+/// let meeting: TimeRange = Interval::excl(Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap(), Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap());
+/// let lunch: TimeRange = Interval::excl(Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap(), Utc.with_ymd_and_hms(2024, 3, 1, 13, 0, 0).unwrap());
+/// assert_eq!(meeting.overlap_duration(&lunch), Some(Duration::minutes(30)));
+/// ```
+pub type TimeRange<Tz = Utc> = Interval<DateTime<Tz>>;
+
+fn midnight(date: NaiveDate) -> NaiveDateTime {
+    date.and_time(NaiveTime::MIN)
+}
+
+/// How long `a` and `b` overlap for, which is zero if they do not overlap at all.
+fn overlap_duration<T: PartialOrd + Clone + Sub<Output = Duration>>(a: &Interval<T>, b: &Interval<T>) -> Option<Duration> {
+    match a.intersect(b) {
+        Some(both) => both.length(),
+        None => Some(Duration::zero()),
+    }
+}
+
+impl Interval<NaiveDate> {
+    /// The times covered by the days in this interval, from midnight at the start of its first day to
+    /// midnight at the end of its last. E.g. `Interval::incl(d1, d2)` holds every time on the days `d1` and
+    /// `d2` and those between them, while `Interval::excl(d1, d2)` stops at the start of `d2`. Only
+    /// available with the `chrono` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use chrono::NaiveDate;
+    ///
+    /// let (mar_1, mar_3) = (NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 3, 3).unwrap());
+    /// let times = Interval::incl(mar_1, mar_3).to_datetimes();
+    /// assert!(times.contains_point(&mar_3.and_hms_opt(23, 59, 59).unwrap()));
+    /// assert_eq!(times, Interval::excl(mar_1.and_hms_opt(0, 0, 0).unwrap(), NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(0, 0, 0).unwrap()));
+    /// ```
+    pub fn to_datetimes(&self) -> Interval<NaiveDateTime> {
+        // The day after the last date chrono can represent can't be written as a midnight, so those
+        // bounds stop at the last time chrono can represent instead
+        let start = match self.start() {
+            Bound::Included(&first) => Bound::Included(midnight(first)),
+            Bound::Excluded(&before) => before.succ_opt().map_or(Bound::Excluded(NaiveDateTime::MAX), |first| Bound::Included(midnight(first))),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end = match self.end() {
+            Bound::Included(&last) => last.succ_opt().map_or(Bound::Included(NaiveDateTime::MAX), |after| Bound::Excluded(midnight(after))),
+            Bound::Excluded(&after) => Bound::Excluded(midnight(after)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        Interval::from_bounds(start, end)
+    }
+
+    /// How much time the days in this interval and `other` have in common, counting each day as
+    /// [`to_datetimes`](Interval::to_datetimes) does. The result is zero if they have no days in common,
+    /// and `None` if the days in common go on forever. Only available with the `chrono` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use chrono::{Duration, NaiveDate};
+    ///
+    /// let date = |day| NaiveDate::from_ymd_opt(2024, 7, day).unwrap();
+    /// let trip = Interval::incl(date(1), date(10));
+    /// assert_eq!(trip.overlap_duration(&Interval::incl(date(9), date(20))), Some(Duration::days(2)));
+    /// assert_eq!(trip.overlap_duration(&Interval::incl(date(11), date(20))), Some(Duration::zero()));
+    /// ```
+    pub fn overlap_duration(&self, other: &Interval<NaiveDate>) -> Option<Duration> {
+        overlap_duration(&self.to_datetimes(), &other.to_datetimes())
+    }
+}
+
+impl Interval<NaiveDateTime> {
+    /// The times on the days from `first` to `last`, both included: from midnight at the start of
+    /// `first` to midnight at the end of `last`. Only available with the `chrono` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |day| NaiveDate::from_ymd_opt(2024, 7, day).unwrap();
+    /// let week = Interval::from_dates(date(1), date(7));
+    /// assert!(week.contains_point(&date(7).and_hms_opt(18, 30, 0).unwrap()));
+    /// assert!(!week.contains_point(&date(8).and_hms_opt(0, 0, 0).unwrap()));
+    /// ```
+    pub fn from_dates(first: NaiveDate, last: NaiveDate) -> Self {
+        Interval::incl(first, last).to_datetimes()
+    }
+
+    /// How long this interval and `other` overlap for: zero if they do not overlap, and `None` if the
+    /// overlap goes on forever. Only available with the `chrono` feature.
+    pub fn overlap_duration(&self, other: &Interval<NaiveDateTime>) -> Option<Duration> {
+        overlap_duration(self, other)
+    }
+}
+
+impl<Tz: TimeZone> Interval<DateTime<Tz>> {
+    /// The times on the days from `first` to `last` in time zone `tz`, both included: from midnight at
+    /// the start of `first` to midnight at the end of `last`, local time. If clocks go back over midnight,
+    /// the earlier of the two midnights is used; returns `None` if either midnight is skipped by clocks
+    /// going forward. Only available with the `chrono` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use chrono::{Duration, FixedOffset, NaiveDate, TimeZone};
+    ///
+    /// let date = |day| NaiveDate::from_ymd_opt(2024, 7, day).unwrap();
+    /// let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    /// let stay = Interval::from_dates_in(&tokyo, date(1), date(3)).unwrap();
+    /// assert!(stay.contains_point(&tokyo.with_ymd_and_hms(2024, 7, 3, 23, 0, 0).unwrap()));
+    /// assert_eq!(stay.length(), Some(Duration::days(3)));
+    /// ```
+    pub fn from_dates_in(tz: &Tz, first: NaiveDate, last: NaiveDate) -> Option<Self> {
+        let start = tz.from_local_datetime(&midnight(first)).earliest()?;
+        let end = tz.from_local_datetime(&midnight(last.succ_opt()?)).earliest()?;
+        Some(Interval::excl(start, end))
+    }
+
+    /// How long this interval and `other` overlap for: zero if they do not overlap, and `None` if the
+    /// overlap goes on forever. Only available with the `chrono` feature.
+    pub fn overlap_duration(&self, other: &Interval<DateTime<Tz>>) -> Option<Duration> {
+        overlap_duration(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{classify_any, RangeOverlap};
+    use chrono::FixedOffset;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_dates_directly() {
+        // The cases the open range tests had to convert to day-of-year
+        for inclusive in [false, true] {
+            assert_eq!(classify_any(Some(date(2017, 1, 1)), Some(date(2017, 12, 1)), Some(date(2017, 1, 1)), None, inclusive), RangeOverlap::AInsideB);
+            assert_eq!(classify_any(Some(date(2004, 12, 1)), Some(date(2005, 1, 1)), Some(date(2004, 7, 1)), Some(date(2005, 1, 1)), inclusive), RangeOverlap::AInsideB);
+        }
+    }
+
+    #[test]
+    fn test_to_datetimes() {
+        let (first, last) = (date(2024, 2, 28), date(2024, 3, 1));
+        assert_eq!(Interval::incl(first, last).to_datetimes(), Interval::excl(midnight(first), midnight(date(2024, 3, 2))));
+        assert_eq!(Interval::excl(first, last).to_datetimes(), Interval::excl(midnight(first), midnight(last)));
+        assert_eq!(Interval::greater_than(first).to_datetimes(), Interval::at_least(midnight(date(2024, 2, 29))));
+        assert_eq!(Interval::at_most(last).to_datetimes(), Interval::less_than(midnight(date(2024, 3, 2))));
+        assert_eq!(Interval::incl(NaiveDate::MAX, NaiveDate::MAX).to_datetimes(), Interval::incl(midnight(NaiveDate::MAX), NaiveDateTime::MAX));
+        assert_eq!(Interval::greater_than(NaiveDate::MAX).to_datetimes(), Interval::greater_than(NaiveDateTime::MAX));
+        assert_eq!(Interval::from_dates(first, last), Interval::incl(first, last).to_datetimes());
+    }
+
+    #[test]
+    fn test_overlap_duration() {
+        let days = Interval::incl(date(2024, 3, 1), date(2024, 3, 10));
+        assert_eq!(days.overlap_duration(&Interval::excl(date(2024, 3, 5), date(2024, 3, 8))), Some(Duration::days(3)));
+        assert_eq!(days.overlap_duration(&Interval::at_least(date(2024, 3, 10))), Some(Duration::days(1)));
+        assert_eq!(days.overlap_duration(&Interval::greater_than(date(2024, 3, 10))), Some(Duration::zero()));
+        assert_eq!(Interval::at_least(date(2024, 3, 1)).overlap_duration(&Interval::all()), None);
+
+        let times = Interval::from_dates(date(2024, 3, 1), date(2024, 3, 1));
+        let evening = Interval::excl(date(2024, 3, 1).and_hms_opt(18, 0, 0).unwrap(), date(2024, 3, 2).and_hms_opt(2, 0, 0).unwrap());
+        assert_eq!(times.overlap_duration(&evening), Some(Duration::hours(6)));
+
+        // The same instants in different offsets are the same times
+        let utc: TimeRange = Interval::from_dates_in(&Utc, date(2024, 3, 1), date(2024, 3, 1)).unwrap();
+        let east = FixedOffset::east_opt(3 * 3600).unwrap();
+        let local = Interval::from_dates_in(&east, date(2024, 3, 1), date(2024, 3, 1)).unwrap();
+        let (start, end) = local.into_bounds();
+        let local_in_utc = Interval::from_bounds(start.map(|t| t.with_timezone(&Utc)), end.map(|t| t.with_timezone(&Utc)));
+        assert_eq!(utc.overlap_duration(&local_in_utc), Some(Duration::hours(21)));
+    }
+}
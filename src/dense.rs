@@ -0,0 +1,273 @@
+//! An interval set for small integer domains, stored as one bit per value.
+use std::ops::{BitAnd, BitOr, BitXor, Bound, Sub};
+
+use crate::{Interval, IntervalSet};
+
+/// A set of the integers `0..domain_size`, stored as a bitset with one bit per value. For small, dense
+/// domains such as port numbers or days of the year, this answers [`contains`](DenseIntervalSet::contains)
+/// in constant time and combines sets a word (64 values) at a time, which is much faster than the
+/// comparisons an [`IntervalSet`] needs. It converts to and from lists of intervals, and to an
+/// [`IntervalSet`].
+///
+/// Sets combined with each other must have the same domain size.
+///
+/// ```
+/// # use range_overlap::{DenseIntervalSet, Interval};
+/// let mut open_ports = DenseIntervalSet::new(65536);
+/// open_ports.insert(&Interval::excl(8000, 8100));
+/// open_ports.insert(&Interval::point(443));
+/// assert!(open_ports.contains(8080));
+/// assert!(!open_ports.contains(8100));
+///
+/// let allowed = DenseIntervalSet::from_intervals(65536, [Interval::at_least(1024)]);
+/// assert_eq!((&open_ports & &allowed).to_intervals(), [Interval::incl(8000, 8099)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DenseIntervalSet {
+    words: Vec<u64>,
+    domain_size: usize,
+}
+
+/// A mask of the bits from `lo` to `hi` (inclusive) within one word.
+fn word_mask(lo: usize, hi: usize) -> u64 {
+    (u64::MAX >> (63 - hi)) & (u64::MAX << lo)
+}
+
+impl DenseIntervalSet {
+    /// Create an empty set over the values `0..domain_size`.
+    pub fn new(domain_size: usize) -> Self {
+        Self { words: vec![0; domain_size.div_ceil(64)], domain_size }
+    }
+
+    /// Create a set holding every value in `0..domain_size`.
+    pub fn full(domain_size: usize) -> Self {
+        let mut set = Self::new(domain_size);
+        set.insert(&Interval::all());
+        set
+    }
+
+    /// Create a set over `0..domain_size` holding the values in `intervals`. Values outside the domain are ignored.
+    pub fn from_intervals<I: IntoIterator<Item = Interval<usize>>>(domain_size: usize, intervals: I) -> Self {
+        let mut set = Self::new(domain_size);
+        for interval in intervals {
+            set.insert(&interval);
+        }
+        set
+    }
+
+    /// The number of values in the domain; the set can hold the values `0..domain_size`.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns `true` if there are no values in this set.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// The number of values in this set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns `true` if `value` is in this set. Values outside the domain never are.
+    pub fn contains(&self, value: usize) -> bool {
+        value < self.domain_size && self.words[value / 64] >> (value % 64) & 1 == 1
+    }
+
+    /// The first and last values of `interval` that are in the domain, if any.
+    fn clamp(&self, interval: &Interval<usize>) -> Option<(usize, usize)> {
+        let closed = interval.to_closed_discrete()?;
+        let (Bound::Included(&lo), Bound::Included(&hi)) = (closed.start(), closed.end()) else {
+            unreachable!("usize intervals always close to included ends")
+        };
+        let hi = hi.min(self.domain_size.checked_sub(1)?);
+        (lo <= hi).then_some((lo, hi))
+    }
+
+    /// Set or clear the bits from `lo` to `hi`, inclusive.
+    fn fill(&mut self, lo: usize, hi: usize, on: bool) {
+        for w in lo / 64..=hi / 64 {
+            let mask = word_mask(if w == lo / 64 { lo % 64 } else { 0 }, if w == hi / 64 { hi % 64 } else { 63 });
+            if on {
+                self.words[w] |= mask;
+            } else {
+                self.words[w] &= !mask;
+            }
+        }
+    }
+
+    /// Add the values in `interval` to this set. Values outside the domain are ignored.
+    pub fn insert(&mut self, interval: &Interval<usize>) {
+        if let Some((lo, hi)) = self.clamp(interval) {
+            self.fill(lo, hi, true);
+        }
+    }
+
+    /// Remove the values in `interval` from this set.
+    pub fn remove(&mut self, interval: &Interval<usize>) {
+        if let Some((lo, hi)) = self.clamp(interval) {
+            self.fill(lo, hi, false);
+        }
+    }
+
+    /// The first value from `from` onwards whose bit is `set`, or the domain size if there is none. Bits
+    /// past the end of the domain are never set, so this is found a word at a time.
+    fn find(&self, from: usize, set: bool) -> usize {
+        let word_at = |w: usize| if set { self.words[w] } else { !self.words[w] };
+        let mut w = from / 64;
+        if w >= self.words.len() {
+            return self.domain_size;
+        }
+        let mut word = word_at(w) & (u64::MAX << (from % 64));
+        while word == 0 {
+            w += 1;
+            if w >= self.words.len() {
+                return self.domain_size;
+            }
+            word = word_at(w);
+        }
+        (w * 64 + word.trailing_zeros() as usize).min(self.domain_size)
+    }
+
+    /// The runs of values in this set, as intervals with both ends included, in order.
+    pub fn to_intervals(&self) -> Vec<Interval<usize>> {
+        let mut intervals = Vec::new();
+        let mut from = 0;
+        loop {
+            let start = self.find(from, true);
+            if start >= self.domain_size {
+                return intervals;
+            }
+            from = self.find(start, false);
+            intervals.push(Interval::incl(start, from - 1));
+        }
+    }
+
+    fn combine(&self, other: &DenseIntervalSet, op: impl Fn(u64, u64) -> u64) -> DenseIntervalSet {
+        assert_eq!(self.domain_size, other.domain_size, "dense interval sets must have the same domain size to be combined");
+        let words = self.words.iter().zip(&other.words).map(|(&a, &b)| op(a, b)).collect();
+        DenseIntervalSet { words, domain_size: self.domain_size }
+    }
+
+    /// The values in either this set or `other`. Panics if the sets have different domain sizes.
+    pub fn union(&self, other: &DenseIntervalSet) -> DenseIntervalSet {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// The values in both this set and `other`. Panics if the sets have different domain sizes.
+    pub fn intersection(&self, other: &DenseIntervalSet) -> DenseIntervalSet {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// The values in this set but not in `other`. Panics if the sets have different domain sizes.
+    pub fn difference(&self, other: &DenseIntervalSet) -> DenseIntervalSet {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// The values in exactly one of this set and `other`. Panics if the sets have different domain sizes.
+    pub fn symmetric_difference(&self, other: &DenseIntervalSet) -> DenseIntervalSet {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    /// The values in the domain that are not in this set.
+    pub fn complement(&self) -> DenseIntervalSet {
+        DenseIntervalSet::full(self.domain_size).difference(self)
+    }
+}
+
+macro_rules! impl_dense_op {
+    ($($op:ident, $method:ident => $set_method:ident);*) => {
+        $(
+            impl $op<&DenseIntervalSet> for &DenseIntervalSet {
+                type Output = DenseIntervalSet;
+
+                fn $method(self, rhs: &DenseIntervalSet) -> Self::Output {
+                    self.$set_method(rhs)
+                }
+            }
+
+            impl $op for DenseIntervalSet {
+                type Output = DenseIntervalSet;
+
+                fn $method(self, rhs: DenseIntervalSet) -> Self::Output {
+                    self.$set_method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_dense_op!(
+    BitOr, bitor => union;
+    BitAnd, bitand => intersection;
+    Sub, sub => difference;
+    BitXor, bitxor => symmetric_difference
+);
+
+impl From<&DenseIntervalSet> for IntervalSet<usize> {
+    fn from(set: &DenseIntervalSet) -> Self {
+        set.to_intervals().into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval};
+
+    #[test]
+    fn test_matches_model() {
+        let mut rng = Lcg::new(11235);
+        for domain_size in [0, 1, 63, 64, 65, 200] {
+            let mut set = DenseIntervalSet::new(domain_size);
+            let mut model = vec![false; domain_size];
+            for _ in 0..200 {
+                // Clamp the few that end below zero, which are empty either way
+                let interval = random_interval(&mut rng, 230, 230);
+                let interval = Interval::from_bounds(interval.start().map(|&v| v.max(0) as usize), interval.end().map(|&v| v.max(0) as usize));
+                let insert = rng.below(3) != 0;
+                if insert {
+                    set.insert(&interval);
+                } else {
+                    set.remove(&interval);
+                }
+                (0..domain_size).filter(|v| interval.contains_point(v)).for_each(|v| model[v] = insert);
+
+                assert_eq!((0..domain_size + 70).map(|v| set.contains(v)).collect::<Vec<_>>(), (0..domain_size + 70).map(|v| v < domain_size && model[v]).collect::<Vec<_>>());
+                assert_eq!(set.count(), model.iter().filter(|&&m| m).count());
+                assert_eq!(set.is_empty(), set.count() == 0);
+                let intervals = set.to_intervals();
+                assert_eq!(DenseIntervalSet::from_intervals(domain_size, intervals.iter().copied()), set);
+                // Runs are maximal, so there is at least one value missing between each
+                for pair in intervals.windows(2) {
+                    let (Bound::Included(&end), Bound::Included(&start)) = (pair[0].end(), pair[1].start()) else { panic!("{intervals:?}") };
+                    assert!(start > end + 1, "{intervals:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = DenseIntervalSet::from_intervals(100, [Interval::excl(0, 30), Interval::incl(60, 70)]);
+        let b = DenseIntervalSet::from_intervals(100, [Interval::excl(20, 65), Interval::at_least(90)]);
+        let as_set = |d: &DenseIntervalSet| IntervalSet::from(d);
+        let domain = Interval::excl(0, 100);
+        for value in 0..100 {
+            assert_eq!((&a | &b).contains(value), a.contains(value) || b.contains(value));
+            assert_eq!((&a & &b).contains(value), a.contains(value) && b.contains(value));
+            assert_eq!((&a - &b).contains(value), a.contains(value) && !b.contains(value));
+            assert_eq!((&a ^ &b).contains(value), a.contains(value) != b.contains(value));
+            assert_eq!(a.complement().contains(value), !a.contains(value));
+        }
+        assert_eq!(as_set(&a.complement()), as_set(&a).complement(Some(&domain)).iter().filter_map(|iv| iv.to_closed_discrete()).collect());
+        assert_eq!(DenseIntervalSet::full(100).count(), 100);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_domains() {
+        let _ = DenseIntervalSet::new(10).union(&DenseIntervalSet::new(20));
+    }
+}
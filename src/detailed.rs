@@ -0,0 +1,135 @@
+//! Classification that also reports the overlapping region and which end points coincide.
+use std::ops::Bound;
+
+use crate::{Interval, RangeOverlap};
+
+/// The result of [`Interval::classify_detailed`] or [`classify_detailed`]: how two ranges `A` and `B`
+/// overlap, along with information that usually has to be recomputed after classifying.
+///
+/// End points "coincide" if they are at the same value, whether or not each is included in its range,
+/// or if they are both open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OverlapDetails<T> {
+    /// How the two ranges overlap.
+    pub overlap: RangeOverlap,
+
+    /// The values in both ranges, or `None` if they do not overlap.
+    pub intersection: Option<Interval<T>>,
+
+    /// `true` if the starts of `A` and `B` coincide.
+    pub same_start: bool,
+
+    /// `true` if the ends of `A` and `B` coincide.
+    pub same_end: bool,
+
+    /// `true` if the end of `A` is at the same value as the start of `B`.
+    pub a_end_at_b_start: bool,
+
+    /// `true` if the start of `A` is at the same value as the end of `B`.
+    pub a_start_at_b_end: bool,
+}
+
+fn coincide<T: PartialOrd>(a: Bound<&T>, b: Bound<&T>) -> bool {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => true,
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y)) => x == y,
+        _ => false,
+    }
+}
+
+fn same_value<T: PartialOrd>(a: Bound<&T>, b: Bound<&T>) -> bool {
+    !matches!((a, b), (Bound::Unbounded, _) | (_, Bound::Unbounded)) && coincide(a, b)
+}
+
+impl<T: PartialOrd + Clone> Interval<T> {
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`), also returning the intersection
+    /// of the two and which of their end points coincide. See [`OverlapDetails`].
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeOverlap};
+    /// let details = Interval::incl(1, 5).classify_detailed(&Interval::excl(5, 9));
+    /// assert_eq!(details.overlap, RangeOverlap::AEndsInB);
+    /// assert_eq!(details.intersection, Some(Interval::point(5)));
+    /// assert!(details.a_end_at_b_start);
+    /// assert!(!details.same_start);
+    /// ```
+    pub fn classify_detailed(&self, other: &Interval<T>) -> OverlapDetails<T> {
+        OverlapDetails {
+            overlap: self.classify_against(other),
+            intersection: self.intersect(other),
+            same_start: coincide(self.start(), other.start()),
+            same_end: coincide(self.end(), other.end()),
+            a_end_at_b_start: same_value(self.end(), other.start()),
+            a_start_at_b_end: same_value(self.start(), other.end()),
+        }
+    }
+}
+
+/// Classify the kind of overlap between two ranges given in the same way as for [`classify_any`](crate::classify_any),
+/// also returning the intersection of the two and which of their end points coincide. See [`OverlapDetails`].
+///
+/// Unlike [`classify_any`](crate::classify_any), an empty range (e.g. `[5, 5)`) never overlaps anything.
+///
+/// ```
+/// # use range_overlap::{classify_detailed, Interval, RangeOverlap};
+/// let details = classify_detailed(Some(1), Some(10), Some(5), None, false);
+/// assert_eq!(details.overlap, RangeOverlap::AEndsInB);
+/// assert_eq!(details.intersection, Some(Interval::excl(5, 10)));
+/// ```
+pub fn classify_detailed<T: PartialOrd + Clone>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> OverlapDetails<T> {
+    let a = Interval::new_unchecked(a_start, a_end, inclusive);
+    let b = Interval::new_unchecked(b_start, b_end, inclusive);
+    a.classify_detailed(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection() {
+        let cases = [
+            (Interval::excl(1, 5), Interval::excl(3, 9), Some(Interval::excl(3, 5))),
+            (Interval::incl(1, 5), Interval::excl(3, 9), Some(Interval::incl(3, 5))),
+            (Interval::excl(1, 5), Interval::excl(5, 9), None),
+            (Interval::less_than(5), Interval::at_least(0), Some(Interval::excl(0, 5))),
+            (Interval::all(), Interval::greater_than(3), Some(Interval::greater_than(3))),
+            (Interval::excl(1, 9), Interval::excl(3, 5), Some(Interval::excl(3, 5))),
+            (Interval::excl(3, 5), Interval::excl(3, 5), Some(Interval::excl(3, 5))),
+            (Interval::excl(3, 3), Interval::excl(1, 5), None),
+        ];
+
+        for (a, b, expected) in cases {
+            let details = a.classify_detailed(&b);
+            assert_eq!(details.overlap, a.classify_against(&b));
+            assert_eq!(details.intersection, expected, "{a:?} vs {b:?}");
+            assert_eq!(b.classify_detailed(&a).intersection, expected, "{b:?} vs {a:?}");
+        }
+
+        // The intersection keeps the tighter bound kind where the values are the same
+        let a = Interval::from_bounds(Bound::Excluded(1), Bound::Included(5));
+        let b = Interval::from_bounds(Bound::Included(1), Bound::Excluded(5));
+        assert_eq!(a.classify_detailed(&b).intersection, Some(Interval::from_bounds(Bound::Excluded(1), Bound::Excluded(5))));
+    }
+
+    #[test]
+    fn test_coinciding_ends() {
+        let details = Interval::excl(1, 5).classify_detailed(&Interval::excl(5, 9));
+        assert_eq!(details.overlap, RangeOverlap::None);
+        assert!(details.a_end_at_b_start);
+        assert!(!details.a_start_at_b_end && !details.same_start && !details.same_end);
+
+        let details = Interval::excl(1, 5).classify_detailed(&Interval::incl(1, 5));
+        assert_eq!(details.overlap, RangeOverlap::AInsideB);
+        assert!(details.same_start && details.same_end);
+
+        let details = Interval::less_than(5).classify_detailed(&Interval::at_most(1));
+        assert!(details.same_start && !details.same_end && !details.a_start_at_b_end);
+
+        let details = classify_detailed(Some(5), None, Some(1), Some(5), true);
+        assert_eq!(details.overlap, RangeOverlap::AStartsInB);
+        assert!(details.a_start_at_b_end);
+        assert_eq!(details.intersection, Some(Interval::point(5)));
+    }
+}
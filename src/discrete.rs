@@ -0,0 +1,202 @@
+//! Classification for discrete domains, such as integers, where there are no values between
+//! one value and the next.
+//!
+//! Over the integers, `[1, 5]` and `[1, 6)` hold exactly the same values, and `[1, 5]` and
+//! `[6, 10]` leave no value between them. The regular classification functions treat end points
+//! as if there could always be more values between them, so they see `[1, 5]` as inside `[1, 6)`
+//! and `[1, 5]` and `[6, 10]` as separated by a gap. The functions here account for this.
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::{Interval, RangeOverlap};
+
+/// A type whose values have a well-defined next and previous value, like the integers.
+///
+/// This is implemented for all of the primitive integer types. It can be implemented for other
+/// discrete types, such as calendar dates.
+pub trait Discrete: PartialOrd + Clone {
+    /// The value immediately after this one, or `None` if this is the largest value.
+    fn succ(&self) -> Option<Self>;
+
+    /// The value immediately before this one, or `None` if this is the smallest value.
+    fn pred(&self) -> Option<Self>;
+
+    /// The smallest value of this type, if there is one. An open start is treated as starting here.
+    fn min_value() -> Option<Self> {
+        None
+    }
+
+    /// The largest value of this type, if there is one. An open end is treated as ending here.
+    fn max_value() -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! impl_discrete_int {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn succ(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(&self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn min_value() -> Option<Self> {
+                    Some(<$t>::MIN)
+                }
+
+                fn max_value() -> Option<Self> {
+                    Some(<$t>::MAX)
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: Discrete> Interval<T> {
+    /// Rewrite this interval so that every end is included, e.g. `[1, 6)` becomes `[1, 5]`. Open ends
+    /// become the type's [smallest](Discrete::min_value) or [largest](Discrete::max_value) value, or stay
+    /// open if it has none. Returns `None` if the interval holds no values, including when an excluded
+    /// end has no next (or previous) value, like `(u8::MAX, ..)`.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(1, 6).to_closed_discrete(), Some(Interval::incl(1, 5)));
+    /// assert_eq!(Interval::greater_than(1u8).to_closed_discrete(), Some(Interval::incl(2, u8::MAX)));
+    /// assert_eq!(Interval::excl(5, 5).to_closed_discrete(), None);
+    /// ```
+    pub fn to_closed_discrete(&self) -> Option<Interval<T>> {
+        let start = match self.start() {
+            Bound::Included(s) => Bound::Included(s.clone()),
+            Bound::Excluded(s) => Bound::Included(s.succ()?),
+            Bound::Unbounded => T::min_value().map_or(Bound::Unbounded, Bound::Included),
+        };
+        let end = match self.end() {
+            Bound::Included(e) => Bound::Included(e.clone()),
+            Bound::Excluded(e) => Bound::Included(e.pred()?),
+            Bound::Unbounded => T::max_value().map_or(Bound::Unbounded, Bound::Included),
+        };
+
+        if bounds::is_empty_ref(start.as_ref(), end.as_ref()) {
+            None
+        } else {
+            Some(Interval::from_bounds(start, end))
+        }
+    }
+
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`), comparing the sets of discrete
+    /// values in each rather than their end points.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeOverlap};
+    /// assert_eq!(Interval::incl(1, 5).classify_against(&Interval::excl(1, 6)), RangeOverlap::AInsideB);
+    /// assert_eq!(Interval::incl(1, 5).classify_discrete(&Interval::excl(1, 6)), RangeOverlap::AEqualsB);
+    /// ```
+    pub fn classify_discrete(&self, other: &Interval<T>) -> RangeOverlap {
+        match (self.to_closed_discrete(), other.to_closed_discrete()) {
+            (Some(a), Some(b)) => a.classify_against(&b),
+            _ => RangeOverlap::None,
+        }
+    }
+
+    /// Returns `true` if this interval and `other` share no values, but there is also no value
+    /// between them, e.g. `[1, 5]` and `[6, 10]`.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert!(Interval::incl(1, 5).is_adjacent_discrete(&Interval::incl(6, 10)));
+    /// assert!(!Interval::incl(1, 5).is_adjacent_to(&Interval::incl(6, 10)));
+    /// assert!(!Interval::incl(1, 5).is_adjacent_discrete(&Interval::incl(7, 10)));
+    /// ```
+    pub fn is_adjacent_discrete(&self, other: &Interval<T>) -> bool {
+        let (Some(a), Some(b)) = (self.to_closed_discrete(), other.to_closed_discrete()) else {
+            return false;
+        };
+
+        let next_after = |end: Bound<&T>, start: Bound<&T>| match (end, start) {
+            (Bound::Included(e), Bound::Included(s)) => e.succ().is_some_and(|n| &n == s),
+            _ => false,
+        };
+        next_after(a.end(), b.start()) || next_after(b.end(), a.start())
+    }
+}
+
+/// Classify the kind of overlap between two closed ranges of discrete values, such as integers.
+/// `inclusive` indicates whether `a_end` and `b_end` are part of their ranges, as in
+/// [`classify_any`](crate::classify_any). An empty range never overlaps anything. To compare ranges
+/// that differ in which ends are included, e.g. `[1, 5]` and `[1, 6)`, use [`Interval::classify_discrete`].
+///
+/// ```
+/// # use range_overlap::{classify_discrete, RangeOverlap};
+/// assert_eq!(classify_discrete(1, 5, 0, 5, true), RangeOverlap::AInsideB);
+/// assert_eq!(classify_discrete(0, u8::MAX, 0, u8::MAX, true), RangeOverlap::AEqualsB);
+/// // [3, 3) has no integers in it
+/// assert_eq!(classify_discrete(3, 3, 1, 5, false), RangeOverlap::None);
+/// ```
+pub fn classify_discrete<T: Discrete>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> RangeOverlap {
+    let a = Interval::new_unchecked(Some(a_start), Some(a_end), inclusive);
+    let b = Interval::new_unchecked(Some(b_start), Some(b_end), inclusive);
+    a.classify_discrete(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::sample_intervals;
+
+    /// The values in `interval`, out of the whole `u8` range
+    fn members(interval: &Interval<u8>) -> Vec<u8> {
+        (0..=u8::MAX).filter(|v| interval.contains_point(v)).collect()
+    }
+
+    #[test]
+    fn test_classify_matches_members() {
+        let intervals = sample_intervals(5);
+        for a in &intervals {
+            for b in &intervals {
+                let (in_a, in_b) = (members(a), members(b));
+                let overlap = a.classify_discrete(b);
+                if in_a.is_empty() || in_b.is_empty() || !in_a.iter().any(|v| in_b.contains(v)) {
+                    assert_eq!(overlap, RangeOverlap::None, "{a:?} vs {b:?}");
+                } else if in_a == in_b {
+                    assert_eq!(overlap, RangeOverlap::AEqualsB, "{a:?} vs {b:?}");
+                } else if in_b.iter().all(|v| in_a.contains(v)) {
+                    assert_eq!(overlap, RangeOverlap::AContainsB, "{a:?} vs {b:?}");
+                } else if in_a.iter().all(|v| in_b.contains(v)) {
+                    assert_eq!(overlap, RangeOverlap::AInsideB, "{a:?} vs {b:?}");
+                } else if in_a[0] < in_b[0] {
+                    assert_eq!(overlap, RangeOverlap::AEndsInB, "{a:?} vs {b:?}");
+                } else {
+                    assert_eq!(overlap, RangeOverlap::AStartsInB, "{a:?} vs {b:?}");
+                }
+
+                let adjacent = !in_a.is_empty() && !in_b.is_empty() && (
+                    in_a.last().unwrap().checked_add(1) == Some(in_b[0]) || in_b.last().unwrap().checked_add(1) == Some(in_a[0])
+                );
+                assert_eq!(a.is_adjacent_discrete(b), adjacent, "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(Interval::greater_than(u8::MAX).to_closed_discrete(), None);
+        assert_eq!(Interval::less_than(i8::MIN).to_closed_discrete(), None);
+        assert_eq!(Interval::excl(0u8, u8::MAX).to_closed_discrete(), Some(Interval::incl(0, 254)));
+        assert_eq!(Interval::<i8>::all().to_closed_discrete(), Some(Interval::incl(i8::MIN, i8::MAX)));
+        assert_eq!(Interval::at_least(0u8).classify_discrete(&Interval::incl(0, u8::MAX)), RangeOverlap::AEqualsB);
+    }
+
+    #[test]
+    fn test_free_function() {
+        assert_eq!(classify_discrete(1, 5, 6, 10, true), RangeOverlap::None);
+        assert_eq!(classify_discrete(1, 6, 5, 10, false), RangeOverlap::AEndsInB);
+        assert_eq!(classify_discrete(1, 6, 0, 6, false), RangeOverlap::AInsideB);
+        assert_eq!(classify_discrete(3, 3, 1, 5, false), RangeOverlap::None);
+    }
+}
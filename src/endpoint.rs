@@ -0,0 +1,137 @@
+//! An explicit representation of range ends that may be infinite.
+use crate::{excl_classify, incl_classify, RangeOverlap};
+
+/// One end of a range, which can be a finite value or either infinity.
+///
+/// When [`classify_any`](crate::classify_any) and friends take `Option<T>` ends, `None` means
+/// "open", and whether that is negative or positive infinity depends on which side of the range
+/// it is given for. `Endpoint` makes that explicit. Endpoints are ordered with [`Endpoint::NegInf`]
+/// before every finite value and [`Endpoint::PosInf`] after every finite value:
+///
+/// ```
+/// # use range_overlap::Endpoint;
+/// assert!(Endpoint::NegInf < Endpoint::Finite(-1_000_000));
+/// assert!(Endpoint::Finite(1_000_000) < Endpoint::PosInf);
+/// assert!(Endpoint::Finite(1) < Endpoint::Finite(2));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Endpoint<T> {
+    /// Negative infinity, less than any finite value.
+    NegInf,
+
+    /// A finite value.
+    Finite(T),
+
+    /// Positive infinity, greater than any finite value.
+    PosInf,
+}
+
+impl<T> Endpoint<T> {
+    /// Convert an `Option` given as the start of a range, where `None` means the range
+    /// has no lower limit, into an `Endpoint`.
+    pub fn from_start(start: Option<T>) -> Self {
+        match start {
+            Some(v) => Self::Finite(v),
+            None => Self::NegInf,
+        }
+    }
+
+    /// Convert an `Option` given as the end of a range, where `None` means the range
+    /// has no upper limit, into an `Endpoint`.
+    pub fn from_end(end: Option<T>) -> Self {
+        match end {
+            Some(v) => Self::Finite(v),
+            None => Self::PosInf,
+        }
+    }
+
+    /// Returns `true` if this endpoint is a finite value.
+    pub fn is_finite(&self) -> bool {
+        matches!(self, Self::Finite(_))
+    }
+
+    /// Returns a reference to the value, if this endpoint is finite.
+    pub fn finite(&self) -> Option<&T> {
+        match self {
+            Self::Finite(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Convert this endpoint into an `Option`, with either infinity becoming `None`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Finite(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<T> for Endpoint<T> {
+    fn from(value: T) -> Self {
+        Self::Finite(value)
+    }
+}
+
+/// Classify the kind of overlap between two ranges whose ends are given as [`Endpoint`]s.
+/// As with [`classify_any`](crate::classify_any), `inclusive` indicates whether finite
+/// values of `a_end` and `b_end` are part of their ranges; the starts are always included.
+///
+/// ```
+/// # use range_overlap::{classify_endpoints, Endpoint, RangeOverlap};
+/// let overlap = classify_endpoints(Endpoint::NegInf, Endpoint::Finite(10), Endpoint::Finite(5), Endpoint::PosInf, false);
+/// assert_eq!(overlap, RangeOverlap::AEndsInB);
+/// ```
+pub fn classify_endpoints<T: PartialOrd>(a_start: Endpoint<T>, a_end: Endpoint<T>, b_start: Endpoint<T>, b_end: Endpoint<T>, inclusive: bool) -> RangeOverlap {
+    // Because the infinities are ordered relative to the finite values,
+    // the closed-range logic applies directly.
+    if inclusive {
+        incl_classify(a_start, a_end, b_start, b_end)
+    } else {
+        excl_classify(a_start, a_end, b_start, b_end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify_any;
+
+    #[test]
+    fn test_matches_classify_any() {
+        let ends = [None, Some(1), Some(5), Some(10)];
+        for a_start in ends {
+            for a_end in ends {
+                for b_start in ends {
+                    for b_end in ends {
+                        if matches!((a_start, a_end), (Some(s), Some(e)) if s >= e) || matches!((b_start, b_end), (Some(s), Some(e)) if s >= e) {
+                            continue;
+                        }
+
+                        for inclusive in [false, true] {
+                            let overlap = classify_endpoints(
+                                Endpoint::from_start(a_start), Endpoint::from_end(a_end),
+                                Endpoint::from_start(b_start), Endpoint::from_end(b_end),
+                                inclusive
+                            );
+                            assert_eq!(overlap, classify_any(a_start, a_end, b_start, b_end, inclusive),
+                                       "{a_start:?}, {a_end:?}, {b_start:?}, {b_end:?}, {inclusive}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Endpoint::from_start(None::<i32>), Endpoint::NegInf);
+        assert_eq!(Endpoint::from_end(None::<i32>), Endpoint::PosInf);
+        assert_eq!(Endpoint::from_end(Some(3)), Endpoint::Finite(3));
+        assert_eq!(Endpoint::from(3), Endpoint::Finite(3));
+        assert_eq!(Endpoint::Finite(3).finite(), Some(&3));
+        assert_eq!(Endpoint::<i32>::PosInf.into_option(), None);
+        assert!(!Endpoint::<i32>::NegInf.is_finite());
+    }
+}
@@ -0,0 +1,113 @@
+//! Error types returned by fallible operations in this crate.
+use std::fmt::{Debug, Display};
+use std::ops::Bound;
+
+use crate::Interval;
+
+/// Returned when an [`Interval`](crate::Interval) cannot be converted into one of the std
+/// range types because its bounds do not have the right shape, e.g. converting an interval
+/// with an included end into a [`std::ops::Range`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeConversionError;
+
+impl Display for RangeConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "interval bounds do not match the requested range type")
+    }
+}
+
+impl std::error::Error for RangeConversionError {}
+
+/// Returned by [`IntervalMap::insert`](crate::IntervalMap::insert) with [`InsertPolicy::Error`](crate::InsertPolicy::Error)
+/// when the new interval overlaps an interval already in the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlapError;
+
+impl Display for OverlapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "interval overlaps an existing entry")
+    }
+}
+
+impl std::error::Error for OverlapError {}
+
+/// Returned by [`minimal_cover`](crate::minimal_cover) when the candidate intervals cannot cover the
+/// whole target. The message includes the gap, written in interval notation.
+///
+/// ```
+/// # use range_overlap::{minimal_cover, Interval};
+/// let err = minimal_cover(&Interval::excl(0, 15), &[Interval::excl(0, 12)]).unwrap_err();
+/// assert_eq!(err.to_string(), "part of the target is not covered by any interval: [12, 15)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoverageGapError<T> {
+    /// The first part of the target, in order, that none of the candidates cover.
+    pub gap: Interval<T>,
+}
+
+impl<T: Debug> Display for CoverageGapError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "part of the target is not covered by any interval: ")?;
+        match self.gap.start() {
+            Bound::Included(s) => write!(f, "[{s:?}, ")?,
+            Bound::Excluded(s) => write!(f, "({s:?}, ")?,
+            Bound::Unbounded => write!(f, "(-inf, ")?,
+        }
+        match self.gap.end() {
+            Bound::Included(e) => write!(f, "{e:?}]"),
+            Bound::Excluded(e) => write!(f, "{e:?})"),
+            Bound::Unbounded => write!(f, "inf)"),
+        }
+    }
+}
+
+impl<T: Debug> std::error::Error for CoverageGapError<T> {}
+
+/// Identifies which of the ranges given to a function a [`RangeError`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WhichRange {
+    /// The first range, `A`. Functions that only check one range (e.g. [`Interval::new`](crate::Interval::new))
+    /// always report it as `A`.
+    A,
+
+    /// The second range, `B`.
+    B,
+
+    /// Both ranges.
+    Both,
+}
+
+impl Display for WhichRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WhichRange::A => write!(f, "range A"),
+            WhichRange::B => write!(f, "range B"),
+            WhichRange::Both => write!(f, "ranges A and B"),
+        }
+    }
+}
+
+/// Returned when the end points given for a range do not describe a valid range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum RangeError {
+    /// The start of a range is after its end. `which` says which range that was.
+    StartAfterEnd { which: WhichRange },
+
+    /// At least one end point could not be compared (e.g. it is a NaN float), so
+    /// there is no way to tell which values are in the range.
+    IncomparableEndpoints,
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeError::StartAfterEnd { which: WhichRange::Both } => write!(f, "start of both ranges is after their end"),
+            RangeError::StartAfterEnd { which } => write!(f, "start of {which} is after its end"),
+            RangeError::IncomparableEndpoints => write!(f, "range end points could not be compared"),
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
@@ -0,0 +1,71 @@
+//! C-callable entry points for classifying arrays of range pairs, so that code in other languages can
+//! hand over a whole batch at once.
+//!
+//! There is one function for each common end point type, each taking a pointer to an array of
+//! [`RangePair`]s, its length, whether the ends are inclusive, and a pointer to an array of the same
+//! length to write the results to. In C they would be declared as
+//!
+//! ```c
+//! struct RangePairI64 { int64_t a_start; int64_t a_end; int64_t b_start; int64_t b_end; };
+//!
+//! /* 0 = AContainsB, 1 = AInsideB, 2 = AEndsInB, 3 = AStartsInB, 4 = AEqualsB, 5 = None */
+//! void range_overlap_classify_i64(const struct RangePairI64 *pairs, size_t len, bool inclusive, uint8_t *out);
+//! ```
+//!
+//! and likewise `range_overlap_classify_i32`, `_u64`, `_f32`, and `_f64`.
+use crate::{classify_pairs_into, RangeOverlap, RangePair};
+
+macro_rules! ffi_classifiers {
+    ($($num:ty => $name:ident),*) => {
+        $(
+            #[doc = concat!("Classify `len` pairs of `", stringify!($num), "` ranges from `pairs`, writing the results to `out`, as")]
+            /// [`classify_pairs_into`] does. Only available with the `ffi` feature.
+            ///
+            /// # Safety
+            ///
+            /// Unless `len` is 0, `pairs` must point to `len` initialized pairs and `out` to room for `len`
+            /// results, and the two must not overlap.
+            #[no_mangle]
+            pub unsafe extern "C" fn $name(pairs: *const RangePair<$num>, len: usize, inclusive: bool, out: *mut RangeOverlap) {
+                if len == 0 {
+                    return;
+                }
+                // SAFETY: the caller promises both pointers are valid for `len` elements and do not alias
+                let (pairs, out) = unsafe { (std::slice::from_raw_parts(pairs, len), std::slice::from_raw_parts_mut(out, len)) };
+                classify_pairs_into(pairs, inclusive, out);
+            }
+        )*
+    };
+}
+
+ffi_classifiers!(
+    i32 => range_overlap_classify_i32,
+    i64 => range_overlap_classify_i64,
+    u64 => range_overlap_classify_u64,
+    f32 => range_overlap_classify_f32,
+    f64 => range_overlap_classify_f64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_through_pointers() {
+        let pairs = [
+            RangePair { a_start: 0.0, a_end: 10.0, b_start: 2.0, b_end: 4.0 },
+            RangePair { a_start: 0.0, a_end: 5.0, b_start: 5.0, b_end: 9.0 },
+            RangePair { a_start: 1.0, a_end: 2.0, b_start: 1.0, b_end: 2.0 },
+        ];
+        let mut out = [0xffu8; 3];
+        unsafe { range_overlap_classify_f64(pairs.as_ptr(), pairs.len(), false, out.as_mut_ptr().cast()) };
+        assert_eq!(out, [0, 5, 4]);
+
+        let mut out = [RangeOverlap::None; 3];
+        unsafe { range_overlap_classify_f64(pairs.as_ptr(), pairs.len(), true, out.as_mut_ptr()) };
+        assert_eq!(out, [RangeOverlap::AContainsB, RangeOverlap::AEndsInB, RangeOverlap::AEqualsB]);
+
+        // An empty batch may come with null pointers
+        unsafe { range_overlap_classify_i64(std::ptr::null(), 0, false, std::ptr::null_mut()) };
+    }
+}
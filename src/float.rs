@@ -0,0 +1,288 @@
+//! Classification helpers for floating point end points.
+use std::cmp::Ordering;
+use std::ops::Sub;
+
+use crate::{classify_any, excl_classify, incl_classify, RangeOverlap};
+
+/// A type with values that stand in for an open side of a range, such as `f64::NEG_INFINITY`
+/// and `f64::INFINITY`. This lets ranges be stored as plain values instead of `Option`s,
+/// which is convenient for large numeric arrays.
+///
+/// This is implemented for `f32` and `f64`, using negative infinity for an open start and
+/// positive infinity for an open end. It can be implemented for other types, e.g. to treat
+/// `i64::MIN` and `i64::MAX` as open.
+pub trait OpenSentinel: PartialOrd {
+    /// Returns `true` if this value, used as the start of a range, means the start is open.
+    fn is_open_start(&self) -> bool;
+
+    /// Returns `true` if this value, used as the end of a range, means the end is open.
+    fn is_open_end(&self) -> bool;
+}
+
+macro_rules! impl_open_sentinel_float {
+    ($($t:ty),*) => {
+        $(
+            impl OpenSentinel for $t {
+                fn is_open_start(&self) -> bool {
+                    *self == <$t>::NEG_INFINITY
+                }
+
+                fn is_open_end(&self) -> bool {
+                    *self == <$t>::INFINITY
+                }
+            }
+        )*
+    };
+}
+
+impl_open_sentinel_float!(f32, f64);
+
+fn start_or_open<T: OpenSentinel>(value: T) -> Option<T> {
+    if value.is_open_start() { None } else { Some(value) }
+}
+
+fn end_or_open<T: OpenSentinel>(value: T) -> Option<T> {
+    if value.is_open_end() { None } else { Some(value) }
+}
+
+/// Classify the kind of overlap between two ranges the same way as [`classify_any`], but with
+/// open sides given by sentinel values (see [`OpenSentinel`]) rather than `None`.
+///
+/// ```
+/// # use range_overlap::{classify_sentinel, classify_any, RangeOverlap};
+/// let inf = f64::INFINITY;
+/// assert_eq!(classify_sentinel(-inf, 5.0, 2.0, inf, false), RangeOverlap::AEndsInB);
+/// assert_eq!(classify_sentinel(-inf, 5.0, 2.0, inf, false), classify_any(None, Some(5.0), Some(2.0), None, false));
+/// ```
+pub fn classify_sentinel<T: OpenSentinel>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> RangeOverlap {
+    classify_any(start_or_open(a_start), end_or_open(a_end), start_or_open(b_start), end_or_open(b_end), inclusive)
+}
+
+/// A convenience function that directly returns `true` if the two ranges given, with open sides given
+/// by sentinel values, have overlap. `inclusive` has the same meaning as in [`classify_any`].
+pub fn has_sentinel_overlap<T: OpenSentinel>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> bool {
+    classify_sentinel(a_start, a_end, b_start, b_end, inclusive).has_overlap()
+}
+
+/// Wraps a value so that comparisons treat values within `eps` of each other as equal.
+struct WithinTolerance<'a, T, D> {
+    value: T,
+    eps: &'a D,
+}
+
+impl<T: Copy + PartialOrd + Sub<Output = D>, D: PartialOrd> PartialEq for WithinTolerance<'_, T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<T: Copy + PartialOrd + Sub<Output = D>, D: PartialOrd> PartialOrd for WithinTolerance<'_, T, D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // Subtract the smaller from the larger so that unsigned types can't underflow
+        let ord = self.value.partial_cmp(&other.value)?;
+        let diff = match ord {
+            Ordering::Less => other.value - self.value,
+            _ => self.value - other.value,
+        };
+        if diff <= *self.eps { Some(Ordering::Equal) } else { Some(ord) }
+    }
+}
+
+/// Classify the kind of overlap between two closed ranges, treating any two end points within `eps`
+/// of each other as equal. This is useful when end points come from measurements, where e.g. one range
+/// ending exactly where another starts will rarely be exactly true. `inclusive` has the same meaning
+/// as in [`classify_any`]; with it `false`, this behaves like [`excl_classify`], otherwise like
+/// [`incl_classify`].
+///
+/// `T` only needs to be subtractable, with `eps` being the type of the difference, so this can also
+/// be used with e.g. timestamps and durations.
+///
+/// ```
+/// # use range_overlap::{classify_with_tolerance, excl_classify, RangeOverlap};
+/// // 0.1 + 0.2 != 0.3 in floating point
+/// assert_eq!(excl_classify(0.0, 0.1 + 0.2, 0.3, 1.0), RangeOverlap::AEndsInB);
+/// assert_eq!(classify_with_tolerance(0.0, 0.1 + 0.2, 0.3, 1.0, 1e-9, false), RangeOverlap::None);
+/// assert_eq!(classify_with_tolerance(0.0, 1.0, 1e-12, 1.0 + 1e-12, 1e-9, false), RangeOverlap::AEqualsB);
+/// ```
+pub fn classify_with_tolerance<T, D>(a_start: T, a_end: T, b_start: T, b_end: T, eps: D, inclusive: bool) -> RangeOverlap
+where
+    T: Copy + PartialOrd + Sub<Output = D>,
+    D: PartialOrd,
+{
+    let [a_start, a_end, b_start, b_end] = [a_start, a_end, b_start, b_end].map(|value| WithinTolerance { value, eps: &eps });
+    if inclusive {
+        incl_classify(a_start, a_end, b_start, b_end)
+    } else {
+        excl_classify(a_start, a_end, b_start, b_end)
+    }
+}
+
+/// Floating point types that can be compared with a total order, i.e. their `total_cmp` method.
+/// Used by the `*_total` classification functions, e.g. [`classify_any_total`].
+pub trait TotalCmp: Copy {
+    /// Compare two values with IEEE 754's `totalOrder` predicate.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+impl TotalCmp for f32 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+}
+
+impl TotalCmp for f64 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+}
+
+/// Wraps a float so that comparisons go through [`TotalCmp::total_cmp`].
+#[derive(Clone, Copy)]
+struct Total<T>(T);
+
+impl<T: TotalCmp> PartialEq for Total<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl<T: TotalCmp> PartialOrd for Total<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.0.total_cmp(&other.0))
+    }
+}
+
+/// Like [`excl_classify`], but comparing the end points with `total_cmp`. Every pair of values is
+/// ordered, so NaNs give a deterministic result: positive NaNs come after positive infinity and
+/// negative NaNs before negative infinity. `-0.0` is treated as less than `0.0`.
+///
+/// ```
+/// # use range_overlap::{excl_classify_total, RangeOverlap};
+/// assert_eq!(excl_classify_total(0.0, 1.0, 0.5, f64::NAN), RangeOverlap::AEndsInB);
+/// assert_eq!(excl_classify_total(-1.0, -0.0, 0.0, 1.0), RangeOverlap::None);
+/// ```
+pub fn excl_classify_total<T: TotalCmp>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
+    excl_classify(Total(a_start), Total(a_end), Total(b_start), Total(b_end))
+}
+
+/// Like [`incl_classify`], but comparing the end points with `total_cmp`. See [`excl_classify_total`].
+pub fn incl_classify_total<T: TotalCmp>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
+    incl_classify(Total(a_start), Total(a_end), Total(b_start), Total(b_end))
+}
+
+/// Like [`classify_any`], but comparing the end points with `total_cmp`. See [`excl_classify_total`].
+pub fn classify_any_total<T: TotalCmp>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> RangeOverlap {
+    classify_any(a_start.map(Total), a_end.map(Total), b_start.map(Total), b_end.map(Total), inclusive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_classify_any() {
+        let values = [f64::NEG_INFINITY, 0.0, 1.0, 2.0, 3.0, f64::INFINITY];
+        let as_start = |v: f64| if v == f64::NEG_INFINITY { None } else { Some(v) };
+        let as_end = |v: f64| if v == f64::INFINITY { None } else { Some(v) };
+
+        for a_start in values {
+            for a_end in values {
+                for b_start in values {
+                    for b_end in values {
+                        for inclusive in [false, true] {
+                            assert_eq!(
+                                classify_sentinel(a_start, a_end, b_start, b_end, inclusive),
+                                classify_any(as_start(a_start), as_end(a_end), as_start(b_start), as_end(b_end), inclusive),
+                                "{a_start}, {a_end}, {b_start}, {b_end}, {inclusive}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_f32() {
+        let inf = f32::INFINITY;
+        assert_eq!(classify_sentinel(-inf, inf, 1.0, 2.0, true), RangeOverlap::AContainsB);
+        assert_eq!(classify_sentinel(-inf, inf, -inf, inf, false), RangeOverlap::AEqualsB);
+        assert!(!has_sentinel_overlap(-inf, 1.0, 1.0, inf, false));
+        assert!(has_sentinel_overlap(-inf, 1.0, 1.0, inf, true));
+    }
+
+    #[derive(PartialEq, PartialOrd)]
+    struct Year(i32);
+
+    impl OpenSentinel for Year {
+        fn is_open_start(&self) -> bool {
+            self.0 == 0
+        }
+
+        fn is_open_end(&self) -> bool {
+            self.0 == 9999
+        }
+    }
+
+    #[test]
+    fn test_custom_sentinel() {
+        assert_eq!(classify_sentinel(Year(0), Year(2000), Year(1990), Year(9999), false), RangeOverlap::AEndsInB);
+        assert_eq!(classify_sentinel(Year(0), Year(9999), Year(1990), Year(2000), false), RangeOverlap::AContainsB);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let eps = 0.01;
+        for inclusive in [false, true] {
+            // Without any near-equal end points, the tolerance makes no difference
+            assert_eq!(classify_with_tolerance(0.0, 1.0, 0.5, 2.0, eps, inclusive), RangeOverlap::AEndsInB);
+            assert_eq!(classify_with_tolerance(0.0, 1.0, 2.0, 3.0, eps, inclusive), RangeOverlap::None);
+
+            assert_eq!(classify_with_tolerance(0.0, 1.0, 0.005, 0.995, eps, inclusive), RangeOverlap::AEqualsB);
+            assert_eq!(classify_with_tolerance(0.0, 1.0, 0.005, 0.5, eps, inclusive), RangeOverlap::AContainsB);
+            assert_eq!(classify_with_tolerance(0.0, 0.5, 0.005, 1.0, eps, inclusive), RangeOverlap::AInsideB);
+        }
+
+        // Ends that nearly meet only overlap if the ranges are inclusive
+        assert_eq!(classify_with_tolerance(0.0, 1.0, 1.005, 2.0, eps, false), RangeOverlap::None);
+        assert_eq!(classify_with_tolerance(0.0, 1.0, 1.005, 2.0, eps, true), RangeOverlap::AEndsInB);
+        assert_eq!(classify_with_tolerance(0.0, 1.0, 0.995, 2.0, eps, false), RangeOverlap::None);
+        assert_eq!(classify_with_tolerance(1.005, 2.0, 0.0, 1.0, eps, true), RangeOverlap::AStartsInB);
+    }
+
+    #[test]
+    fn test_tolerance_unsigned() {
+        assert_eq!(classify_with_tolerance(10u32, 20, 0, 9, 1, false), RangeOverlap::None);
+        assert_eq!(classify_with_tolerance(10u32, 20, 0, 9, 1, true), RangeOverlap::AStartsInB);
+        assert_eq!(classify_with_tolerance(0u32, 20, 1, 19, 1, true), RangeOverlap::AEqualsB);
+    }
+
+    #[test]
+    fn test_total_matches_partial() {
+        let values = [-1.0, 0.0, 1.0, 2.0, 3.0];
+        for a_start in values {
+            for a_end in values {
+                for b_start in values {
+                    for b_end in values {
+                        assert_eq!(excl_classify_total(a_start, a_end, b_start, b_end), excl_classify(a_start, a_end, b_start, b_end));
+                        assert_eq!(incl_classify_total(a_start, a_end, b_start, b_end), incl_classify(a_start, a_end, b_start, b_end));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_total_nan_and_zero() {
+        let nan = f64::NAN;
+        assert_eq!(incl_classify_total(nan, nan, nan, nan), RangeOverlap::AEqualsB);
+        assert_eq!(incl_classify_total(0.0, f64::INFINITY, 1.0, nan), RangeOverlap::AEndsInB);
+        assert_eq!(incl_classify_total(-nan, 0.0, f64::NEG_INFINITY, 1.0), RangeOverlap::AEndsInB);
+        assert_eq!(classify_any_total(None, Some(nan), Some(0.0), Some(1.0), false), RangeOverlap::AContainsB);
+
+        // Signed zeros are distinct end points
+        assert_eq!(incl_classify_total(-1.0, -0.0, 0.0, 1.0), RangeOverlap::None);
+        assert_eq!(incl_classify(-1.0, -0.0, 0.0, 1.0), RangeOverlap::AEndsInB);
+        assert_eq!(excl_classify_total(-0.0f32, 1.0, 0.0, 1.0), RangeOverlap::AContainsB);
+    }
+}
@@ -0,0 +1,323 @@
+//! Aligning interval end points to a regular grid, such as minute or hour boundaries.
+use std::cmp::Ordering;
+use std::ops::{Add, Bound, Sub};
+
+use crate::bounds;
+use crate::Interval;
+
+/// A type whose values can be rounded down or up to a multiple of a step from some origin.
+///
+/// This is implemented for all of the primitive integer and float types. `step` must be positive;
+/// for integers, a zero step panics, as does a result that does not fit in the type.
+pub trait GridSnap: Sized {
+    /// The largest `origin + k * step` (for integer `k`) that is not after this value.
+    fn floor_to_grid(&self, step: &Self, origin: &Self) -> Self;
+
+    /// The smallest `origin + k * step` (for integer `k`) that is not before this value.
+    fn ceil_to_grid(&self, step: &Self, origin: &Self) -> Self;
+}
+
+macro_rules! impl_grid_snap_int {
+    ($($t:ty),*) => {
+        $(
+            impl GridSnap for $t {
+                fn floor_to_grid(&self, step: &Self, origin: &Self) -> Self {
+                    // Work with distances from the origin so unsigned types never go negative
+                    if self >= origin {
+                        origin + (self - origin) / step * step
+                    } else {
+                        let distance = origin - self;
+                        origin - (distance / step + <$t>::from(distance % step != 0)) * step
+                    }
+                }
+
+                fn ceil_to_grid(&self, step: &Self, origin: &Self) -> Self {
+                    if self >= origin {
+                        let distance = self - origin;
+                        origin + (distance / step + <$t>::from(distance % step != 0)) * step
+                    } else {
+                        origin - (origin - self) / step * step
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_grid_snap_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_grid_snap_float {
+    ($($t:ty),*) => {
+        $(
+            impl GridSnap for $t {
+                fn floor_to_grid(&self, step: &Self, origin: &Self) -> Self {
+                    origin + ((self - origin) / step).floor() * step
+                }
+
+                fn ceil_to_grid(&self, step: &Self, origin: &Self) -> Self {
+                    origin + ((self - origin) / step).ceil() * step
+                }
+            }
+        )*
+    };
+}
+
+impl_grid_snap_float!(f32, f64);
+
+/// How [`Interval::snap_to_grid`] moves each end point onto the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SnapMode {
+    /// Move the start earlier and the end later, so the snapped interval contains the original.
+    Outward,
+
+    /// Move the start later and the end earlier, so the original contains the snapped interval.
+    Inward,
+
+    /// Move each end point to the nearest grid line, rounding up when it is halfway between two.
+    Nearest,
+}
+
+fn snap_nearest<T: GridSnap + PartialOrd + Clone + Sub<Output = T>>(value: &T, step: &T, origin: &T) -> T {
+    let down = value.floor_to_grid(step, origin);
+    let up = value.ceil_to_grid(step, origin);
+    if value.clone() - down.clone() < up.clone() - value.clone() { down } else { up }
+}
+
+impl<T: GridSnap + PartialOrd + Clone + Sub<Output = T>> Interval<T> {
+    /// Move the end points of this interval onto the grid of values `origin + k * step`, in the
+    /// direction given by `mode`. Open sides stay open, and whether each end is included does not
+    /// change. Snapping [inward](SnapMode::Inward) or to the [nearest](SnapMode::Nearest) grid line
+    /// can leave an interval [empty](Interval::is_empty), if no grid line or only one falls inside it.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, SnapMode};
+    /// // Align to 15 minute buckets, counting minutes from midnight
+    /// let meeting = Interval::excl(548, 611);
+    /// assert_eq!(meeting.snap_to_grid(15, 0, SnapMode::Outward), Interval::excl(540, 615));
+    /// assert_eq!(meeting.snap_to_grid(15, 0, SnapMode::Inward), Interval::excl(555, 600));
+    /// assert_eq!(meeting.snap_to_grid(15, 0, SnapMode::Nearest), Interval::excl(555, 615));
+    /// assert_eq!(Interval::at_least(0.7).snap_to_grid(0.5, 0.25, SnapMode::Outward), Interval::at_least(0.25));
+    /// ```
+    pub fn snap_to_grid(&self, step: T, origin: T, mode: SnapMode) -> Interval<T> {
+        let (start, end) = match mode {
+            SnapMode::Outward => (
+                self.start().map(|s| s.floor_to_grid(&step, &origin)),
+                self.end().map(|e| e.ceil_to_grid(&step, &origin)),
+            ),
+            SnapMode::Inward => (
+                self.start().map(|s| s.ceil_to_grid(&step, &origin)),
+                self.end().map(|e| e.floor_to_grid(&step, &origin)),
+            ),
+            SnapMode::Nearest => (
+                self.start().map(|s| snap_nearest(s, &step, &origin)),
+                self.end().map(|e| snap_nearest(e, &step, &origin)),
+            ),
+        };
+        Interval::from_bounds(start, end)
+    }
+}
+
+/// One of the fixed windows returned by [`bucketize`], with the intervals that overlap it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Bucket<T> {
+    /// The window, from `origin + k * window_size` (included) to `origin + (k + 1) * window_size` (excluded).
+    pub window: Interval<T>,
+
+    /// The index of each interval that overlaps the window, with the part of that interval inside the
+    /// window. The lengths of these parts split each interval's length between the windows it spans.
+    pub members: Vec<(usize, Interval<T>)>,
+}
+
+/// The grid line after `line`, rounded back onto `origin + k * step` so that errors do not build up.
+fn next_grid_line<T: GridSnap + PartialOrd + Clone + Add<Output = T> + Sub<Output = T>>(line: &T, step: &T, origin: &T) -> T {
+    snap_nearest(&(line.clone() + step.clone()), step, origin)
+}
+
+/// Assign each of `intervals` to every fixed-size window it overlaps, where the windows start at `origin`
+/// and repeat every `window_size` in both directions, each including its start and excluding its end.
+/// Returns the windows that at least one interval overlaps, in order. Intervals with an open side would
+/// overlap endless windows, so they are left out, as are empty intervals.
+///
+/// Each window boundary is worked out afresh as `origin + k * window_size`, rather than by adding
+/// `window_size` over and over, so float windows stay on the grid however many there are.
+///
+/// # Panics
+///
+/// Panics if `window_size` is not positive, since the windows would then never move past an interval.
+///
+/// ```
+/// # use range_overlap::{bucketize, Interval};
+/// // Calls, in minutes since midnight, grouped by hour
+/// let calls = [Interval::excl(50, 70), Interval::excl(65, 80), Interval::excl(130, 140)];
+/// let hourly = bucketize(&calls, 60, 0);
+/// assert_eq!(hourly.len(), 3);
+/// assert_eq!(hourly[0].window, Interval::excl(0, 60));
+/// assert_eq!(hourly[0].members, [(0, Interval::excl(50, 60))]);
+/// assert_eq!(hourly[1].members, [(0, Interval::excl(60, 70)), (1, Interval::excl(65, 80))]);
+/// let minutes: i32 = hourly[1].members.iter().filter_map(|(_, part)| part.length()).sum();
+/// assert_eq!(minutes, 25);
+/// ```
+pub fn bucketize<T>(intervals: &[Interval<T>], window_size: T, origin: T) -> Vec<Bucket<T>>
+where
+    T: GridSnap + PartialOrd + Clone + Add<Output = T> + Sub<Output = T>,
+{
+    assert!(window_size > origin.clone() - origin.clone(), "bucketize needs a positive window_size");
+
+    let mut parts: Vec<(T, usize, Interval<T>)> = Vec::new();
+    for (index, interval) in intervals.iter().enumerate() {
+        if interval.is_empty() {
+            continue;
+        }
+        let (Bound::Included(start) | Bound::Excluded(start)) = interval.start() else { continue };
+        if interval.end() == Bound::Unbounded {
+            continue;
+        }
+
+        let mut window_start = start.floor_to_grid(&window_size, &origin);
+        loop {
+            let window_end = next_grid_line(&window_start, &window_size, &origin);
+            // A step too small to change values this large can go no further
+            if window_end <= window_start {
+                break;
+            }
+            let window = Interval::excl(window_start.clone(), window_end.clone());
+            if bounds::ends_before(interval.end(), window.start()) != Some(false) {
+                break;
+            }
+            if let Some(part) = interval.intersect(&window) {
+                parts.push((window_start, index, part));
+            }
+            window_start = window_end;
+        }
+    }
+    // Window starts are grid values, which are always comparable
+    parts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal).then(a.1.cmp(&b.1)));
+
+    let mut buckets: Vec<Bucket<T>> = Vec::new();
+    for (window_start, index, part) in parts {
+        match buckets.last_mut() {
+            Some(bucket) if bucket.window.start() == Bound::Included(&window_start) => bucket.members.push((index, part)),
+            _ => {
+                let window_end = next_grid_line(&window_start, &window_size, &origin);
+                let window = Interval::excl(window_start, window_end);
+                buckets.push(Bucket { window, members: vec![(index, part)] });
+            },
+        }
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Lcg;
+    use std::ops::Bound;
+
+    #[test]
+    fn test_int_matches_float() {
+        for value in -20..20 {
+            for step in 1..7 {
+                for origin in -8..8 {
+                    let as_float = |v: i32| v as f64;
+                    let (v, s, o) = (as_float(value), as_float(step), as_float(origin));
+                    assert_eq!(as_float(value.floor_to_grid(&step, &origin)), v.floor_to_grid(&s, &o), "{value} floor {step} from {origin}");
+                    assert_eq!(as_float(value.ceil_to_grid(&step, &origin)), v.ceil_to_grid(&s, &o), "{value} ceil {step} from {origin}");
+                }
+            }
+        }
+
+        // Unsigned values below the origin round down without going negative
+        assert_eq!(3u8.floor_to_grid(&5, &7), 2);
+        assert_eq!(3u8.ceil_to_grid(&5, &7), 7);
+        assert_eq!(250u8.ceil_to_grid(&10, &5), 255);
+    }
+
+    #[test]
+    fn test_snap_modes() {
+        for start in -10i32..10 {
+            for end in start..10 {
+                let a = Interval::excl(start, end);
+                let outward = a.snap_to_grid(4, 1, SnapMode::Outward);
+                let inward = a.snap_to_grid(4, 1, SnapMode::Inward);
+                let nearest = a.snap_to_grid(4, 1, SnapMode::Nearest);
+                for interval in [outward, inward, nearest] {
+                    let (Bound::Included(&s), Bound::Excluded(&e)) = (interval.start(), interval.end()) else { unreachable!() };
+                    assert_eq!((s - 1).rem_euclid(4), 0);
+                    assert_eq!((e - 1).rem_euclid(4), 0);
+                    assert!((s - start).abs() < 4 && (e - end).abs() < 4);
+                }
+
+                for p in -20..20 {
+                    assert!(!a.contains_point(&p) || outward.contains_point(&p), "{a:?} outward at {p}");
+                    assert!(!inward.contains_point(&p) || a.contains_point(&p), "{a:?} inward at {p}");
+                }
+            }
+        }
+
+        assert_eq!(Interval::incl(2, 6).snap_to_grid(4, 0, SnapMode::Nearest), Interval::incl(4, 8));
+        assert_eq!(Interval::<i32>::all().snap_to_grid(4, 0, SnapMode::Inward), Interval::all());
+    }
+
+    #[test]
+    fn test_bucketize() {
+        let mut rng = Lcg::new(8642);
+        for _ in 0..300 {
+            let intervals: Vec<Interval<i32>> = (0..rng.below(8)).map(|_| {
+                let start = rng.below(40) - 20;
+                let end = start + rng.below(15) - 1;
+                match rng.below(6) {
+                    0 => Interval::at_least(start),
+                    1 => Interval::incl(start, end),
+                    2 => Interval::from_bounds(Bound::Excluded(start), Bound::Included(end)),
+                    _ => Interval::excl(start, end),
+                }
+            }).collect();
+            let (size, origin) = (rng.below(6) + 1, rng.below(10) - 5);
+            let buckets = bucketize(&intervals, size, origin);
+
+            assert!(buckets.windows(2).all(|w| w[0].window < w[1].window));
+            for bucket in &buckets {
+                let Bound::Included(&start) = bucket.window.start() else { panic!("{bucket:?}") };
+                assert_eq!((start - origin).rem_euclid(size), 0);
+                assert_eq!(bucket.window, Interval::excl(start, start + size));
+            }
+            // Each bounded interval is in exactly the windows it overlaps, split into parts that cover it
+            for (i, interval) in intervals.iter().enumerate() {
+                let parts: Vec<(&Interval<i32>, &Interval<i32>)> = buckets.iter()
+                    .flat_map(|b| b.members.iter().filter(|(m, _)| *m == i).map(move |(_, part)| (&b.window, part)))
+                    .collect();
+                if interval.length().is_none() || interval.is_empty() {
+                    assert!(parts.is_empty());
+                    continue;
+                }
+                let windows: Vec<Interval<i32>> = (-60..60).map(|k| Interval::excl(origin + k * size, origin + (k + 1) * size)).filter(|w| w.has_overlap(interval)).collect();
+                assert_eq!(parts.iter().map(|(w, _)| **w).collect::<Vec<_>>(), windows, "{interval:?}");
+                assert!(parts.iter().all(|(w, part)| Some(**part) == interval.intersect(w)));
+                assert_eq!(parts.iter().map(|(_, part)| part.length().unwrap()).sum::<i32>(), interval.length().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_bucketize_rejects_non_positive_step() {
+        let intervals = [Interval::excl(0.0, 1.0)];
+        for step in [0.0, -1.0, f64::NAN] {
+            assert!(std::panic::catch_unwind(|| bucketize(&intervals, step, 0.0)).is_err(), "{step}");
+        }
+        assert!(std::panic::catch_unwind(|| bucketize(&[Interval::excl(0, 5)], 0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_bucketize_long_float_runs() {
+        // A tenth is not exact in binary, so adding it 10,000 times would drift off the grid
+        let buckets = bucketize(&[Interval::excl(0.0, 1000.0)], 0.1, 0.0);
+        assert_eq!(buckets.len(), 10_000);
+        for (k, bucket) in buckets.iter().enumerate() {
+            assert_eq!(bucket.window.start(), Bound::Included(&(k as f64 * 0.1)), "window {k}");
+        }
+        // and each window ends exactly where the next one starts
+        assert!(buckets.windows(2).all(|w| matches!((w[0].window.end(), w[1].window.start()), (Bound::Excluded(a), Bound::Included(b)) if a == b)));
+    }
+}
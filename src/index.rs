@@ -0,0 +1,245 @@
+//! A read-only interval index, built once from a collection of intervals and then queried many times.
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::Interval;
+
+/// An immutable index of intervals, each with a value, for finding the intervals that overlap a point
+/// or another interval. It answers the same queries as [`IntervalTree`](crate::IntervalTree) in the same
+/// `O(log n + k)` time, but is built all at once and stored in flat arrays, so it is smaller and faster
+/// to query. Use it when the intervals are known up front and queried many times.
+///
+/// The intervals form a balanced binary search tree laid out in breadth-first (Eytzinger) order: the
+/// root is at position 0 and the children of position `k` are at `2k + 1` and `2k + 2`, so no pointers are
+/// stored, and the levels near the root, which every query visits, sit together at the front of the array.
+/// Each position also records the latest end of any interval in its subtree, and the values are kept in a
+/// separate array, so a query only reads the values of the intervals it returns.
+///
+/// ```
+/// # use range_overlap::{Interval, IntervalIndex};
+/// let index: IntervalIndex<_, _> = [
+///     (Interval::excl(0, 100), "a.log"),
+///     (Interval::excl(50, 150), "b.log"),
+///     (Interval::excl(200, 300), "c.log"),
+/// ].into_iter().collect();
+///
+/// let at_75: Vec<_> = index.query_point(&75).into_iter().map(|(_, name)| *name).collect();
+/// assert_eq!(at_75, ["a.log", "b.log"]);
+/// let names: Vec<_> = index.query_interval(&Interval::excl(120, 250)).into_iter().map(|(_, name)| *name).collect();
+/// assert_eq!(names, ["b.log", "c.log"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntervalIndex<T, V> {
+    nodes: Vec<IndexNode<T>>,
+    values: Vec<V>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexNode<T> {
+    interval: Interval<T>,
+    /// The latest end of any interval in the subtree rooted here
+    max_end: Bound<T>,
+}
+
+/// The position after `k` in an in-order walk of a breadth-first tree of `len` positions.
+fn in_order_successor(k: usize, len: usize) -> Option<usize> {
+    if 2 * k + 2 < len {
+        let mut next = 2 * k + 2;
+        while 2 * next + 1 < len {
+            next = 2 * next + 1;
+        }
+        return Some(next);
+    }
+    // Climb while this is a right child; the parent of the first left child reached comes next
+    let mut k = k;
+    while k > 0 && k.is_multiple_of(2) {
+        k = (k - 1) / 2;
+    }
+    (k > 0).then(|| (k - 1) / 2)
+}
+
+/// The positions of a breadth-first tree of `len` positions, in order.
+fn in_order(len: usize) -> impl Iterator<Item = usize> {
+    let mut first = 0;
+    while 2 * first + 1 < len {
+        first = 2 * first + 1;
+    }
+    std::iter::successors((len > 0).then_some(first), move |&k| in_order_successor(k, len))
+}
+
+impl<T, V> IntervalIndex<T, V> {
+    /// Returns `true` if there are no intervals in this index.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The number of intervals in this index.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Iterate over the intervals in this index with their values, ordered by interval.
+    pub fn iter(&self) -> impl Iterator<Item = (&Interval<T>, &V)> {
+        in_order(self.nodes.len()).map(|k| (&self.nodes[k].interval, &self.values[k]))
+    }
+}
+
+impl<T: PartialOrd + Clone, V> IntervalIndex<T, V> {
+    /// Build an index from `entries`. Empty intervals (including those whose end points cannot be
+    /// compared) would never be found by a query, so they are left out.
+    pub fn new(entries: Vec<(Interval<T>, V)>) -> Self {
+        let mut entries: Vec<(Interval<T>, V)> = entries.into_iter().filter(|(iv, _)| !iv.is_empty()).collect();
+        // Non-empty intervals can always be compared
+        entries.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("end points are comparable"));
+
+        // Walking the tree in order visits the sorted entries in turn
+        let len = entries.len();
+        let mut slots: Vec<Option<(Interval<T>, V)>> = (0..len).map(|_| None).collect();
+        for (k, entry) in in_order(len).zip(entries) {
+            slots[k] = Some(entry);
+        }
+        let (mut nodes, values): (Vec<IndexNode<T>>, Vec<V>) = slots.into_iter().map(|slot| {
+            let (interval, value) = slot.expect("an in-order walk visits every position");
+            let max_end = interval.end().cloned();
+            (IndexNode { interval, max_end }, value)
+        }).unzip();
+
+        // Children come after their parents, so working backwards fills in each subtree before its root
+        for k in (1..len).rev() {
+            let parent = (k - 1) / 2;
+            let later = bounds::later_end(nodes[parent].max_end.as_ref(), nodes[k].max_end.as_ref()).map(|b| b.cloned());
+            if let Some(later) = later {
+                nodes[parent].max_end = later;
+            }
+        }
+        Self { nodes, values }
+    }
+
+    fn find_overlapping<'a>(&'a self, query: &Interval<T>, k: usize, found: &mut Vec<(&'a Interval<T>, &'a V)>) {
+        let Some(node) = self.nodes.get(k) else { return };
+        // Nothing in this subtree ends late enough to reach the query
+        if bounds::ends_before(node.max_end.as_ref(), query.start()) != Some(false) {
+            return;
+        }
+        self.find_overlapping(query, 2 * k + 1, found);
+        if node.interval.has_overlap(query) {
+            found.push((&node.interval, &self.values[k]));
+        }
+        // Everything in the right subtree starts no earlier than this entry does
+        if bounds::ends_before(query.end(), node.interval.start()) == Some(false) {
+            self.find_overlapping(query, 2 * k + 2, found);
+        }
+    }
+
+    /// The intervals that overlap `interval`, with their values, ordered by interval.
+    pub fn query_interval(&self, interval: &Interval<T>) -> Vec<(&Interval<T>, &V)> {
+        let mut found = Vec::new();
+        self.find_overlapping(interval, 0, &mut found);
+        found
+    }
+
+    /// The intervals that contain `point`, with their values, ordered by interval.
+    pub fn query_point(&self, point: &T) -> Vec<(&Interval<T>, &V)> {
+        self.query_interval(&Interval::point(point.clone()))
+    }
+
+    /// The intervals in the index that contain `point`, found by the same search as [`query_point`](Self::query_point).
+    pub fn stab(&self, point: &T) -> Vec<(&Interval<T>, &V)> {
+        self.query_point(point)
+    }
+}
+
+impl<T: PartialOrd + Clone, V> FromIterator<(Interval<T>, V)> for IntervalIndex<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, V)>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval, sample_intervals};
+    use crate::IntervalTree;
+
+    #[test]
+    fn test_matches_tree() {
+        let mut entries = Vec::new();
+        let mut rng = Lcg::new(13579);
+        for value in 0..700 {
+            entries.push((random_interval(&mut rng, 300, 25), value));
+        }
+
+        let index: IntervalIndex<i32, i32> = entries.iter().copied().collect();
+        let tree: IntervalTree<i32, i32> = entries.iter().copied().collect();
+        assert_eq!(index.len(), tree.len());
+        assert_eq!(index.iter().map(|(iv, _)| iv).collect::<Vec<_>>(), tree.to_vec().into_iter().map(|(iv, _)| iv).collect::<Vec<_>>());
+
+        let values = |found: Vec<(&Interval<i32>, &i32)>| {
+            let mut values: Vec<i32> = found.into_iter().map(|(_, &v)| v).collect();
+            values.sort();
+            values
+        };
+        for start in -5..330 {
+            assert_eq!(values(index.query_point(&start)), values(tree.query_point(&start)), "at {start}");
+            for query in [Interval::excl(start, start + 9), Interval::less_than(start), Interval::greater_than(start), Interval::excl(start, start)] {
+                let found = index.query_interval(&query);
+                assert!(found.windows(2).all(|w| w[0].0 <= w[1].0));
+                let expected: Vec<(&Interval<i32>, &i32)> = entries.iter().filter(|(iv, _)| iv.has_overlap(&query)).map(|(iv, v)| (iv, v)).collect();
+                assert_eq!(values(found), values(expected), "{query:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_layout() {
+        for len in 0..70 {
+            let order: Vec<usize> = in_order(len).collect();
+            let mut sorted = order.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..len).collect::<Vec<_>>(), "{len}");
+
+            // An in-order walk of the tree shape visits left subtrees before their roots, and roots before right subtrees
+            let rank: Vec<usize> = (0..len).map(|k| order.iter().position(|&o| o == k).unwrap()).collect();
+            for k in 0..len {
+                for child in [2 * k + 1, 2 * k + 2].into_iter().filter(|&c| c < len) {
+                    assert_eq!(rank[child] < rank[k], child == 2 * k + 1, "{len}");
+                }
+            }
+        }
+
+        let index: IntervalIndex<i32, usize> = (0..50).map(|i| (Interval::excl(i, i + (i * 7) % 13 + 1), i as usize)).collect();
+        for k in 0..index.len() {
+            let mut subtree = vec![k];
+            let mut latest = index.nodes[k].interval.end();
+            while let Some(j) = subtree.pop() {
+                latest = bounds::later_end(latest, index.nodes[j].interval.end()).unwrap();
+                subtree.extend([2 * j + 1, 2 * j + 2].into_iter().filter(|&c| c < index.len()));
+            }
+            assert_eq!(index.nodes[k].max_end.as_ref(), latest);
+        }
+    }
+
+    #[test]
+    fn test_small_indices() {
+        let index: IntervalIndex<f64, ()> = IntervalIndex::new(Vec::new());
+        assert!(index.is_empty());
+        assert!(index.query_point(&1.0).is_empty());
+
+        let index: IntervalIndex<f64, char> = [(Interval::excl(f64::NAN, 1.0), 'a'), (Interval::at_least(0.5), 'b')].into_iter().collect();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.query_point(&0.75), [(&Interval::at_least(0.5), &'b')]);
+        assert!(index.query_point(&0.25).is_empty());
+    }
+
+    #[test]
+    fn test_stab() {
+        let stored: Vec<(Interval<i32>, usize)> = sample_intervals(5).into_iter().zip(0..).collect();
+        let index: IntervalIndex<i32, usize> = stored.iter().copied().collect();
+        for point in -1..=5 {
+            let expected: Vec<usize> = stored.iter().filter(|(iv, _)| iv.contains_point(&point)).map(|(_, v)| *v).collect();
+            let mut found: Vec<usize> = index.stab(&point).into_iter().map(|(_, &v)| v).collect();
+            found.sort();
+            assert_eq!(found, expected, "at {point}");
+        }
+    }
+}
@@ -0,0 +1,614 @@
+//! A first-class interval type, so that ranges can be passed around as a single value
+//! rather than as four loose end points.
+use std::ops::{Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::bounds;
+use crate::error::{RangeConversionError, RangeError, WhichRange};
+use crate::RangeOverlap;
+
+/// A range of values, each end of which may be included, excluded, or open.
+///
+/// The constructors follow the same conventions as the rest of the crate: [`Interval::new`]
+/// takes `Option`s for the start and end (with `None` meaning that side is open) and an
+/// `inclusive` flag for whether the end is part of the range. The start is always included.
+/// If you need finer control over each end, use [`Interval::from_bounds`].
+///
+/// ```
+/// # use range_overlap::{Interval, RangeOverlap};
+/// let a = Interval::excl(1, 10);
+/// let b = Interval::new(Some(5), None, false).unwrap();
+/// assert_eq!(a.classify_against(&b), RangeOverlap::AEndsInB);
+/// assert!(a.has_overlap(&b));
+/// assert!(a.contains_point(&9));
+/// assert!(!a.contains_point(&10));
+/// ```
+///
+/// Intervals are ordered by their start, then by their end. For starts, an open start comes
+/// before any value, and an included start comes before an excluded start at the same value.
+/// For ends, an excluded end comes before an included end at the same value, and an open end
+/// comes after any value. In other words, whichever interval lets in values earlier sorts first,
+/// and ties are broken by whichever interval stops letting in values first:
+///
+/// ```
+/// # use range_overlap::Interval;
+/// use std::ops::Bound;
+///
+/// assert!(Interval::less_than(0) < Interval::incl(0, 5));
+/// assert!(Interval::incl(0, 5) < Interval::from_bounds(Bound::Excluded(0), Bound::Included(5)));
+/// assert!(Interval::excl(0, 5) < Interval::incl(0, 5));
+/// assert!(Interval::incl(0, 5) < Interval::at_least(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Interval<T> {
+    start: Bound<T>,
+    end: Bound<T>,
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// Create a new interval. `start` and `end` may be `None` to indicate that side is open,
+    /// and `inclusive` indicates whether `end` is part of the interval.
+    ///
+    /// Returns [`RangeError::StartAfterEnd`] if `start` is after `end`, or
+    /// [`RangeError::IncomparableEndpoints`] if either could not be compared (e.g. a NaN).
+    /// `start` equal to `end` is allowed, even if the interval is exclusive. With only one range to
+    /// check, `StartAfterEnd` always names it [`WhichRange::A`].
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeError, WhichRange};
+    /// assert!(Interval::new(Some(1), Some(5), false).is_ok());
+    /// assert_eq!(Interval::new(Some(5), Some(1), false), Err(RangeError::StartAfterEnd { which: WhichRange::A }));
+    /// assert_eq!(Interval::new(Some(f64::NAN), None, false), Err(RangeError::IncomparableEndpoints));
+    /// ```
+    pub fn new(start: Option<T>, end: Option<T>, inclusive: bool) -> Result<Self, RangeError> {
+        let interval = Self::new_unchecked(start, end, inclusive);
+        interval.validate()?;
+        Ok(interval)
+    }
+
+    /// Check that this interval's start is not after its end and that its end points can be compared.
+    /// Intervals created with [`Interval::new`] have already been checked; this is useful for ones built
+    /// from the unchecked constructors.
+    ///
+    /// The errors are the same as from [`Interval::new`]: a start after the end is always reported
+    /// as [`WhichRange::A`], since there is no other range for it to be.
+    pub fn validate(&self) -> Result<(), RangeError> {
+        for value in [&self.start, &self.end] {
+            if let Bound::Included(v) | Bound::Excluded(v) = value {
+                // Only NaN-like values are not comparable to themselves
+                if v.partial_cmp(v).is_none() {
+                    return Err(RangeError::IncomparableEndpoints);
+                }
+            }
+        }
+
+        match (self.start(), self.end()) {
+            (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) => {
+                match s.partial_cmp(e) {
+                    Some(std::cmp::Ordering::Greater) => Err(RangeError::StartAfterEnd { which: WhichRange::A }),
+                    Some(_) => Ok(()),
+                    None => Err(RangeError::IncomparableEndpoints),
+                }
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<T> Interval<T> {
+    /// Create a new interval in the same way as [`Interval::new`], but without checking that
+    /// `start` is not after `end`.
+    pub fn new_unchecked(start: Option<T>, end: Option<T>, inclusive: bool) -> Self {
+        let start = match start {
+            Some(s) => Bound::Included(s),
+            None => Bound::Unbounded,
+        };
+        let end = match (end, inclusive) {
+            (Some(e), true) => Bound::Included(e),
+            (Some(e), false) => Bound::Excluded(e),
+            (None, _) => Bound::Unbounded,
+        };
+        Self { start, end }
+    }
+
+    /// Create a new closed interval from `start` up to but not including `end`, like `start..end`.
+    /// Like the std range types, this does not check that `start` is not after `end`.
+    pub fn excl(start: T, end: T) -> Self {
+        Self { start: Bound::Included(start), end: Bound::Excluded(end) }
+    }
+
+    /// Create a new closed interval from `start` up to and including `end`, like `start..=end`.
+    /// Like the std range types, this does not check that `start` is not after `end`.
+    pub fn incl(start: T, end: T) -> Self {
+        Self { start: Bound::Included(start), end: Bound::Included(end) }
+    }
+
+    /// Create an interval containing the single value `value`, i.e. `value..=value`.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeOverlap};
+    /// // A point at the start of an exclusive interval is inside it, but one at the end is not.
+    /// assert_eq!(Interval::point(1).classify_against(&Interval::excl(1, 5)), RangeOverlap::AInsideB);
+    /// assert_eq!(Interval::point(5).classify_against(&Interval::excl(1, 5)), RangeOverlap::None);
+    /// ```
+    pub fn point(value: T) -> Self where T: Clone {
+        Self { start: Bound::Included(value.clone()), end: Bound::Included(value) }
+    }
+
+    /// Create an interval containing `value` and everything after it, i.e. `value..`.
+    pub fn at_least(value: T) -> Self {
+        Self { start: Bound::Included(value), end: Bound::Unbounded }
+    }
+
+    /// Create an interval containing everything after `value`, but not `value` itself.
+    pub fn greater_than(value: T) -> Self {
+        Self { start: Bound::Excluded(value), end: Bound::Unbounded }
+    }
+
+    /// Create an interval containing `value` and everything before it, i.e. `..=value`.
+    pub fn at_most(value: T) -> Self {
+        Self { start: Bound::Unbounded, end: Bound::Included(value) }
+    }
+
+    /// Create an interval containing everything before `value`, but not `value` itself, i.e. `..value`.
+    pub fn less_than(value: T) -> Self {
+        Self { start: Bound::Unbounded, end: Bound::Excluded(value) }
+    }
+
+    /// Create an interval containing every value, i.e. `..`.
+    pub fn all() -> Self {
+        Self { start: Bound::Unbounded, end: Bound::Unbounded }
+    }
+
+    /// Create an interval including both `a` and `b` and everything between them. Unlike
+    /// [`Interval::incl`], the values can be given in either order.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::between(9, 3), Interval::incl(3, 9));
+    /// assert_eq!(Interval::between(3, 9), Interval::incl(3, 9));
+    /// ```
+    pub fn between(a: T, b: T) -> Self where T: PartialOrd {
+        if b < a {
+            Self::incl(b, a)
+        } else {
+            Self::incl(a, b)
+        }
+    }
+
+    /// Create a new interval with full control over whether each end is included, excluded,
+    /// or open. This does not check that `start` is not after `end`; call [`Interval::validate`]
+    /// if that is needed.
+    pub fn from_bounds(start: Bound<T>, end: Bound<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// The start of the interval.
+    pub fn start(&self) -> Bound<&T> {
+        self.start.as_ref()
+    }
+
+    /// The end of the interval.
+    pub fn end(&self) -> Bound<&T> {
+        self.end.as_ref()
+    }
+
+    /// Consume the interval, returning its start and end bounds.
+    pub fn into_bounds(self) -> (Bound<T>, Bound<T>) {
+        (self.start, self.end)
+    }
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`).
+    ///
+    /// Because each interval carries its own bounds, this correctly handles comparing an interval
+    /// with an inclusive end against one with an exclusive end. If any end points cannot be compared
+    /// (e.g. they are NaNs), or either interval is [empty](Interval::is_empty), this returns [`RangeOverlap::None`].
+    pub fn classify_against(&self, other: &Interval<T>) -> RangeOverlap {
+        bounds::classify_bound_refs(self.start(), self.end(), other.start(), other.end())
+    }
+
+    /// Returns `true` if this interval and `other` share any points.
+    pub fn has_overlap(&self, other: &Interval<T>) -> bool {
+        self.classify_against(other).has_overlap()
+    }
+
+    /// Returns `true` if `point` lies within this interval.
+    pub fn contains_point(&self, point: &T) -> bool {
+        bounds::contains_point_ref(self.start(), self.end(), point)
+    }
+
+    /// Returns `true` if no value can lie in this interval, e.g. `[5, 5)` or `(5, 5]`.
+    /// An interval with an open side is never empty, unless its other end cannot be
+    /// compared (e.g. it is a NaN).
+    pub fn is_empty(&self) -> bool {
+        bounds::is_empty_ref(self.start(), self.end())
+    }
+
+    /// Swap the start and end values if the start is after the end, reporting whether a swap
+    /// was needed. This is meant for end points that come from user input and may have been
+    /// given in the wrong order.
+    ///
+    /// When the values are swapped, whether each end is included stays with that end, so a
+    /// reversed exclusive interval `[5, 1)` becomes `[1, 5)`. An interval that was not reversed
+    /// but still has no values in it (e.g. `[5, 5)`, or one with NaN end points) is returned
+    /// unchanged with [`Normalized::Empty`].
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, Normalized};
+    /// assert_eq!(Interval::excl(5, 1).normalized(), (Interval::excl(1, 5), Normalized::Swapped));
+    /// assert_eq!(Interval::excl(1, 5).normalized(), (Interval::excl(1, 5), Normalized::Unchanged));
+    /// assert_eq!(Interval::excl(5, 5).normalized(), (Interval::excl(5, 5), Normalized::Empty));
+    /// ```
+    pub fn normalized(self) -> (Self, Normalized) {
+        let reversed = matches!(
+            (self.start(), self.end()),
+            (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) if s > e
+        );
+
+        if reversed {
+            let (start, end) = match self.into_bounds() {
+                (Bound::Included(s), Bound::Included(e)) => (Bound::Included(e), Bound::Included(s)),
+                (Bound::Included(s), Bound::Excluded(e)) => (Bound::Included(e), Bound::Excluded(s)),
+                (Bound::Excluded(s), Bound::Included(e)) => (Bound::Excluded(e), Bound::Included(s)),
+                (Bound::Excluded(s), Bound::Excluded(e)) => (Bound::Excluded(e), Bound::Excluded(s)),
+                _ => unreachable!("a reversed interval has two finite ends"),
+            };
+            (Self { start, end }, Normalized::Swapped)
+        } else if self.is_empty() {
+            (self, Normalized::Empty)
+        } else {
+            (self, Normalized::Unchanged)
+        }
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Interval<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        let starts = bounds::cmp_starts(self.start(), other.start())?;
+        let ends = bounds::cmp_ends(self.end(), other.end())?;
+        Some(starts.then(ends))
+    }
+}
+
+impl<T: Ord> Ord for Interval<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.partial_cmp(other).expect("values with a total order are always comparable")
+    }
+}
+
+/// The result of [`Interval::normalized`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Normalized {
+    /// The interval's start was after its end, so they were swapped.
+    Swapped,
+
+    /// The interval was already in order.
+    Unchanged,
+
+    /// The interval was not reversed, but contains no values.
+    Empty,
+}
+
+impl<T> RangeBounds<T> for Interval<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.start()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.end()
+    }
+}
+
+/// Lets iterators over borrowed intervals be used wherever ranges are expected, e.g.
+/// `intervals.iter().overlapping_pairs()`.
+impl<T> RangeBounds<T> for &Interval<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.start()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.end()
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(value: Range<T>) -> Self {
+        Self::excl(value.start, value.end)
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for Interval<T> {
+    fn from(value: RangeInclusive<T>) -> Self {
+        let (start, end) = value.into_inner();
+        Self::incl(start, end)
+    }
+}
+
+impl<T> From<RangeFrom<T>> for Interval<T> {
+    fn from(value: RangeFrom<T>) -> Self {
+        Self::from_bounds(Bound::Included(value.start), Bound::Unbounded)
+    }
+}
+
+impl<T> From<RangeTo<T>> for Interval<T> {
+    fn from(value: RangeTo<T>) -> Self {
+        Self::from_bounds(Bound::Unbounded, Bound::Excluded(value.end))
+    }
+}
+
+impl<T> From<RangeToInclusive<T>> for Interval<T> {
+    fn from(value: RangeToInclusive<T>) -> Self {
+        Self::from_bounds(Bound::Unbounded, Bound::Included(value.end))
+    }
+}
+
+impl<T> From<RangeFull> for Interval<T> {
+    fn from(_value: RangeFull) -> Self {
+        Self::from_bounds(Bound::Unbounded, Bound::Unbounded)
+    }
+}
+
+impl<T> TryFrom<Interval<T>> for Range<T> {
+    type Error = RangeConversionError;
+
+    fn try_from(value: Interval<T>) -> Result<Self, Self::Error> {
+        match value.into_bounds() {
+            (Bound::Included(start), Bound::Excluded(end)) => Ok(start..end),
+            _ => Err(RangeConversionError),
+        }
+    }
+}
+
+impl<T> TryFrom<Interval<T>> for RangeInclusive<T> {
+    type Error = RangeConversionError;
+
+    fn try_from(value: Interval<T>) -> Result<Self, Self::Error> {
+        match value.into_bounds() {
+            (Bound::Included(start), Bound::Included(end)) => Ok(start..=end),
+            _ => Err(RangeConversionError),
+        }
+    }
+}
+
+impl<T> TryFrom<Interval<T>> for RangeFrom<T> {
+    type Error = RangeConversionError;
+
+    fn try_from(value: Interval<T>) -> Result<Self, Self::Error> {
+        match value.into_bounds() {
+            (Bound::Included(start), Bound::Unbounded) => Ok(start..),
+            _ => Err(RangeConversionError),
+        }
+    }
+}
+
+impl<T> TryFrom<Interval<T>> for RangeTo<T> {
+    type Error = RangeConversionError;
+
+    fn try_from(value: Interval<T>) -> Result<Self, Self::Error> {
+        match value.into_bounds() {
+            (Bound::Unbounded, Bound::Excluded(end)) => Ok(..end),
+            _ => Err(RangeConversionError),
+        }
+    }
+}
+
+impl<T> TryFrom<Interval<T>> for RangeToInclusive<T> {
+    type Error = RangeConversionError;
+
+    fn try_from(value: Interval<T>) -> Result<Self, Self::Error> {
+        match value.into_bounds() {
+            (Bound::Unbounded, Bound::Included(end)) => Ok(..=end),
+            _ => Err(RangeConversionError),
+        }
+    }
+}
+
+impl<T> TryFrom<Interval<T>> for RangeFull {
+    type Error = RangeConversionError;
+
+    fn try_from(value: Interval<T>) -> Result<Self, Self::Error> {
+        match value.into_bounds() {
+            (Bound::Unbounded, Bound::Unbounded) => Ok(..),
+            _ => Err(RangeConversionError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify_any;
+
+    #[test]
+    fn test_new_matches_classify_any() {
+        let ends = [None, Some(1), Some(5), Some(10)];
+        for a_start in ends {
+            for a_end in ends {
+                for b_start in ends {
+                    for b_end in ends {
+                        if matches!((a_start, a_end), (Some(s), Some(e)) if s >= e) || matches!((b_start, b_end), (Some(s), Some(e)) if s >= e) {
+                            continue;
+                        }
+
+                        for inclusive in [false, true] {
+                            let a = Interval::new(a_start, a_end, inclusive).unwrap();
+                            let b = Interval::new(b_start, b_end, inclusive).unwrap();
+                            assert_eq!(a.classify_against(&b), classify_any(a_start, a_end, b_start, b_end, inclusive),
+                                       "{a:?} vs {b:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixed_inclusivity() {
+        let a = Interval::incl(1, 5);
+        let b = Interval::excl(5, 10);
+        assert_eq!(a.classify_against(&b), RangeOverlap::AEndsInB);
+        assert_eq!(b.classify_against(&a), RangeOverlap::AStartsInB);
+
+        let c = Interval::excl(1, 5);
+        assert_eq!(c.classify_against(&b), RangeOverlap::None);
+        assert_eq!(a.classify_against(&c), RangeOverlap::AContainsB);
+        assert_eq!(c.classify_against(&a), RangeOverlap::AInsideB);
+    }
+
+    #[test]
+    fn test_std_range_conversions() {
+        assert_eq!(Interval::from(1..5), Interval::excl(1, 5));
+        assert_eq!(Interval::from(1..=5), Interval::incl(1, 5));
+        assert_eq!(Interval::from(1..), Interval::new(Some(1), None, false).unwrap());
+        assert_eq!(Interval::from(..5), Interval::new(None, Some(5), false).unwrap());
+        assert_eq!(Interval::from(..=5), Interval::new(None, Some(5), true).unwrap());
+        assert_eq!(Interval::<i32>::from(..), Interval::new(None, None, false).unwrap());
+
+        assert_eq!(Range::try_from(Interval::excl(1, 5)), Ok(1..5));
+        assert_eq!(RangeInclusive::try_from(Interval::incl(1, 5)), Ok(1..=5));
+        assert_eq!(RangeFrom::try_from(Interval::from(1..)), Ok(1..));
+        assert_eq!(RangeTo::try_from(Interval::from(..5)), Ok(..5));
+        assert_eq!(RangeToInclusive::try_from(Interval::from(..=5)), Ok(..=5));
+        assert_eq!(RangeFull::try_from(Interval::<i32>::from(..)), Ok(..));
+
+        assert_eq!(Range::try_from(Interval::incl(1, 5)), Err(RangeConversionError));
+        assert_eq!(RangeInclusive::try_from(Interval::excl(1, 5)), Err(RangeConversionError));
+        assert_eq!(RangeFull::try_from(Interval::from(1..)), Err(RangeConversionError));
+        assert_eq!(Range::try_from(Interval::from_bounds(Bound::Excluded(1), Bound::Excluded(5))), Err(RangeConversionError));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(Interval::new(Some(1), Some(1), false), Ok(Interval::excl(1, 1)));
+        assert_eq!(Interval::new(Some(2), Some(1), true), Err(RangeError::StartAfterEnd { which: WhichRange::A }));
+        assert_eq!(Interval::new(Some(2), None, true), Ok(Interval::from(2..)));
+        assert_eq!(Interval::new(None, Some(f64::NAN), true), Err(RangeError::IncomparableEndpoints));
+        assert_eq!(Interval::new(Some(1.0), Some(f64::NAN), true), Err(RangeError::IncomparableEndpoints));
+
+        assert_eq!(Interval::new_unchecked(Some(2), Some(1), true), Interval::incl(2, 1));
+        assert_eq!(Interval::incl(2, 1).validate(), Err(RangeError::StartAfterEnd { which: WhichRange::A }));
+        assert_eq!(Interval::from_bounds(Bound::Excluded(1), Bound::Excluded(1)).validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(Interval::excl(5, 5).is_empty());
+        assert!(Interval::incl(6, 5).is_empty());
+        assert!(Interval::from_bounds(Bound::Excluded(5), Bound::Included(5)).is_empty());
+        assert!(!Interval::incl(5, 5).is_empty());
+        assert!(!Interval::from(5..).is_empty());
+
+        assert_eq!(Interval::excl(5, 5).classify_against(&Interval::excl(1, 10)), RangeOverlap::None);
+        assert_eq!(Interval::excl(1, 10).classify_against(&Interval::excl(5, 5)), RangeOverlap::None);
+        assert_eq!(Interval::incl(5, 5).classify_against(&Interval::excl(1, 10)), RangeOverlap::AInsideB);
+        assert!(!Interval::excl(5, 5).has_overlap(&Interval::excl(5, 5)));
+    }
+
+    #[test]
+    fn test_point() {
+        let p = Interval::point(5);
+        assert!(!p.is_empty());
+        assert!(p.contains_point(&5));
+        assert_eq!(p.classify_against(&Interval::point(5)), RangeOverlap::AEqualsB);
+        assert_eq!(p.classify_against(&Interval::point(6)), RangeOverlap::None);
+
+        for (other, expected) in [
+            (Interval::excl(1, 5), RangeOverlap::None),
+            (Interval::incl(1, 5), RangeOverlap::AInsideB),
+            (Interval::excl(5, 10), RangeOverlap::AInsideB),
+            (Interval::from_bounds(Bound::Excluded(5), Bound::Included(10)), RangeOverlap::None),
+            (Interval::from(..5), RangeOverlap::None),
+            (Interval::from(..=5), RangeOverlap::AInsideB),
+            (Interval::from(5..), RangeOverlap::AInsideB),
+        ] {
+            assert_eq!(p.classify_against(&other), expected, "{other:?}");
+            assert_eq!(other.classify_against(&p), match expected {
+                RangeOverlap::AInsideB => RangeOverlap::AContainsB,
+                o => o,
+            }, "{other:?}");
+        }
+    }
+
+    #[test]
+    fn test_named_constructors() {
+        assert_eq!(Interval::at_least(3), Interval::from(3..));
+        assert_eq!(Interval::at_most(9), Interval::from(..=9));
+        assert_eq!(Interval::less_than(9), Interval::from(..9));
+        assert_eq!(Interval::<i32>::all(), Interval::from(..));
+        assert_eq!(Interval::between(1, 5), Interval::incl(1, 5));
+        assert_eq!(Interval::between(5, 1), Interval::incl(1, 5));
+
+        let gt = Interval::greater_than(3);
+        assert!(!gt.contains_point(&3));
+        assert!(gt.contains_point(&4));
+        assert_eq!(gt.classify_against(&Interval::at_most(3)), RangeOverlap::None);
+        assert_eq!(Interval::at_least(3).classify_against(&Interval::at_most(3)), RangeOverlap::AStartsInB);
+        assert_eq!(Interval::all().classify_against(&Interval::at_most(3)), RangeOverlap::AContainsB);
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(Interval::incl(5, 1).normalized(), (Interval::incl(1, 5), Normalized::Swapped));
+        assert_eq!(Interval::incl(1, 5).normalized(), (Interval::incl(1, 5), Normalized::Unchanged));
+        assert_eq!(Interval::incl(5, 5).normalized(), (Interval::incl(5, 5), Normalized::Unchanged));
+        assert_eq!(Interval::excl(5, 5).normalized(), (Interval::excl(5, 5), Normalized::Empty));
+        assert_eq!(Interval::at_least(5).normalized(), (Interval::at_least(5), Normalized::Unchanged));
+
+        let reversed = Interval::from_bounds(Bound::Excluded(5), Bound::Included(1));
+        assert_eq!(reversed.normalized(), (Interval::from_bounds(Bound::Excluded(1), Bound::Included(5)), Normalized::Swapped));
+
+        let (_, status) = Interval::incl(f64::NAN, 1.0).normalized();
+        assert_eq!(status, Normalized::Empty);
+    }
+
+    #[test]
+    fn test_ordering() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let intervals = [
+            Interval::less_than(0),
+            Interval::at_most(0),
+            Interval::all(),
+            Interval::excl(0, 5),
+            Interval::incl(0, 5),
+            Interval::at_least(0),
+            Interval::from_bounds(Bound::Excluded(0), Bound::Excluded(5)),
+            Interval::greater_than(0),
+            Interval::incl(1, 2),
+        ];
+        let sorted: Vec<_> = intervals.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect();
+        assert_eq!(sorted, intervals);
+
+        let mut reversed = intervals;
+        reversed.reverse();
+        reversed.sort();
+        assert_eq!(reversed, intervals);
+
+        let set: HashSet<_> = intervals.iter().chain(intervals.iter()).cloned().collect();
+        assert_eq!(set.len(), intervals.len());
+
+        assert_eq!(Interval::incl(1.0, f64::NAN).partial_cmp(&Interval::incl(1.0, 2.0)), None);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let a = Interval::excl(1, 5);
+        assert!(!a.contains_point(&0));
+        assert!(a.contains_point(&1));
+        assert!(a.contains_point(&4));
+        assert!(!a.contains_point(&5));
+
+        let b = Interval::new(None, Some(5), true).unwrap();
+        assert!(b.contains_point(&-100));
+        assert!(b.contains_point(&5));
+        assert!(!b.contains_point(&6));
+
+        let c = Interval::from_bounds(Bound::Excluded(1.0), Bound::Unbounded);
+        assert!(!c.contains_point(&1.0));
+        assert!(c.contains_point(&1e30));
+        assert!(!c.contains_point(&f64::NAN));
+    }
+}
@@ -0,0 +1,236 @@
+//! Joining two sorted streams of intervals on overlap.
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::iter::Peekable;
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+use crate::bounds;
+use crate::RangeOverlap;
+
+/// An iterator over the overlapping pairs from two sorted streams of intervals, created by [`overlap_join`].
+#[derive(Debug, Clone)]
+pub struct OverlapJoin<T, A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    /// Intervals already read from each stream that may still overlap ones to come from the other
+    active_a: Vec<A::Item>,
+    active_b: Vec<B::Item>,
+    pending: VecDeque<(A::Item, B::Item, RangeOverlap)>,
+    value_type: PhantomData<fn() -> T>,
+}
+
+impl<T, A, B> Iterator for OverlapJoin<T, A, B>
+where
+    T: PartialOrd,
+    A: Iterator,
+    B: Iterator,
+    A::Item: RangeBounds<T> + Clone,
+    B::Item: RangeBounds<T> + Clone,
+{
+    type Item = (A::Item, B::Item, RangeOverlap);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(pair) = self.pending.pop_front() {
+                return Some(pair);
+            }
+
+            let take_a = match (self.a.peek(), self.b.peek()) {
+                (Some(x), Some(y)) => bounds::cmp_starts(x.start_bound(), y.start_bound()) != Some(Ordering::Greater),
+                // Once one stream runs out, the other only needs reading while there are intervals left to pair with
+                (Some(_), None) if !self.active_b.is_empty() => true,
+                (None, Some(_)) if !self.active_a.is_empty() => false,
+                _ => return None,
+            };
+
+            // Everything still to come starts no earlier than the interval read here, so any interval from
+            // the other stream that ends before it starts can never overlap anything again
+            if take_a {
+                let x = self.a.next().unwrap();
+                if bounds::is_empty_ref(x.start_bound(), x.end_bound()) {
+                    continue;
+                }
+                self.active_b.retain(|y| bounds::ends_before(y.end_bound(), x.start_bound()) == Some(false));
+                for y in &self.active_b {
+                    let relation = bounds::classify_bound_refs(x.start_bound(), x.end_bound(), y.start_bound(), y.end_bound());
+                    self.pending.push_back((x.clone(), y.clone(), relation));
+                }
+                self.active_a.push(x);
+            } else {
+                let y = self.b.next().unwrap();
+                if bounds::is_empty_ref(y.start_bound(), y.end_bound()) {
+                    continue;
+                }
+                self.active_a.retain(|x| bounds::ends_before(x.end_bound(), y.start_bound()) == Some(false));
+                for x in &self.active_a {
+                    let relation = bounds::classify_bound_refs(x.start_bound(), x.end_bound(), y.start_bound(), y.end_bound());
+                    self.pending.push_back((x.clone(), y.clone(), relation));
+                }
+                self.active_b.push(y);
+            }
+        }
+    }
+}
+
+/// Join two streams of intervals on overlap: yield every pair of an interval from `a` and one from `b`
+/// that overlap, with how the first overlaps the second. Both streams must be sorted by start, as
+/// [`Interval`](crate::Interval)'s ordering does; if they are not, some overlapping pairs will be missed.
+///
+/// This reads each stream once, in order, keeping only the intervals that could still overlap ones yet
+/// to be read, so it works on streams far too large to hold in memory or compare pairwise. The items can
+/// be anything with bounds, such as [`LabeledInterval`](crate::LabeledInterval)s carrying the record each
+/// interval came from. Pairs are yielded in the order of whichever of the two intervals starts later.
+///
+/// The intervals that could still overlap, and the pairs found but not yet yielded, are kept in buffers
+/// that grow on the heap as needed, so the join allocates in proportion to the largest number of intervals
+/// active at once, not to the length of the streams. The buffers are reused once they have grown.
+///
+/// ```
+/// # use range_overlap::{overlap_join, Interval, LabeledInterval, RangeOverlap};
+/// let genes = [LabeledInterval::new(Interval::excl(100, 500), "geneA"), LabeledInterval::new(Interval::excl(800, 900), "geneB")];
+/// let reads = [Interval::excl(50, 150), Interval::excl(450, 850), Interval::excl(950, 1000)];
+/// let hits: Vec<_> = overlap_join(genes, reads).map(|(gene, read, relation)| (*gene.label(), read, relation)).collect();
+/// assert_eq!(hits, [
+///     ("geneA", Interval::excl(50, 150), RangeOverlap::AStartsInB),
+///     ("geneA", Interval::excl(450, 850), RangeOverlap::AEndsInB),
+///     ("geneB", Interval::excl(450, 850), RangeOverlap::AStartsInB),
+/// ]);
+/// ```
+pub fn overlap_join<T, A, B>(a: A, b: B) -> OverlapJoin<T, A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    B: IntoIterator,
+{
+    OverlapJoin {
+        a: a.into_iter().peekable(),
+        b: b.into_iter().peekable(),
+        active_a: Vec::new(),
+        active_b: Vec::new(),
+        pending: VecDeque::new(),
+        value_type: PhantomData,
+    }
+}
+
+/// An iterator over the overlapping pairs within one sorted stream of intervals, created by
+/// [`OverlappingPairsExt::overlapping_pairs`].
+#[derive(Debug, Clone)]
+pub struct OverlappingPairs<T, I: Iterator> {
+    iter: I,
+    next_index: usize,
+    /// Intervals already read that may still overlap ones to come, with their indices
+    active: Vec<(usize, I::Item)>,
+    pending: VecDeque<(usize, usize, RangeOverlap)>,
+    value_type: PhantomData<fn() -> T>,
+}
+
+impl<T, I> Iterator for OverlappingPairs<T, I>
+where
+    T: PartialOrd,
+    I: Iterator,
+    I::Item: RangeBounds<T>,
+{
+    type Item = (usize, usize, RangeOverlap);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(pair) = self.pending.pop_front() {
+                return Some(pair);
+            }
+
+            let y = self.iter.next()?;
+            let j = self.next_index;
+            self.next_index += 1;
+            if bounds::is_empty_ref(y.start_bound(), y.end_bound()) {
+                continue;
+            }
+            self.active.retain(|(_, x)| bounds::ends_before(x.end_bound(), y.start_bound()) == Some(false));
+            for (i, x) in &self.active {
+                let relation = bounds::classify_bound_refs(x.start_bound(), x.end_bound(), y.start_bound(), y.end_bound());
+                self.pending.push_back((*i, j, relation));
+            }
+            self.active.push((j, y));
+        }
+    }
+}
+
+/// Adds [`overlapping_pairs`](OverlappingPairsExt::overlapping_pairs) to any iterable collection of intervals.
+pub trait OverlappingPairsExt<T>: IntoIterator + Sized
+where
+    Self::Item: RangeBounds<T>,
+{
+    /// Lazily find every pair of intervals in this collection that overlap, yielding `(i, j, relation)`
+    /// where `i < j` are the positions of the two intervals and `relation` is how interval `i` overlaps
+    /// interval `j`. The intervals must be sorted by start; if they are not, some overlapping pairs will be
+    /// missed. Like [`overlap_join`], this reads the intervals once, keeping only those that could still
+    /// overlap ones to come, and allocates in proportion to the largest number of those at once.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, OverlappingPairsExt, RangeOverlap};
+    /// let events = vec![Interval::excl(0, 10), Interval::excl(2, 4), Interval::excl(8, 12), Interval::excl(20, 30)];
+    /// let pairs: Vec<_> = events.iter().overlapping_pairs().collect();
+    /// assert_eq!(pairs, [(0, 1, RangeOverlap::AContainsB), (0, 2, RangeOverlap::AEndsInB)]);
+    /// ```
+    fn overlapping_pairs(self) -> OverlappingPairs<T, Self::IntoIter> {
+        OverlappingPairs { iter: self.into_iter(), next_index: 0, active: Vec::new(), pending: VecDeque::new(), value_type: PhantomData }
+    }
+}
+
+impl<T, I: IntoIterator> OverlappingPairsExt<T> for I where I::Item: RangeBounds<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval};
+    use crate::{Interval, LabeledInterval};
+
+    fn sorted_stream(seed: u32, n: usize) -> Vec<LabeledInterval<i32, usize>> {
+        let mut rng = Lcg::new(seed);
+        let mut intervals: Vec<Interval<i32>> = (0..n).map(|_| random_interval(&mut rng, 60, 12)).collect();
+        intervals.sort();
+        intervals.into_iter().enumerate().map(|(i, iv)| LabeledInterval::new(iv, i)).collect()
+    }
+
+    #[test]
+    fn test_matches_pairwise() {
+        for seed in 0..300 {
+            let a = sorted_stream(seed, (seed % 20) as usize);
+            let b = sorted_stream(seed + 5000, (seed % 13) as usize);
+            let mut found: Vec<(usize, usize, RangeOverlap)> = overlap_join(a.iter().copied(), b.iter().copied())
+                .map(|(x, y, relation)| (*x.label(), *y.label(), relation))
+                .collect();
+            found.sort_by_key(|&(i, j, _)| (i, j));
+
+            let mut expected = Vec::new();
+            for x in &a {
+                for y in &b {
+                    let relation = x.classify_against(y);
+                    if relation.has_overlap() {
+                        expected.push((*x.label(), *y.label(), relation));
+                    }
+                }
+            }
+            assert_eq!(found, expected, "{a:?} joined with {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_overlapping_pairs() {
+        for seed in 0..300 {
+            let stream = sorted_stream(seed, (seed % 25) as usize);
+            let mut found: Vec<(usize, usize, RangeOverlap)> = stream.iter().overlapping_pairs().collect();
+            found.sort_by_key(|&(i, j, _)| (i, j));
+
+            let mut expected = Vec::new();
+            for (i, x) in stream.iter().enumerate() {
+                for (j, y) in stream.iter().enumerate().skip(i + 1) {
+                    let relation = x.classify_against(y);
+                    if relation.has_overlap() {
+                        expected.push((i, j, relation));
+                    }
+                }
+            }
+            assert_eq!(found, expected, "{stream:?}");
+        }
+    }
+}
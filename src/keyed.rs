@@ -0,0 +1,140 @@
+//! Comparing intervals through a key extraction or comparison function, for end point
+//! types that are not themselves `PartialOrd` (or whose ordering is not the one you want).
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::{Interval, RangeOverlap};
+
+/// Wraps a reference to a value so that comparisons between wrapped values go through `cmp`.
+struct ByCmp<'a, T, F> {
+    value: &'a T,
+    cmp: &'a F,
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> PartialEq for ByCmp<'_, T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.cmp)(self.value, other.value) == Ordering::Equal
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> PartialOrd for ByCmp<'_, T, F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some((self.cmp)(self.value, other.value))
+    }
+}
+
+fn wrap<'a, T, F>(bound: Bound<&'a T>, cmp: &'a F) -> Bound<ByCmp<'a, T, F>> {
+    bound.map(|value| ByCmp { value, cmp })
+}
+
+impl<T> Interval<T> {
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`), comparing the keys that `key`
+    /// extracts from each end point rather than the end points themselves.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeOverlap};
+    /// struct Record { id: u32, time: u64 }
+    ///
+    /// let a = Interval::excl(Record { id: 1, time: 10 }, Record { id: 2, time: 20 });
+    /// let b = Interval::excl(Record { id: 3, time: 15 }, Record { id: 4, time: 25 });
+    /// assert_eq!(a.classify_against_by_key(&b, |r| r.time), RangeOverlap::AEndsInB);
+    /// ```
+    pub fn classify_against_by_key<K: PartialOrd, F: Fn(&T) -> K>(&self, other: &Interval<T>, key: F) -> RangeOverlap {
+        let keys = [self.start(), self.end(), other.start(), other.end()].map(|b| b.map(&key));
+        let [a_start, a_end, b_start, b_end] = keys;
+        bounds::classify_bound_refs(a_start.as_ref(), a_end.as_ref(), b_start.as_ref(), b_end.as_ref())
+    }
+
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`), comparing end points with `cmp`.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeOverlap};
+    /// // Compare strings case-insensitively
+    /// let a = Interval::incl("apple", "Mango");
+    /// let b = Interval::incl("banana", "zucchini");
+    /// let overlap = a.classify_against_by(&b, |x, y| x.to_lowercase().cmp(&y.to_lowercase()));
+    /// assert_eq!(overlap, RangeOverlap::AEndsInB);
+    /// ```
+    pub fn classify_against_by<F: Fn(&T, &T) -> Ordering>(&self, other: &Interval<T>, cmp: F) -> RangeOverlap {
+        let [a_start, a_end, b_start, b_end] = [self.start(), self.end(), other.start(), other.end()].map(|b| wrap(b, &cmp));
+        bounds::classify_bound_refs(a_start.as_ref(), a_end.as_ref(), b_start.as_ref(), b_end.as_ref())
+    }
+
+    /// Returns `true` if this interval and `other` share any points, comparing the keys that `key`
+    /// extracts from each end point.
+    pub fn has_overlap_by_key<K: PartialOrd, F: Fn(&T) -> K>(&self, other: &Interval<T>, key: F) -> bool {
+        self.classify_against_by_key(other, key).has_overlap()
+    }
+
+    /// Returns `true` if this interval and `other` share any points, comparing end points with `cmp`.
+    pub fn has_overlap_by<F: Fn(&T, &T) -> Ordering>(&self, other: &Interval<T>, cmp: F) -> bool {
+        self.classify_against_by(other, cmp).has_overlap()
+    }
+
+    /// Returns `true` if `point` lies within this interval, comparing the keys that `key` extracts
+    /// from `point` and the end points.
+    pub fn contains_point_by_key<K: PartialOrd, F: Fn(&T) -> K>(&self, point: &T, key: F) -> bool {
+        let start = self.start().map(&key);
+        let end = self.end().map(&key);
+        bounds::contains_point_ref(start.as_ref(), end.as_ref(), &key(point))
+    }
+
+    /// Returns `true` if `point` lies within this interval, comparing it to the end points with `cmp`.
+    pub fn contains_point_by<F: Fn(&T, &T) -> Ordering>(&self, point: &T, cmp: F) -> bool {
+        let start = wrap(self.start(), &cmp);
+        let end = wrap(self.end(), &cmp);
+        bounds::contains_point_ref(start.as_ref(), end.as_ref(), &ByCmp { value: point, cmp: &cmp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Booking {
+        room: &'static str,
+        day: u32,
+    }
+
+    fn booking(room: &'static str, day: u32) -> Booking {
+        Booking { room, day }
+    }
+
+    #[test]
+    fn test_by_key() {
+        let a = Interval::excl(booking("A", 1), booking("A", 5));
+        let b = Interval::excl(booking("B", 5), booking("B", 9));
+        let c = Interval::incl(booking("C", 5), booking("C", 9));
+        assert_eq!(a.classify_against_by_key(&b, |b| b.day), RangeOverlap::None);
+        assert!(!a.has_overlap_by_key(&b, |b| b.day));
+        assert_eq!(b.classify_against_by_key(&c, |b| b.day), RangeOverlap::AInsideB);
+
+        assert!(a.contains_point_by_key(&booking("Z", 1), |b| b.day));
+        assert!(!a.contains_point_by_key(&booking("Z", 5), |b| b.day));
+        assert!(c.contains_point_by_key(&booking("Z", 9), |b| b.day));
+
+        // The key can be something other than the field, e.g. ordering by room name
+        assert_eq!(a.classify_against_by_key(&c, |b| b.room), RangeOverlap::None);
+    }
+
+    #[test]
+    fn test_by_cmp() {
+        let by_day = |x: &Booking, y: &Booking| x.day.cmp(&y.day);
+        let a = Interval::excl(booking("A", 1), booking("A", 5));
+        let b = Interval::incl(booking("B", 5), booking("B", 9));
+        let c = Interval::from_bounds(Bound::Unbounded, Bound::Included(booking("C", 5)));
+        assert_eq!(a.classify_against_by(&b, by_day), RangeOverlap::None);
+        assert_eq!(c.classify_against_by(&b, by_day), RangeOverlap::AEndsInB);
+        assert!(c.has_overlap_by(&a, by_day));
+        assert!(c.contains_point_by(&booking("Z", 0), by_day));
+        assert!(!c.contains_point_by(&booking("Z", 6), by_day));
+
+        // Reversing the comparison reverses which values are "before" others
+        let reversed = |x: &i32, y: &i32| y.cmp(x);
+        let d = Interval::incl(10, 1);
+        assert!(d.contains_point_by(&5, reversed));
+        assert!(!d.contains_point_by(&11, reversed));
+    }
+}
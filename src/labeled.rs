@@ -0,0 +1,132 @@
+//! Intervals that carry an arbitrary payload alongside their end points.
+use std::ops::{Bound, RangeBounds};
+
+use crate::{Interval, RangeOverlap};
+
+/// An [`Interval`] paired with a label, such as the ID of the record the interval came from.
+///
+/// All comparisons only look at the interval; the label is ignored. This means that two labeled
+/// intervals with different labels (even different label types) can still be
+/// [`AEqualsB`](RangeOverlap::AEqualsB).
+///
+/// ```
+/// # use range_overlap::{Interval, LabeledInterval, RangeOverlap};
+/// let meeting = LabeledInterval::new(Interval::excl(9, 11), "standup");
+/// let lunch = LabeledInterval::new(Interval::excl(11, 13), 42);
+/// assert_eq!(meeting.classify_against(&lunch), RangeOverlap::None);
+/// assert_eq!(meeting.label(), &"standup");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LabeledInterval<T, M> {
+    interval: Interval<T>,
+    label: M,
+}
+
+impl<T, M> LabeledInterval<T, M> {
+    /// Attach `label` to `interval`.
+    pub fn new(interval: Interval<T>, label: M) -> Self {
+        Self { interval, label }
+    }
+
+    /// The interval, without its label.
+    pub fn interval(&self) -> &Interval<T> {
+        &self.interval
+    }
+
+    /// The label attached to the interval.
+    pub fn label(&self) -> &M {
+        &self.label
+    }
+
+    /// A mutable reference to the label attached to the interval.
+    pub fn label_mut(&mut self) -> &mut M {
+        &mut self.label
+    }
+
+    /// Consume this, returning the interval and label separately.
+    pub fn into_parts(self) -> (Interval<T>, M) {
+        (self.interval, self.label)
+    }
+
+    /// Replace the label, keeping the same interval.
+    pub fn map_label<N, F: FnOnce(M) -> N>(self, f: F) -> LabeledInterval<T, N> {
+        LabeledInterval { interval: self.interval, label: f(self.label) }
+    }
+}
+
+impl<T: PartialOrd, M> LabeledInterval<T, M> {
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`), ignoring both labels.
+    pub fn classify_against<N>(&self, other: &LabeledInterval<T, N>) -> RangeOverlap {
+        self.interval.classify_against(&other.interval)
+    }
+
+    /// Returns `true` if this interval and `other` share any points, ignoring both labels.
+    pub fn has_overlap<N>(&self, other: &LabeledInterval<T, N>) -> bool {
+        self.interval.has_overlap(&other.interval)
+    }
+
+    /// Returns `true` if `point` lies within this interval.
+    pub fn contains_point(&self, point: &T) -> bool {
+        self.interval.contains_point(point)
+    }
+
+    /// Returns `true` if no value can lie in this interval.
+    pub fn is_empty(&self) -> bool {
+        self.interval.is_empty()
+    }
+}
+
+impl<T, M> From<(Interval<T>, M)> for LabeledInterval<T, M> {
+    fn from(value: (Interval<T>, M)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl<T, M> RangeBounds<T> for LabeledInterval<T, M> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.interval.start()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.interval.end()
+    }
+}
+
+impl<T, M> RangeBounds<T> for &LabeledInterval<T, M> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.interval.start()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.interval.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_ignored() {
+        let a = LabeledInterval::new(Interval::incl(1, 5), "a");
+        let b = LabeledInterval::new(Interval::incl(1, 5), vec![1, 2, 3]);
+        let c = LabeledInterval::new(Interval::incl(5, 10), "c");
+        assert_eq!(a.classify_against(&b), RangeOverlap::AEqualsB);
+        assert_eq!(a.classify_against(&c), RangeOverlap::AEndsInB);
+        assert!(c.has_overlap(&b));
+        assert!(a.contains_point(&5));
+        assert!(!a.is_empty());
+    }
+
+    #[test]
+    fn test_parts() {
+        let mut a = LabeledInterval::from((Interval::excl(1, 5), 1));
+        *a.label_mut() += 1;
+        assert_eq!(a.label(), &2);
+        assert_eq!(a.interval(), &Interval::excl(1, 5));
+
+        let a = a.map_label(|l| l.to_string());
+        assert_eq!(a.into_parts(), (Interval::excl(1, 5), "2".to_string()));
+    }
+}
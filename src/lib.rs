@@ -134,7 +134,8 @@
 //! ```
 //! 
 //! Again, since `None` is taken to mean the same thing for both A and B, all points in one
-//! must be in the other, so these two are equal ranges.
+//! must be in the other, so these two are equal ranges. If you would rather spell out which
+//! infinity an open end represents, use [`Endpoint`] and [`classify_endpoints`] instead.
 //! 
 //! If you only need to determine if two ranges overlap, but not how, you can either use the 
 //! [`RangeOverlap::has_overlap`] method, or call one of the convenience methods:
@@ -144,13 +145,265 @@
 //! - [`has_open_excl_overlap`]
 //! - [`has_open_incl_overlap`]
 //! 
+//! To check a classification against several acceptable variants at once, build a [`RangeOverlapMask`] and
+//! use [`RangeOverlap::matches`].
+//! 
+//! To check whether a single value is in a range rather than classifying two ranges, use [`range_contains_point`],
+//! or [`open_range_contains_point`] for ranges that may have open sides.
+//! 
+//! A range can also be *empty*, meaning that no value is in it, for example `[5, 5)`. The original
+//! functions above do not check for this, and may classify an empty range as overlapping another.
+//! [`excl_classify_nonempty`], [`incl_classify_nonempty`], and [`classify_any_nonempty`] instead always
+//! return [`RangeOverlap::None`] if either range is empty; [`is_empty_range`] checks a single range.
+//! 
+//! If your end points are floats, you may find it easier to represent open sides with infinities than with
+//! `None`; [`classify_sentinel`] does this, and can be extended to other types through [`OpenSentinel`].
+//! For end points that come from measurements, [`classify_with_tolerance`] treats nearly equal end points as equal.
+//! 
+//! Ranges that may have been given in descending order (e.g. from a slider that can be dragged either way)
+//! can be classified with [`classify_unordered`], which also reports which ranges were reversed.
+//! 
+//! If you need to control whether each end of each range is included separately (e.g. to compare
+//! `[1, 5)` against `(2, 8]`), use [`classify_bounds`], which takes [`std::ops::Bound`]s. [`classify`] goes
+//! one step further and accepts the std range types directly, e.g. `classify(1..5, ..=10)`, and
+//! [`classify_range_bounds`] does the same with borrowed ranges. The [`Overlaps`] trait lets you write the
+//! same as methods, e.g. `(1..5).classify(&(..=10))` or `(1..5).overlaps(&(3..=8))`.
+//! 
+//! If you would rather pass ranges around as single values, the [`Interval`] type stores both
+//! ends of a range, along with whether each end is included, excluded, or open:
+//! 
+//! ```
+//! # use range_overlap::{Interval, RangeOverlap};
+//! let a = Interval::incl(1, 5);
+//! let b = Interval::excl(5, 10);
+//! assert_eq!(a.classify_against(&b), RangeOverlap::AEndsInB);
+//! ```
+//! 
+//! If you need to know more than how two ranges overlap, [`classify_detailed`] and [`Interval::classify_detailed`]
+//! also return the overlapping region and which end points coincide ([`OverlapDetails`]).
+//! 
+//! Once you know two ranges overlap, [`intersection`] and [`Interval::intersect`] give the range of values they share.
+//! [`Interval::union`] gives the values in either range, [`Interval::difference`] the values in one range
+//! but not the other, and [`Interval::symmetric_difference`] the values in exactly one of them. [`Interval::hull`]
+//! gives the smallest range containing both, and [`Interval::gap`] the range between two that do not overlap.
+//! [`Interval::split_at`] and [`Interval::split_by`] cut an interval into pieces at a point or around another interval.
+//! [`Interval::subtract`] gives the same values as [`Interval::difference`], but as an [`IntervalSet`].
+//! [`Interval::clamp_to`] trims an interval to lie within another. For coalescing loops, [`merge_if_overlapping`] and [`merge_if_adjacent`]
+//! (and the [`Interval`] methods of the same names) combine two ranges only if they overlap or touch.
+//! If you have already classified two intervals, [`RangeOverlap::merge`] and [`RangeOverlap::intersect`] combine
+//! them without comparing their end points again.
+//! 
+//! To work with a collection of ranges as one set of values, use [`IntervalSet`], which keeps its intervals
+//! sorted and merges any that overlap or touch. Sets can be combined with [`IntervalSet::union`],
+//! [`IntervalSet::intersection`], [`IntervalSet::difference`], and [`IntervalSet::symmetric_difference`], or
+//! the `|`, `&`, `-`, and `^` operators, and [`IntervalSet::complement`] gives the gaps in a set.
+//! With the `smallvec` feature, sets of up to four intervals are stored inline, without allocating.
+//! [`CompactIntervalSet`] stores a set as just the values where it starts and stops, for sets of many intervals,
+//! [`DenseIntervalSet`] stores a set of small integers as one bit per value, and [`PersistentIntervalSet`] is an
+//! immutable set whose updates return new versions that share most of their structure with the old ones.
+//! [`IntervalMap`] maps non-overlapping intervals to values, with an [`InsertPolicy`] for what to do when
+//! a new interval overlaps existing ones, while [`SegmentMap`] combines the values where intervals overlap.
+//! To find which of many possibly overlapping intervals contain a point or overlap a query, store them
+//! in an [`IntervalTree`], or in an [`IntervalIndex`] if they are all known up front. [`ArenaIntervalTree`]
+//! keeps its nodes in one vector, so rebuilding it for each batch of intervals reuses the same memory. If you
+//! already keep a sorted `Vec` of disjoint intervals, [`query_sorted`] finds the ones overlapping a target by
+//! binary search.
+//! 
+//! Functions for whole collections of intervals work by sweeping over them in order.
+//! [`cluster_overlapping`] groups intervals into clusters connected by overlap, and [`merge_intervals`]
+//! coalesces them into the fewest disjoint intervals covering the same values. [`gaps`] finds the values
+//! in a window that none of them cover, and [`covered_length`] the total length they do cover. Where memory
+//! must not be allocated, [`merge_intervals_in_place`] merges within a vector you provide, and for intervals
+//! already sorted by start, [`merge_sorted`] and [`gaps_sorted`] do their work lazily as the intervals arrive.
+//! [`is_fully_covered`] checks that a target has no gaps, and [`minimal_cover`] picks the fewest intervals
+//! that leave none. [`max_overlap_depth`] finds the most intervals that overlap at once, and where, and
+//! [`depth_profile`] how many overlap over each stretch. [`assign_tracks`] lays intervals out on as few
+//! non-overlapping tracks as possible, and [`max_disjoint_subset`] picks as many intervals as possible with
+//! none overlapping. [`classify_matrix`] classifies every interval in one list against every interval in
+//! another, [`find_conflicts`] finds the overlapping pairs within one list, and [`overlap_join`] finds the
+//! overlapping pairs from two sorted streams of intervals in one pass, or within one stream with
+//! [`OverlappingPairsExt::overlapping_pairs`]. For streams too long to keep, [`OnlineSweep`] reports the
+//! overlaps of each interval as it arrives and forgets intervals once nothing more can overlap them. To write
+//! other sweeps, [`interval_events`] lists where intervals start and end, in order. With the `rayon` feature,
+//! `par_classify_matrix` and `par_find_conflicts` spread the work of the all-pairs searches across threads.
+//! 
+//! Intervals with numeric end points can be shifted by adding or subtracting a value, and stretched with
+//! [`Interval::scale_about`]. [`Interval::expanded`] and [`Interval::shrunk`] add or remove a margin at both ends.
+//! [`Interval::snap_to_grid`] aligns end points to regular steps, for types implementing [`GridSnap`], and
+//! [`bucketize`] sorts intervals into the fixed-size windows they overlap.
+//! [`Interval::length`] and [`Interval::midpoint`] give the size and center of a closed interval.
+//! [`overlap_length`] and [`Interval::overlap_length`] measure how much two ranges overlap, and
+//! [`fraction_of_a_in_b`], [`fraction_of_b_in_a`] and [`jaccard_index`] turn that into a score for how
+//! similar they are. [`coverage_of_a_by_b`] and [`coverage_of_b_by_a`] do the same as the `fraction_*` functions
+//! for ranges with open sides. [`distance`] and [`Interval::distance`] give how far apart two ranges are.
+//! For spans of [`std::time::Instant`]s or [`std::time::SystemTime`]s given as `(start, end)` tuples,
+//! [`span_overlap`] gives how long they overlap as a [`std::time::Duration`], and [`has_span_overlap`]
+//! whether they overlap at all. With the `chrono` feature, intervals of `chrono` dates and times gain `overlap_duration`, which gives
+//! a `chrono::Duration`, and constructors such as `Interval::from_dates` that turn a pair of dates into the
+//! times on those days; `TimeRange` is shorthand for an interval of `DateTime`s.
+//! The `time` feature does the same for the `time` crate's `Date`, `PrimitiveDateTime` and `OffsetDateTime`, with
+//! `Interval::from_date_range` and `Interval::from_date_range_at` as the constructors, and adds `Interval::iso_week`
+//! and `Interval::month` for the days of an ISO week or a calendar month.
+//! 
+//! When [`RangeOverlap`] is too coarse, [`Interval::allen_relation`] gives one of the 13 relations
+//! of Allen's interval algebra ([`AllenRelation`]), which, for example, distinguishes ranges that
+//! only touch from ranges that have a gap between them. When two ranges do not overlap, [`disjoint_cmp`] and
+//! [`Interval::disjoint_cmp`] tell you which one comes first, as do [`range_side`] and [`Interval::side_of`].
+//! 
+//! If an end point may be a NaN (or any other value that [`PartialOrd`] cannot order), the classification
+//! functions will still return a variant, but it will not be meaningful. The `try_*` versions (e.g.
+//! [`try_classify_any`]) check for this and return [`RangeError::IncomparableEndpoints`] instead, while
+//! [`classify_any_with_nan_policy`] lets you choose whether a NaN means an error or an open side ([`NanPolicy`]).
+//! For `f32` and `f64`, the `*_total` functions (e.g. [`classify_any_total`]) instead order NaNs and signed zeros
+//! with `total_cmp`.
+//! 
+//! For integers and other discrete types, where `[1, 5]` and `[1, 6)` hold the same values, use
+//! [`classify_discrete`] or [`Interval::classify_discrete`], which are available for any type that
+//! implements [`Discrete`]. [`excl_classify_i64`], [`incl_classify_i64`] and their `u64` versions are
+//! `const fn`s, for working out tables of overlaps at compile time. [`excl_classify_branchless`] and
+//! [`incl_classify_branchless`] give the same answers as the plain functions for integers without branching,
+//! which is faster in tight loops where the kind of overlap is hard to predict.
+//! 
 //! Finally, note that all of these method are defined for any type that implements [`PartialOrd`].
 //! This means you can use them for integers, floats, `chrono` times, and many other types. This includes
 //! types such as [`std::string::String`], which may not produce intuitive behavior unless you are very
-//! clear on how they are ordered.
+//! clear on how they are ordered. The classification and `has_*_overlap` functions also have versions ending
+//! in `_ref` (e.g. [`classify_any_ref`]) that take references to the end points, so that types which are expensive to
+//! clone can be classified without giving up ownership. For types that implement [`Ord`], [`classify_ord`]
+//! compares each pair of end points only once, which is faster when comparisons are expensive, as for long
+//! strings with a shared prefix. To classify many pairs of ranges stored as columns of end
+//! points, use [`classify_slices`], or [`classify_pairs_into`] for arrays of [`RangePair`] structs laid out as in C.
+//! With the `ffi` feature, C-callable functions such as `range_overlap_classify_f64` classify such arrays
+//! passed from other languages. With the `batch-overlap` feature, `has_excl_overlap_batch` and
+//! `has_incl_overlap_batch` check columns of primitive numbers for overlap without branching on the end points.
+//!
+//! With the `schemars` feature, [`RangeOverlap`], [`Interval`], [`IntervalSet`] and the other public data types
+//! implement `schemars::JsonSchema`, so services that return overlap results or take interval settings can
+//! describe them in OpenAPI. The schemas follow the fields as declared; this crate does not implement `serde`'s traits.
+
+// The list items in the introduction above wrap without indenting their continuation lines
+#![allow(clippy::doc_lazy_continuation)]
+
+mod allen;
+mod arena;
+mod batch;
+#[cfg(feature = "batch-overlap")]
+mod batch_overlap;
+mod branchless;
+mod bounds;
+mod builder;
+mod checked;
+mod compact;
+mod const_int;
+#[cfg(feature = "chrono")]
+mod datetime;
+mod dense;
+mod detailed;
+mod discrete;
+mod endpoint;
+mod error;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod float;
+mod grid;
+mod index;
+mod interval;
+mod join;
+mod keyed;
+mod labeled;
+mod map;
+mod mask;
+mod measure;
+mod nan;
+mod online;
+mod ord;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod persistent;
+mod refs;
+mod segment;
+mod set;
+mod setops;
+mod sorted;
+mod streaming;
+mod sweep;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "time")]
+mod time_rs;
+mod timespan;
+mod transform;
+mod tree;
+mod unordered;
+pub mod typed;
+
+pub use allen::{are_adjacent, disjoint_cmp, range_side, AllenRelation, Side};
+pub use arena::ArenaIntervalTree;
+pub use batch::{classify_pairs_into, classify_slices, RangePair};
+#[cfg(feature = "batch-overlap")]
+pub use batch_overlap::{has_excl_overlap_batch, has_incl_overlap_batch};
+pub use branchless::{excl_classify_branchless, incl_classify_branchless};
+pub use bounds::{classify, classify_bounds, classify_range_bounds, classify_with_inclusivity, Inclusivity, Overlaps};
+pub use builder::IntervalBuilder;
+pub use checked::{classify_strict, try_classify_any, try_classify_bounds, try_excl_classify, try_incl_classify};
+pub use compact::CompactIntervalSet;
+pub use const_int::{excl_classify_i64, excl_classify_u64, incl_classify_i64, incl_classify_u64};
+#[cfg(feature = "chrono")]
+pub use datetime::TimeRange;
+pub use dense::DenseIntervalSet;
+pub use detailed::{classify_detailed, OverlapDetails};
+pub use discrete::{classify_discrete, Discrete};
+pub use endpoint::{classify_endpoints, Endpoint};
+pub use error::{CoverageGapError, OverlapError, RangeConversionError, RangeError, WhichRange};
+#[cfg(feature = "ffi")]
+pub use ffi::{
+    range_overlap_classify_f32, range_overlap_classify_f64, range_overlap_classify_i32, range_overlap_classify_i64,
+    range_overlap_classify_u64,
+};
+pub use float::{
+    classify_any_total, classify_sentinel, classify_with_tolerance, excl_classify_total, has_sentinel_overlap,
+    incl_classify_total, OpenSentinel, TotalCmp,
+};
+pub use grid::{bucketize, Bucket, GridSnap, SnapMode};
+pub use index::IntervalIndex;
+pub use interval::{Interval, Normalized};
+pub use join::{overlap_join, OverlapJoin, OverlappingPairs, OverlappingPairsExt};
+pub use labeled::LabeledInterval;
+pub use map::{InsertPolicy, IntervalMap};
+pub use mask::RangeOverlapMask;
+pub use measure::{coverage_of_a_by_b, coverage_of_b_by_a, distance, fraction_of_a_in_b, fraction_of_b_in_a, jaccard_index, overlap_length};
+pub use nan::{classify_any_with_nan_policy, NanPolicy};
+pub use online::OnlineSweep;
+pub use ord::classify_ord;
+#[cfg(feature = "rayon")]
+pub use parallel::{par_classify_matrix, par_find_conflicts};
+pub use persistent::PersistentIntervalSet;
+pub use refs::{
+    classify_any_nonempty_ref, classify_any_ref, excl_classify_nonempty_ref, excl_classify_ref, has_excl_overlap_ref,
+    has_incl_overlap_ref, has_open_excl_overlap_ref, has_open_incl_overlap_ref, incl_classify_nonempty_ref,
+    incl_classify_ref, open_range_contains_point_ref, range_contains_point_ref,
+};
+pub use segment::SegmentMap;
+pub use set::IntervalSet;
+pub use setops::{intersection, merge_if_adjacent, merge_if_overlapping, IntervalPieces, IntervalUnion};
+pub use sorted::query_sorted;
+pub use streaming::{gaps_sorted, merge_sorted, MergeSorted, SortedGaps};
+pub use sweep::{
+    assign_tracks, classify_matrix, cluster_overlapping, covered_length, depth_profile, find_conflicts,
+    first_uncovered, gaps, interval_events, is_fully_covered, max_disjoint_subset, max_overlap_depth,
+    merge_intervals, merge_intervals_in_place, minimal_cover, DepthProfile, IntervalEvent, MergePolicy,
+};
+pub use timespan::{has_span_overlap, span_overlap, TimePoint};
+pub use tree::IntervalTree;
+pub use unordered::{classify_unordered, UnorderedOverlap};
 
 /// An enum describing the kind of overlap between two ranges.
+///
+/// It is stored as a single byte, with the variants numbered from 0 in the order they are listed here
+/// (so `AContainsB` is 0 and `None` is 5), so that arrays of it can be shared with other languages.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[repr(u8)]
 pub enum RangeOverlap {
     /// The second range is fully within the first, meaning that all values from the second are also in the first
     AContainsB,
@@ -177,6 +430,7 @@ pub enum RangeOverlap {
 
 impl RangeOverlap {
     /// Returns `true` if there was any overlap between the ranges
+    #[allow(clippy::match_like_matches_macro)]
     pub fn has_overlap(&self) -> bool {
         if let Self::None = self {
             false
@@ -188,6 +442,7 @@ impl RangeOverlap {
 
 /// Classify the kind of overlap between two fully closed ranges with the ends
 /// considered exclusive.
+#[inline]
 pub fn excl_classify<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
     if a_start == b_start && a_end == b_end {
         RangeOverlap::AEqualsB
@@ -205,7 +460,9 @@ pub fn excl_classify<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T)
 }
 
 /// Classify the kind of overlap between two fully closed ranges with the ends
-/// considered inclusive.
+/// considered inclusive. Either range may be a single point (i.e. have its start
+/// equal its end).
+#[inline]
 pub fn incl_classify<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
     if a_start == b_start && a_end == b_end {
         RangeOverlap::AEqualsB
@@ -215,7 +472,7 @@ pub fn incl_classify<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T)
         RangeOverlap::AEndsInB
     } else if a_start > b_start && a_start <= b_end && a_end > b_end {
         RangeOverlap::AStartsInB
-    } else if a_start >= b_end || b_start >= a_end {
+    } else if a_start > b_end || b_start > a_end {
         RangeOverlap::None
     } else {
         RangeOverlap::AInsideB
@@ -227,6 +484,7 @@ pub fn incl_classify<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T)
 /// indicates that that side of the range is open. The final parameter, `inclusive`,
 /// can be `true` to indicate that `a_end` and `b_end` are part of their ranges, or
 /// `false` if they are not.
+#[inline]
 pub fn classify_any<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> RangeOverlap {
     match (a_start, a_end, b_start, b_end, inclusive) {
         (None, None, None, None, _) => RangeOverlap::AEqualsB,
@@ -367,19 +625,38 @@ pub fn classify_any<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start
 
 /// A convenience function that directly returns `true` if the two closed ranges given
 /// have overlap, with `a_end` and `b_end` not included in the range.
+///
+/// This gives the same answer as `excl_classify(..).has_overlap()` without working out the
+/// kind of overlap: ranges that overlap are settled in two comparisons, and only ranges that
+/// do not need a few more, to catch the empty ranges [`excl_classify`] treats like points.
+#[inline]
 pub fn has_excl_overlap<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> bool {
-    excl_classify(a_start, a_end, b_start, b_end).has_overlap()
+    if a_start < b_end && b_start < a_end {
+        return true;
+    }
+    // Incomparable end points are neither before nor after each other, so are not apart
+    let apart = a_start >= b_end || b_start >= a_end;
+    !apart || (a_start == b_start && a_end == b_end) || (a_start <= b_start && a_end >= b_end)
 }
 
 /// A convenience function that directly returns `true` if the two closed ranges given
 /// have overlap, with `a_end` and `b_end` included in the range.
+///
+/// Like [`has_excl_overlap`], this gives the same answer as `incl_classify(..).has_overlap()`,
+/// settling ranges that overlap in two comparisons.
+#[inline]
 pub fn has_incl_overlap<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> bool {
-    incl_classify(a_start, a_end, b_start, b_end).has_overlap()
+    if a_start <= b_end && b_start <= a_end {
+        return true;
+    }
+    let apart = a_start > b_end || b_start > a_end;
+    !apart || (a_start == b_start && a_end == b_end) || (a_start <= b_start && a_end >= b_end)
 }
 
 /// A convenience function that directly returns `true` if the two ranges given
 /// (which may be closed, half-open, or fully open) have overlap, with `a_end` and `b_end` 
 /// not included in the range.
+#[inline]
 pub fn has_open_excl_overlap<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>) -> bool {
     classify_any(a_start, a_end, b_start, b_end, false).has_overlap()
 }
@@ -387,15 +664,97 @@ pub fn has_open_excl_overlap<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>
 /// A convenience function that directly returns `true` if the two ranges given
 /// (which may be closed, half-open, or fully open) have overlap, with `a_end` and `b_end` 
 /// included in the range.
+#[inline]
 pub fn has_open_incl_overlap<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>) -> bool {
     classify_any(a_start, a_end, b_start, b_end, true).has_overlap()
 }
 
+/// Returns `true` if `point` lies in the closed range from `start` to `end`, with `end` included
+/// if `inclusive` is `true`. The start is always included.
+///
+/// ```
+/// # use range_overlap::range_contains_point;
+/// assert!(range_contains_point(1, 5, 1, false));
+/// assert!(!range_contains_point(1, 5, 5, false));
+/// assert!(range_contains_point(1, 5, 5, true));
+/// ```
+#[inline]
+pub fn range_contains_point<T: PartialOrd>(start: T, end: T, point: T, inclusive: bool) -> bool {
+    let range = if inclusive { Interval::incl(&start, &end) } else { Interval::excl(&start, &end) };
+    Interval::point(&point).has_overlap(&range)
+}
+
+/// Returns `true` if `point` lies in the range from `start` to `end`, where `None` means that side
+/// is open. As with [`range_contains_point`], the start is always included and `end` is included if
+/// `inclusive` is `true`.
+///
+/// ```
+/// # use range_overlap::open_range_contains_point;
+/// assert!(open_range_contains_point(Some(1), None, 100, false));
+/// assert!(!open_range_contains_point(None, Some(5), 5, false));
+/// assert!(open_range_contains_point(None, Some(5), 5, true));
+/// assert!(open_range_contains_point(None, None, -100, false));
+/// ```
+#[inline]
+pub fn open_range_contains_point<T: PartialOrd>(start: Option<T>, end: Option<T>, point: T, inclusive: bool) -> bool {
+    Interval::new_unchecked(start.as_ref(), end.as_ref(), inclusive).contains_point(&&point)
+}
+
+/// Returns `true` if the closed range from `start` to `end` contains no points. With `inclusive = false`,
+/// that is when `start >= end`; with `inclusive = true`, only when `start > end`. End points that cannot
+/// be compared (e.g. NaNs) are also considered empty, since no value can be shown to be between them.
+#[inline]
+pub fn is_empty_range<T: PartialOrd>(start: &T, end: &T, inclusive: bool) -> bool {
+    let end = if inclusive { std::ops::Bound::Included(end) } else { std::ops::Bound::Excluded(end) };
+    bounds::is_empty_ref(std::ops::Bound::Included(start), end)
+}
+
+/// Like [`excl_classify`], but if either range is empty (its start is not before its end) the result
+/// is always [`RangeOverlap::None`], since an empty range shares no points with any other range.
+/// [`excl_classify`] does not check for empty ranges, so e.g. `excl_classify(5, 5, 1, 10)`
+/// gives [`RangeOverlap::AInsideB`].
+#[inline]
+pub fn excl_classify_nonempty<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
+    if is_empty_range(&a_start, &a_end, false) || is_empty_range(&b_start, &b_end, false) {
+        RangeOverlap::None
+    } else {
+        excl_classify(a_start, a_end, b_start, b_end)
+    }
+}
+
+/// Like [`incl_classify`], but if either range is empty (its start is after its end) the result
+/// is always [`RangeOverlap::None`].
+#[inline]
+pub fn incl_classify_nonempty<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> RangeOverlap {
+    if is_empty_range(&a_start, &a_end, true) || is_empty_range(&b_start, &b_end, true) {
+        RangeOverlap::None
+    } else {
+        incl_classify(a_start, a_end, b_start, b_end)
+    }
+}
+
+/// Like [`classify_any`], but if either range is empty the result is always [`RangeOverlap::None`].
+/// A range with an open side is never empty.
+#[inline]
+pub fn classify_any_nonempty<T: PartialOrd>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> RangeOverlap {
+    let is_empty = |start: &Option<T>, end: &Option<T>| match (start, end) {
+        (Some(s), Some(e)) => is_empty_range(s, e, inclusive),
+        _ => false,
+    };
+
+    if is_empty(&a_start, &a_end) || is_empty(&b_start, &b_end) {
+        RangeOverlap::None
+    } else {
+        classify_any(a_start, a_end, b_start, b_end, inclusive)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_open_range_exclusive_bool() {
         let r1_start = 1;
         let r1_end = 20;
@@ -562,6 +921,92 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_empty_ranges() {
+        assert!(is_empty_range(&5, &5, false));
+        assert!(!is_empty_range(&5, &5, true));
+        assert!(is_empty_range(&6, &5, true));
+        assert!(is_empty_range(&f64::NAN, &5.0, true));
+
+        // The original functions treat empty ranges like points...
+        assert_eq!(excl_classify(5, 5, 1, 10), RangeOverlap::AInsideB);
+        // ...while the nonempty versions say they overlap nothing
+        assert_eq!(excl_classify_nonempty(5, 5, 1, 10), RangeOverlap::None);
+        assert_eq!(excl_classify_nonempty(1, 10, 5, 5), RangeOverlap::None);
+        assert_eq!(excl_classify_nonempty(5, 5, 5, 5), RangeOverlap::None);
+        assert_eq!(excl_classify_nonempty(1, 10, 5, 6), RangeOverlap::AContainsB);
+
+        assert_eq!(incl_classify_nonempty(5, 5, 1, 10), RangeOverlap::AInsideB);
+        assert_eq!(incl_classify_nonempty(6, 5, 1, 10), RangeOverlap::None);
+        assert_eq!(incl_classify_nonempty(1, 10, 10, 9), RangeOverlap::None);
+
+        assert_eq!(classify_any_nonempty(Some(5), Some(5), None, None, false), RangeOverlap::None);
+        assert_eq!(classify_any_nonempty(Some(5), Some(5), None, None, true), RangeOverlap::AInsideB);
+        assert_eq!(classify_any_nonempty(Some(5), None, None, Some(10), false), RangeOverlap::AStartsInB);
+    }
+
+    #[test]
+    fn test_has_overlap_matches_classify() {
+        // Every combination of small end points, including empty and backwards ranges and NaNs
+        let values = [0.0, 1.0, 2.0, 3.0, f64::NAN];
+        for a_start in values {
+            for a_end in values {
+                for b_start in values {
+                    for b_end in values {
+                        let ends = (a_start, a_end, b_start, b_end);
+                        assert_eq!(has_excl_overlap(a_start, a_end, b_start, b_end), excl_classify(a_start, a_end, b_start, b_end).has_overlap(), "{ends:?}");
+                        assert_eq!(has_incl_overlap(a_start, a_end, b_start, b_end), incl_classify(a_start, a_end, b_start, b_end).has_overlap(), "{ends:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_point_ranges() {
+        assert_eq!(incl_classify(5, 5, 5, 10), RangeOverlap::AInsideB);
+        assert_eq!(incl_classify(7, 7, 5, 10), RangeOverlap::AInsideB);
+        assert_eq!(incl_classify(10, 10, 5, 10), RangeOverlap::AInsideB);
+        assert_eq!(incl_classify(4, 4, 5, 10), RangeOverlap::None);
+        assert_eq!(incl_classify(11, 11, 5, 10), RangeOverlap::None);
+        assert_eq!(incl_classify(5, 10, 5, 5), RangeOverlap::AContainsB);
+        assert_eq!(incl_classify(5, 10, 10, 10), RangeOverlap::AContainsB);
+        assert_eq!(incl_classify(5, 10, 11, 11), RangeOverlap::None);
+        assert_eq!(incl_classify(5, 5, 5, 5), RangeOverlap::AEqualsB);
+        assert_eq!(incl_classify(5, 5, 6, 6), RangeOverlap::None);
+
+        assert_eq!(classify_any(Some(5), Some(5), Some(5), None, true), RangeOverlap::AInsideB);
+        assert_eq!(classify_any(Some(5), Some(5), None, Some(5), true), RangeOverlap::AInsideB);
+        assert_eq!(classify_any(Some(5), Some(5), Some(5), Some(10), true), RangeOverlap::AInsideB);
+
+        assert!(range_contains_point(5, 10, 5, false));
+        assert!(range_contains_point(5, 10, 5, true));
+        assert!(!range_contains_point(5, 10, 10, false));
+        assert!(range_contains_point(5, 10, 10, true));
+        assert!(!range_contains_point(5, 10, 4, true));
+        assert!(!range_contains_point(5, 10, 11, true));
+    }
+
+    #[test]
+    fn test_open_range_contains_point() {
+        for start in 0..5 {
+            for end in 0..5 {
+                for point in -1..6 {
+                    for inclusive in [false, true] {
+                        let expected = range_contains_point(start, end, point, inclusive);
+                        assert_eq!(open_range_contains_point(Some(start), Some(end), point, inclusive), expected);
+                    }
+                }
+            }
+        }
+
+        assert!(open_range_contains_point(Some(5), None, 5, false));
+        assert!(!open_range_contains_point(Some(5), None, 4, true));
+        assert!(open_range_contains_point(None, Some(10), 9, false));
+        assert!(!open_range_contains_point(None, Some(10), 10, false));
+        assert!(!open_range_contains_point(Some(0.0), None, f64::NAN, true));
+    }
+
     #[test]
     fn test_exclusive_vs_inclusive() {
         assert_eq!(excl_classify(1, 5, 5, 10), RangeOverlap::None);
@@ -591,4 +1036,18 @@ mod tests {
 
     }
 
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schemas() {
+        let overlap = schemars::schema_for!(RangeOverlap);
+        let variants = overlap.as_value()["oneOf"].as_array().unwrap().iter().map(|v| v["const"].as_str().unwrap()).collect::<Vec<_>>();
+        assert_eq!(variants, ["AContainsB", "AInsideB", "AEndsInB", "AStartsInB", "AEqualsB", "None"]);
+
+        let interval = schemars::schema_for!(Interval<f64>);
+        assert_eq!(interval.as_value()["required"].as_array().unwrap(), &["start", "end"]);
+
+        // Sets list their intervals the same way however they are stored
+        let set = schemars::schema_for!(IntervalSet<i32>);
+        assert_eq!(set.as_value()["properties"]["intervals"]["type"], "array");
+    }
 }
@@ -0,0 +1,263 @@
+//! A map from non-overlapping intervals to values.
+use crate::{AllenRelation, Interval, OverlapError};
+
+/// What [`IntervalMap::insert`] does when the new interval overlaps intervals already in the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum InsertPolicy {
+    /// Leave the map unchanged and return an [`OverlapError`].
+    Error,
+
+    /// Keep the existing entries, and only map the parts of the new interval that are not already
+    /// in the map. The new interval may be split into several entries.
+    Split,
+
+    /// Map the whole new interval to the new value, trimming or splitting existing entries to make room.
+    Overwrite,
+}
+
+/// A map whose keys are intervals, so that every point in a key's interval maps to that key's value.
+/// No two keys overlap, though they may touch. Entries are kept sorted by interval, and empty intervals
+/// are never stored.
+///
+/// ```
+/// # use range_overlap::{InsertPolicy, Interval, IntervalMap};
+/// let mut rooms = IntervalMap::new();
+/// rooms.insert(Interval::excl(9, 12), "standup", InsertPolicy::Error).unwrap();
+/// assert!(rooms.insert(Interval::excl(11, 13), "lunch", InsertPolicy::Error).is_err());
+/// rooms.insert(Interval::excl(11, 13), "lunch", InsertPolicy::Overwrite).unwrap();
+///
+/// assert_eq!(rooms.get(&10), Some(&"standup"));
+/// assert_eq!(rooms.get(&11), Some(&"lunch"));
+/// let overlapping: Vec<_> = rooms.get_overlapping(&Interval::excl(10, 12)).collect();
+/// assert_eq!(overlapping, [(&Interval::excl(9, 11), &"standup"), (&Interval::excl(11, 13), &"lunch")]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct IntervalMap<T, V> {
+    entries: Vec<(Interval<T>, V)>,
+}
+
+impl<T, V> IntervalMap<T, V> {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Returns `true` if there are no entries in this map.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of entries in this map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Iterate over the entries in this map, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Interval<T>, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<T, V> Default for IntervalMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone, V> IntervalMap<T, V> {
+    /// The range of indices of entries whose keys overlap `interval`.
+    fn overlapping_range(&self, interval: &Interval<T>) -> std::ops::Range<usize> {
+        let start = self.entries.partition_point(|(k, _)| matches!(k.allen_relation(interval), Some(AllenRelation::Before | AllenRelation::Meets)));
+        let end = start + self.entries[start..].partition_point(|(k, _)| k.has_overlap(interval));
+        start..end
+    }
+
+    /// The value mapped to `point`, if any.
+    pub fn get(&self, point: &T) -> Option<&V> {
+        self.stab(point).map(|(_, v)| v)
+    }
+
+    /// The entry whose key contains `point`, if any. Keys do not overlap, so at most one can.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalMap};
+    /// let map: IntervalMap<_, _> = [(Interval::excl(0, 5), 'a'), (Interval::incl(5, 9), 'b')].into_iter().collect();
+    /// assert_eq!(map.stab(&5), Some((&Interval::incl(5, 9), &'b')));
+    /// assert_eq!(map.stab(&10), None);
+    /// ```
+    pub fn stab(&self, point: &T) -> Option<(&Interval<T>, &V)> {
+        let point = Interval::point(point.clone());
+        let range = self.overlapping_range(&point);
+        self.entries[range].first().map(|(k, v)| (k, v))
+    }
+
+    /// The entries whose keys overlap `interval`, in order.
+    pub fn get_overlapping(&self, interval: &Interval<T>) -> impl Iterator<Item = (&Interval<T>, &V)> {
+        let range = self.overlapping_range(interval);
+        self.entries[range].iter().map(|(k, v)| (k, v))
+    }
+
+    /// Returns `true` if any key overlaps `interval`.
+    pub fn has_overlap(&self, interval: &Interval<T>) -> bool {
+        !self.overlapping_range(interval).is_empty()
+    }
+}
+
+impl<T: PartialOrd + Clone, V: Clone> IntervalMap<T, V> {
+    /// Map the values in `interval` to `value`. If `interval` overlaps existing keys, `policy` says
+    /// what to do; only [`InsertPolicy::Error`] can return an error. Inserting an empty interval does nothing.
+    ///
+    /// ```
+    /// # use range_overlap::{InsertPolicy, Interval, IntervalMap};
+    /// let mut map = IntervalMap::new();
+    /// map.insert(Interval::excl(2, 4), 'a', InsertPolicy::Error).unwrap();
+    /// map.insert(Interval::excl(0, 6), 'b', InsertPolicy::Split).unwrap();
+    /// let entries: Vec<_> = map.iter().collect();
+    /// assert_eq!(entries, [(&Interval::excl(0, 2), &'b'), (&Interval::excl(2, 4), &'a'), (&Interval::excl(4, 6), &'b')]);
+    /// ```
+    pub fn insert(&mut self, interval: Interval<T>, value: V, policy: InsertPolicy) -> Result<(), OverlapError> {
+        if interval.is_empty() {
+            return Ok(());
+        }
+
+        let range = self.overlapping_range(&interval);
+        let replacement = match policy {
+            InsertPolicy::Error if !range.is_empty() => return Err(OverlapError),
+            InsertPolicy::Error => vec![(interval, value)],
+            InsertPolicy::Overwrite => {
+                let mut before = Vec::new();
+                let mut after = Vec::new();
+                for (key, old) in &self.entries[range.clone()] {
+                    let (b, _, a) = key.split_by(&interval);
+                    before.extend(b.map(|b| (b, old.clone())));
+                    after.extend(a.map(|a| (a, old.clone())));
+                }
+                before.push((interval, value));
+                before.extend(after);
+                before
+            },
+            InsertPolicy::Split => {
+                let mut entries = Vec::new();
+                let mut remaining = Some(interval);
+                for (key, old) in &self.entries[range.clone()] {
+                    if let Some(current) = remaining.take() {
+                        let (b, _, a) = current.split_by(key);
+                        entries.extend(b.map(|b| (b, value.clone())));
+                        remaining = a;
+                    }
+                    entries.push((key.clone(), old.clone()));
+                }
+                entries.extend(remaining.map(|r| (r, value)));
+                entries
+            },
+        };
+        self.entries.splice(range, replacement);
+        Ok(())
+    }
+
+    /// Unmap the values in `interval`, trimming or splitting any entries it overlaps.
+    pub fn remove(&mut self, interval: &Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let range = self.overlapping_range(interval);
+        let mut pieces = Vec::new();
+        for (key, value) in &self.entries[range.clone()] {
+            pieces.extend(key.difference(interval).into_vec().into_iter().map(|k| (k, value.clone())));
+        }
+        self.entries.splice(range, pieces);
+    }
+}
+
+impl<T: PartialOrd + Clone, V: Clone> FromIterator<(Interval<T>, V)> for IntervalMap<T, V> {
+    /// Build a map by inserting each entry in turn with [`InsertPolicy::Overwrite`], so later
+    /// entries take precedence.
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (interval, value) in iter {
+            map.insert(interval, value, InsertPolicy::Overwrite).expect("overwriting never fails");
+        }
+        map
+    }
+}
+
+impl<T, V> IntoIterator for IntervalMap<T, V> {
+    type Item = (Interval<T>, V);
+    type IntoIter = std::vec::IntoIter<(Interval<T>, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, contains, sample_intervals, sample_points};
+
+    fn lookup(map: &IntervalMap<i32, usize>, point: f64) -> Option<usize> {
+        map.iter().find(|(k, _)| contains(k, point)).map(|(_, &v)| v)
+    }
+
+    #[test]
+    fn test_policies() {
+        let intervals = sample_intervals(5);
+        let points = sample_points(5);
+
+        // Apply a long, deterministic but scrambled sequence of operations, checking which value each
+        // sample point should map to after each one
+        let mut map = IntervalMap::new();
+        let mut expected: Vec<Option<usize>> = vec![None; points.len()];
+        let mut rng = Lcg::new(2468);
+        for step in 0..3000 {
+            let interval = *rng.pick(&intervals);
+            let covered: Vec<usize> = (0..points.len()).filter(|&i| contains(&interval, points[i])).collect();
+            match step % 4 {
+                0 => {
+                    let result = map.insert(interval, step, InsertPolicy::Error);
+                    let overlaps = covered.iter().any(|&i| expected[i].is_some());
+                    assert_eq!(result.is_err(), overlaps, "step {step}: inserting {interval:?} into {map:?}");
+                    if !overlaps {
+                        covered.iter().for_each(|&i| expected[i] = Some(step));
+                    }
+                },
+                1 => {
+                    map.insert(interval, step, InsertPolicy::Split).unwrap();
+                    covered.iter().for_each(|&i| expected[i] = expected[i].or(Some(step)));
+                },
+                2 => {
+                    map.insert(interval, step, InsertPolicy::Overwrite).unwrap();
+                    covered.iter().for_each(|&i| expected[i] = Some(step));
+                },
+                _ => {
+                    map.remove(&interval);
+                    covered.iter().for_each(|&i| expected[i] = None);
+                },
+            }
+
+            for pair in map.entries.windows(2) {
+                assert!(!pair[0].0.has_overlap(&pair[1].0) && pair[0].0 < pair[1].0, "step {step}: {map:?}");
+            }
+            assert!(map.iter().all(|(k, _)| !k.is_empty()), "step {step}: {map:?}");
+            for (i, &p) in points.iter().enumerate() {
+                assert_eq!(lookup(&map, p), expected[i], "step {step}: {map:?} at {p}");
+            }
+            for p in -1..6 {
+                assert_eq!(map.get(&p), map.iter().find(|(k, _)| k.contains_point(&p)).map(|(_, v)| v), "step {step}: {map:?} at {p}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_overlapping() {
+        let map: IntervalMap<i32, char> = [(Interval::excl(0, 2), 'a'), (Interval::incl(2, 3), 'b'), (Interval::greater_than(4), 'c')].into_iter().collect();
+        for interval in sample_intervals(5) {
+            let expected: Vec<_> = map.iter().filter(|(k, _)| k.has_overlap(&interval)).collect();
+            assert_eq!(map.get_overlapping(&interval).collect::<Vec<_>>(), expected, "{interval:?}");
+            assert_eq!(map.has_overlap(&interval), !expected.is_empty(), "{interval:?}");
+        }
+    }
+}
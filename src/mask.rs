@@ -0,0 +1,160 @@
+//! Sets of [`RangeOverlap`] variants, for checking a classification against several acceptable ones at once.
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not};
+
+use crate::RangeOverlap;
+
+/// A set of [`RangeOverlap`] variants. Combine masks with `|` and test a classification against one
+/// with [`RangeOverlap::matches`].
+///
+/// ```
+/// # use range_overlap::{excl_classify, RangeOverlap, RangeOverlapMask};
+/// let covers = RangeOverlapMask::A_CONTAINS_B | RangeOverlapMask::A_EQUALS_B;
+/// assert!(excl_classify(1, 10, 2, 5).matches(covers));
+/// assert!(excl_classify(1, 10, 1, 10).matches(covers));
+/// assert!(!excl_classify(1, 10, 5, 15).matches(covers));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RangeOverlapMask(u8);
+
+impl RangeOverlapMask {
+    /// Matches [`RangeOverlap::AContainsB`].
+    pub const A_CONTAINS_B: Self = Self(1 << 0);
+
+    /// Matches [`RangeOverlap::AInsideB`].
+    pub const A_INSIDE_B: Self = Self(1 << 1);
+
+    /// Matches [`RangeOverlap::AEndsInB`].
+    pub const A_ENDS_IN_B: Self = Self(1 << 2);
+
+    /// Matches [`RangeOverlap::AStartsInB`].
+    pub const A_STARTS_IN_B: Self = Self(1 << 3);
+
+    /// Matches [`RangeOverlap::AEqualsB`].
+    pub const A_EQUALS_B: Self = Self(1 << 4);
+
+    /// Matches [`RangeOverlap::None`].
+    pub const NO_OVERLAP: Self = Self(1 << 5);
+
+    /// Matches nothing.
+    pub const EMPTY: Self = Self(0);
+
+    /// Matches every variant except [`RangeOverlap::None`], i.e. any overlap.
+    pub const ANY_OVERLAP: Self = Self(0b01_1111);
+
+    /// Matches every variant.
+    pub const ALL: Self = Self(0b11_1111);
+
+    /// The mask matching only `overlap`.
+    pub const fn of(overlap: RangeOverlap) -> Self {
+        match overlap {
+            RangeOverlap::AContainsB => Self::A_CONTAINS_B,
+            RangeOverlap::AInsideB => Self::A_INSIDE_B,
+            RangeOverlap::AEndsInB => Self::A_ENDS_IN_B,
+            RangeOverlap::AStartsInB => Self::A_STARTS_IN_B,
+            RangeOverlap::AEqualsB => Self::A_EQUALS_B,
+            RangeOverlap::None => Self::NO_OVERLAP,
+        }
+    }
+
+    /// Returns `true` if `overlap` is in this set.
+    pub const fn contains(&self, overlap: RangeOverlap) -> bool {
+        self.0 & Self::of(overlap).0 != 0
+    }
+
+    /// Returns `true` if this set has no variants in it.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The raw bits of this mask.
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<RangeOverlap> for RangeOverlapMask {
+    fn from(value: RangeOverlap) -> Self {
+        Self::of(value)
+    }
+}
+
+impl FromIterator<RangeOverlap> for RangeOverlapMask {
+    fn from_iter<I: IntoIterator<Item = RangeOverlap>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::EMPTY, |mask, overlap| mask | Self::of(overlap))
+    }
+}
+
+impl BitOr for RangeOverlapMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for RangeOverlapMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for RangeOverlapMask {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Not for RangeOverlapMask {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+impl RangeOverlap {
+    /// Returns `true` if this variant is one of those in `mask`.
+    pub fn matches(&self, mask: RangeOverlapMask) -> bool {
+        mask.contains(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARIANTS: [RangeOverlap; 6] = [
+        RangeOverlap::AContainsB, RangeOverlap::AInsideB, RangeOverlap::AEndsInB,
+        RangeOverlap::AStartsInB, RangeOverlap::AEqualsB, RangeOverlap::None,
+    ];
+
+    #[test]
+    fn test_single_variants() {
+        for a in VARIANTS {
+            for b in VARIANTS {
+                assert_eq!(a.matches(RangeOverlapMask::of(b)), a == b);
+                assert_eq!(a.matches(!RangeOverlapMask::of(b)), a != b);
+            }
+            assert!(a.matches(RangeOverlapMask::ALL));
+            assert!(!a.matches(RangeOverlapMask::EMPTY));
+            assert_eq!(a.matches(RangeOverlapMask::ANY_OVERLAP), a.has_overlap());
+        }
+    }
+
+    #[test]
+    fn test_combining() {
+        let mask: RangeOverlapMask = VARIANTS.into_iter().collect();
+        assert_eq!(mask, RangeOverlapMask::ALL);
+        assert_eq!(!RangeOverlapMask::NO_OVERLAP, RangeOverlapMask::ANY_OVERLAP);
+        assert!((RangeOverlapMask::A_ENDS_IN_B & RangeOverlapMask::A_STARTS_IN_B).is_empty());
+
+        let mut mask = RangeOverlapMask::from(RangeOverlap::AEndsInB);
+        mask |= RangeOverlapMask::A_STARTS_IN_B;
+        assert!(RangeOverlap::AStartsInB.matches(mask));
+        assert!(!RangeOverlap::AInsideB.matches(mask));
+        assert_eq!(mask.bits(), 0b1100);
+    }
+}
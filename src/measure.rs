@@ -0,0 +1,436 @@
+//! Measuring ranges and their overlap: lengths, and how far apart two ranges are.
+use std::ops::{Add, Bound, Div, Sub};
+
+use crate::{intersection, AllenRelation, Interval};
+
+impl<T: Clone + Sub> Interval<T> {
+    /// The length of this interval, i.e. its end minus its start, or `None` if either side is open.
+    /// Whether each end is included makes no difference. The subtraction overflows as `-` does (a panic
+    /// in debug builds) if the length is too large for the type, as for `Interval::incl(i64::MIN, i64::MAX)`;
+    /// convert the end points to a wider type first if that can happen.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(2, 7).length(), Some(5));
+    /// assert_eq!(Interval::incl(2.0, 7.5).length(), Some(5.5));
+    /// assert_eq!(Interval::at_least(2).length(), None);
+    /// ```
+    pub fn length(&self) -> Option<T::Output> {
+        match (self.start(), self.end()) {
+            (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) => Some(e.clone() - s.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<bool>> Interval<T> {
+    /// The value halfway between the start and end of this interval, or `None` if either side is open.
+    /// For integers, this rounds toward the start. It does not overflow, even when the ends are the
+    /// type's smallest and largest values or the interval is backwards. Available for the primitive
+    /// integers and floats, or any type that can be made from a `bool`.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(2.0, 7.0).midpoint(), Some(4.5));
+    /// assert_eq!(Interval::incl(2, 7).midpoint(), Some(4));
+    /// assert_eq!(Interval::incl(i64::MIN, i64::MAX).midpoint(), Some(-1));
+    /// assert_eq!(Interval::less_than(7).midpoint(), None);
+    /// ```
+    pub fn midpoint(&self) -> Option<T> {
+        let (s, e) = match (self.start(), self.end()) {
+            (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) => (s.clone(), e.clone()),
+            _ => return None,
+        };
+        let (zero, one) = (T::from(false), T::from(true));
+        let two = one.clone() + one.clone();
+        if (s < zero) == (e < zero) {
+            // Ends on the same side of zero are close enough together that their difference fits, as long
+            // as the smaller is taken from the larger
+            return if s > e { Some(s.clone() - (s - e) / two) } else { Some(s.clone() + (e - s) / two) };
+        }
+
+        // Ends on opposite sides of zero may be too far apart for their difference to fit, but their sum
+        // always does. Halving it rounds toward zero, which for an odd sum may be away from the start
+        let sum = s.clone() + e.clone();
+        let mid = sum.clone() / two;
+        let odd = sum.clone() - mid.clone() - mid.clone() != zero;
+        if odd && s < e && sum < zero {
+            Some(mid - one)
+        } else if odd && s > e && sum > zero {
+            Some(mid + one)
+        } else {
+            Some(mid)
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone + Sub> Interval<T> {
+    /// The length of the values this interval has in common with `other`, or `None` if they have no
+    /// values in common or their overlap is not finite. Intervals that only share a single value
+    /// (e.g. `[1, 5]` and `[5, 9]`) have an overlap of length zero.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(1, 5).overlap_length(&Interval::excl(3, 9)), Some(2));
+    /// assert_eq!(Interval::incl(1, 5).overlap_length(&Interval::incl(5, 9)), Some(0));
+    /// assert_eq!(Interval::excl(1, 5).overlap_length(&Interval::excl(5, 9)), None);
+    /// ```
+    pub fn overlap_length(&self, other: &Interval<T>) -> Option<T::Output> {
+        self.intersect(other)?.length()
+    }
+}
+
+impl<T: PartialOrd + Clone + Sub> Interval<T>
+where
+    T::Output: Default,
+{
+    /// How far apart this interval and `other` are: the length of the [gap](Interval::gap) between them,
+    /// or zero (`T::Output::default()`) if they overlap or touch. Returns `None` if either interval is empty
+    /// or the end points cannot be compared.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(1, 5).distance(&Interval::excl(8, 9)), Some(3));
+    /// assert_eq!(Interval::excl(8, 9).distance(&Interval::excl(1, 5)), Some(3));
+    /// assert_eq!(Interval::excl(1, 5).distance(&Interval::excl(5, 9)), Some(0));
+    /// assert_eq!(Interval::excl(1, 5).distance(&Interval::at_least(2)), Some(0));
+    /// ```
+    pub fn distance(&self, other: &Interval<T>) -> Option<T::Output> {
+        let (first, second) = match self.allen_relation(other)? {
+            AllenRelation::Before => (self, other),
+            AllenRelation::After => (other, self),
+            _ => return Some(T::Output::default()),
+        };
+        match (first.end(), second.start()) {
+            (Bound::Included(e) | Bound::Excluded(e), Bound::Included(s) | Bound::Excluded(s)) => Some(s.clone() - e.clone()),
+            _ => unreachable!("an interval before another must have a finite end, and the other a finite start"),
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone + Sub> Interval<T>
+where
+    T::Output: Into<f64>,
+{
+    /// The fraction of this interval's length that `other` covers, from 0 to 1. Returns `None` if this
+    /// interval is open or has no length, since there is nothing to take a fraction of.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// let shift = Interval::excl(9, 17);
+    /// assert_eq!(shift.fraction_covered_by(&Interval::excl(15, 20)), Some(0.25));
+    /// assert_eq!(shift.fraction_covered_by(&Interval::excl(0, 9)), Some(0.0));
+    /// assert_eq!(Interval::at_least(9).fraction_covered_by(&shift), None);
+    /// ```
+    pub fn fraction_covered_by(&self, other: &Interval<T>) -> Option<f64> {
+        let length: f64 = self.length()?.into();
+        if length <= 0.0 {
+            return None;
+        }
+        let overlap: f64 = self.overlap_length(other).map_or(0.0, Into::into);
+        Some(overlap / length)
+    }
+
+    /// The Jaccard index of this interval and `other`: the length of their overlap divided by the length
+    /// of their union, from 0 (no overlap) to 1 (the same range). Returns `None` if either interval is open
+    /// or both have no length.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(0, 6).jaccard_index(&Interval::excl(3, 9)), Some(1.0 / 3.0));
+    /// assert_eq!(Interval::excl(0, 6).jaccard_index(&Interval::excl(0, 6)), Some(1.0));
+    /// assert_eq!(Interval::excl(0, 6).jaccard_index(&Interval::excl(7, 9)), Some(0.0));
+    /// ```
+    pub fn jaccard_index(&self, other: &Interval<T>) -> Option<f64> {
+        let a: f64 = self.length()?.into();
+        let b: f64 = other.length()?.into();
+        let overlap: f64 = self.overlap_length(other).map_or(0.0, Into::into);
+        let union = a.max(0.0) + b.max(0.0) - overlap;
+        if union <= 0.0 {
+            return None;
+        }
+        Some(overlap / union)
+    }
+}
+
+/// The length of the values two closed ranges have in common, or `None` if they have none in common.
+/// As elsewhere, the start of each range is included and `inclusive` says whether the ends are, which
+/// matters for ranges that only share an end point.
+///
+/// ```
+/// # use range_overlap::overlap_length;
+/// assert_eq!(overlap_length(1.0, 5.0, 3.5, 9.0, false), Some(1.5));
+/// assert_eq!(overlap_length(1, 5, 5, 9, true), Some(0));
+/// assert_eq!(overlap_length(1, 5, 5, 9, false), None);
+/// ```
+pub fn overlap_length<T: PartialOrd + Sub>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<T::Output> {
+    let (start, end) = intersection(a_start, a_end, b_start, b_end, inclusive)?;
+    Some(end - start)
+}
+
+/// The fraction of range `A` that range `B` covers, from 0 to 1, or `None` if `A` has no length. Ranges are given
+/// as for [`overlap_length`].
+///
+/// ```
+/// # use range_overlap::fraction_of_a_in_b;
+/// assert_eq!(fraction_of_a_in_b(9, 17, 15, 20, false), Some(0.25));
+/// assert_eq!(fraction_of_a_in_b(9, 17, 0, 30, false), Some(1.0));
+/// ```
+pub fn fraction_of_a_in_b<T: PartialOrd + Clone + Sub>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<f64>
+where
+    T::Output: Into<f64>,
+{
+    let a = Interval::new_unchecked(Some(a_start), Some(a_end), inclusive);
+    let b = Interval::new_unchecked(Some(b_start), Some(b_end), inclusive);
+    a.fraction_covered_by(&b)
+}
+
+/// The fraction of range `B` that range `A` covers, from 0 to 1, or `None` if `B` has no length. Ranges are given
+/// as for [`overlap_length`].
+///
+/// ```
+/// # use range_overlap::fraction_of_b_in_a;
+/// assert_eq!(fraction_of_b_in_a(9, 17, 15, 20, false), Some(0.4));
+/// ```
+pub fn fraction_of_b_in_a<T: PartialOrd + Clone + Sub>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<f64>
+where
+    T::Output: Into<f64>,
+{
+    fraction_of_a_in_b(b_start, b_end, a_start, a_end, inclusive)
+}
+
+/// The Jaccard index of two ranges, i.e. the length of their overlap divided by the length of their union, or `None`
+/// if both have no length. Ranges are given as for [`overlap_length`].
+///
+/// ```
+/// # use range_overlap::jaccard_index;
+/// assert_eq!(jaccard_index(0.0, 6.0, 3.0, 9.0, false), Some(1.0 / 3.0));
+/// ```
+pub fn jaccard_index<T: PartialOrd + Clone + Sub>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<f64>
+where
+    T::Output: Into<f64>,
+{
+    let a = Interval::new_unchecked(Some(a_start), Some(a_end), inclusive);
+    let b = Interval::new_unchecked(Some(b_start), Some(b_end), inclusive);
+    a.jaccard_index(&b)
+}
+
+/// The fraction of range `A` that range `B` covers, from 0 to 1, for ranges that may have open sides
+/// (given as for [`classify_any`](crate::classify_any)). `B` may be open, but if `A` has an open side,
+/// or no length, there is no fraction of it to give and this returns `None`.
+///
+/// ```
+/// # use range_overlap::coverage_of_a_by_b;
+/// // The maintenance window covers 80% of the outage
+/// assert_eq!(coverage_of_a_by_b(Some(10.0), Some(20.0), Some(12.0), None, false), Some(0.8));
+/// assert_eq!(coverage_of_a_by_b(Some(10.0), None, Some(12.0), Some(20.0), false), None);
+/// ```
+pub fn coverage_of_a_by_b<T: PartialOrd + Clone + Sub>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> Option<f64>
+where
+    T::Output: Into<f64>,
+{
+    let a = Interval::new_unchecked(a_start, a_end, inclusive);
+    let b = Interval::new_unchecked(b_start, b_end, inclusive);
+    a.fraction_covered_by(&b)
+}
+
+/// The fraction of range `B` that range `A` covers, from 0 to 1, for ranges that may have open sides.
+/// Like [`coverage_of_a_by_b`], this returns `None` if `B` has an open side or no length.
+///
+/// ```
+/// # use range_overlap::coverage_of_b_by_a;
+/// assert_eq!(coverage_of_b_by_a(None, Some(15), Some(10), Some(20), false), Some(0.5));
+/// ```
+pub fn coverage_of_b_by_a<T: PartialOrd + Clone + Sub>(a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool) -> Option<f64>
+where
+    T::Output: Into<f64>,
+{
+    coverage_of_a_by_b(b_start, b_end, a_start, a_end, inclusive)
+}
+
+/// How far apart two ranges are: the size of the gap between them, or zero (`T::Output::default()`) if they
+/// overlap or touch. Ranges are given as for [`overlap_length`]; `None` is returned if either is empty or the
+/// end points cannot be compared.
+///
+/// ```
+/// # use range_overlap::distance;
+/// assert_eq!(distance(1.0, 5.0, 7.5, 9.0, false), Some(2.5));
+/// assert_eq!(distance(1, 5, 5, 9, false), Some(0));
+/// assert_eq!(distance(1, 5, 3, 4, false), Some(0));
+/// ```
+pub fn distance<T: PartialOrd + Clone + Sub>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<T::Output>
+where
+    T::Output: Default,
+{
+    let a = Interval::new_unchecked(Some(a_start), Some(a_end), inclusive);
+    let b = Interval::new_unchecked(Some(b_start), Some(b_end), inclusive);
+    a.distance(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlap_length() {
+        for a_start in 0..5 {
+            for a_end in a_start..5 {
+                for b_start in 0..5 {
+                    for b_end in b_start..5 {
+                        for inclusive in [false, true] {
+                            // Count the integers in both ranges; for exclusive ranges that's the length,
+                            // for inclusive ones it's one more.
+                            let in_range = |v, s, e| v >= s && if inclusive { v <= e } else { v < e };
+                            let shared = (0..5).filter(|&v| in_range(v, a_start, a_end) && in_range(v, b_start, b_end)).count() as i32;
+                            let expected = match (shared, inclusive) {
+                                (0, _) => None,
+                                (n, true) => Some(n - 1),
+                                (n, false) => Some(n),
+                            };
+                            assert_eq!(overlap_length(a_start, a_end, b_start, b_end, inclusive), expected);
+
+                            let a = Interval::new_unchecked(Some(a_start), Some(a_end), inclusive);
+                            let b = Interval::new_unchecked(Some(b_start), Some(b_end), inclusive);
+                            assert_eq!(a.overlap_length(&b), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fractions() {
+        for a_start in 0..5 {
+            for a_end in a_start..5 {
+                for b_start in 0..5 {
+                    for b_end in b_start..5 {
+                        let overlap = (a_end.min(b_end) - a_start.max(b_start)).max(0) as f64;
+                        let (a_len, b_len) = ((a_end - a_start) as f64, (b_end - b_start) as f64);
+                        let expected_a = (a_len > 0.0).then(|| overlap / a_len);
+                        let expected_b = (b_len > 0.0).then(|| overlap / b_len);
+                        let expected_j = (a_len + b_len > 0.0).then(|| overlap / (a_len + b_len - overlap));
+
+                        for inclusive in [false, true] {
+                            assert_eq!(fraction_of_a_in_b(a_start, a_end, b_start, b_end, inclusive), expected_a);
+                            assert_eq!(fraction_of_b_in_a(a_start, a_end, b_start, b_end, inclusive), expected_b);
+                            assert_eq!(jaccard_index(a_start, a_end, b_start, b_end, inclusive), expected_j);
+                            assert_eq!(jaccard_index(b_start, b_end, a_start, a_end, inclusive), expected_j);
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(Interval::excl(0, 4).fraction_covered_by(&Interval::at_least(1)), Some(0.75));
+        assert_eq!(Interval::excl(0, 4).jaccard_index(&Interval::at_least(1)), None);
+        assert_eq!(Interval::excl(4, 0).fraction_covered_by(&Interval::all()), None);
+    }
+
+    #[test]
+    fn test_coverage() {
+        for a_start in 0..5 {
+            for a_end in a_start..5 {
+                for b_start in 0..5 {
+                    for b_end in b_start..5 {
+                        for inclusive in [false, true] {
+                            let closed = fraction_of_a_in_b(a_start, a_end, b_start, b_end, inclusive);
+                            assert_eq!(coverage_of_a_by_b(Some(a_start), Some(a_end), Some(b_start), Some(b_end), inclusive), closed);
+                            let closed = fraction_of_b_in_a(a_start, a_end, b_start, b_end, inclusive);
+                            assert_eq!(coverage_of_b_by_a(Some(a_start), Some(a_end), Some(b_start), Some(b_end), inclusive), closed);
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(coverage_of_a_by_b(Some(0), Some(4), None, Some(1), false), Some(0.25));
+        assert_eq!(coverage_of_a_by_b(Some(0), Some(4), None, None, false), Some(1.0));
+        assert_eq!(coverage_of_a_by_b(None, Some(4), Some(0), Some(4), false), None);
+        assert_eq!(coverage_of_b_by_a(None, None, Some(0), None, true), None);
+    }
+
+    #[test]
+    fn test_distance() {
+        for a_start in 0..5 {
+            for a_end in a_start..5 {
+                for b_start in 0..5 {
+                    for b_end in b_start..5 {
+                        for inclusive in [false, true] {
+                            let empty = |s, e| if inclusive { s > e } else { s >= e };
+                            let expected = if empty(a_start, a_end) || empty(b_start, b_end) {
+                                None
+                            } else {
+                                Some((a_start - b_end).max(b_start - a_end).max(0))
+                            };
+                            assert_eq!(distance(a_start, a_end, b_start, b_end, inclusive), expected);
+                            assert_eq!(distance(b_start, b_end, a_start, a_end, inclusive), expected);
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(Interval::less_than(3.0).distance(&Interval::greater_than(4.5)), Some(1.5));
+        assert_eq!(Interval::<f64>::all().distance(&Interval::all()), Some(0.0));
+        assert_eq!(Interval::at_least(f64::NAN).distance(&Interval::all()), None);
+    }
+
+    #[test]
+    fn test_length_and_midpoint() {
+        for start in -5i32..5 {
+            for end in start..5 {
+                let a = Interval::excl(start, end);
+                assert_eq!(a.length(), Some(end - start));
+                assert_eq!(a.midpoint(), Some((start + end).div_euclid(2)));
+                let a = Interval::incl(start as f64, end as f64);
+                assert_eq!(a.midpoint(), Some((start + end) as f64 / 2.0));
+            }
+        }
+
+        assert_eq!(Interval::greater_than(1.0).midpoint(), None);
+        assert_eq!(Interval::<i64>::all().midpoint(), None);
+        assert_eq!(Interval::incl(i64::MAX - 2, i64::MAX).midpoint(), Some(i64::MAX - 1));
+    }
+
+    #[test]
+    fn test_extremes() {
+        // Every pair of i8 or u8 ends, including backwards ones, against the same sum worked out in i32
+        for start in i8::MIN..=i8::MAX {
+            for end in i8::MIN..=i8::MAX {
+                let (s, e) = (i32::from(start), i32::from(end));
+                assert_eq!(Interval::incl(start, end).midpoint(), Some((s + (e - s) / 2) as i8), "{start}..={end}");
+            }
+        }
+        for start in 0..=u8::MAX {
+            for end in 0..=u8::MAX {
+                let (s, e) = (i32::from(start), i32::from(end));
+                assert_eq!(Interval::incl(start, end).midpoint(), Some((s + (e - s) / 2) as u8), "{start}..={end}");
+            }
+        }
+
+        assert_eq!(Interval::incl(i64::MIN, i64::MAX).midpoint(), Some(-1));
+        assert_eq!(Interval::incl(i64::MAX, i64::MIN).midpoint(), Some(0));
+        assert_eq!(Interval::incl(i64::MIN, i64::MIN).midpoint(), Some(i64::MIN));
+        assert_eq!(Interval::incl(0, u64::MAX).midpoint(), Some(u64::MAX / 2));
+        assert_eq!(Interval::incl(5u64, 3).midpoint(), Some(4));
+        assert_eq!(Interval::incl(u64::MAX, 0).midpoint(), Some(u64::MAX - u64::MAX / 2));
+        assert_eq!(Interval::incl(i8::MIN, i8::MAX).midpoint(), Some(-1));
+        assert_eq!(Interval::incl(i8::MAX, i8::MIN).midpoint(), Some(0));
+        assert_eq!(Interval::incl(u64::MAX - 1, u64::MAX).midpoint(), Some(u64::MAX - 1));
+        assert_eq!(Interval::incl(-f64::MAX, f64::MAX).midpoint(), Some(0.0));
+        assert_eq!(Interval::incl(f64::MAX / 2.0, f64::MAX).midpoint(), Some(f64::MAX * 0.75));
+
+        // The longest lengths that still fit
+        assert_eq!(Interval::incl(i64::MIN, -1).length(), Some(i64::MAX));
+        assert_eq!(Interval::incl(0, u64::MAX).length(), Some(u64::MAX));
+        assert_eq!(Interval::incl(-f64::MAX, f64::MAX).length(), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_open_sides() {
+        assert_eq!(Interval::at_least(3).overlap_length(&Interval::less_than(10)), Some(7));
+        assert_eq!(Interval::at_least(3).overlap_length(&Interval::greater_than(10)), None);
+        assert_eq!(Interval::<i32>::all().length(), None);
+    }
+}
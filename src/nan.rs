@@ -0,0 +1,138 @@
+//! Choosing what a NaN end point means when classifying ranges.
+//!
+//! A value is treated as a NaN if it cannot be compared with itself, so this works for
+//! `f32`, `f64`, and any other type whose [`PartialOrd`] implementation behaves the same way.
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::{classify_any, Interval, RangeError, RangeOverlap};
+
+/// What to do when an end point given to [`classify_any_with_nan_policy`] or
+/// [`Interval::classify_against_with_nan_policy`] is a NaN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum NanPolicy {
+    /// Return [`RangeError::IncomparableEndpoints`].
+    Error,
+
+    /// Treat a NaN end point as if that side of the range were open, the same as passing `None`
+    /// to [`classify_any`].
+    TreatAsOpen,
+
+    /// Panic in debug builds. In release builds, the NaN is passed through unchanged, giving the
+    /// same result as the function without a policy would.
+    PanicInDebug,
+}
+
+fn is_nan<T: PartialOrd>(value: &T) -> bool {
+    value.partial_cmp(value).is_none()
+}
+
+impl NanPolicy {
+    /// Apply this policy to one end point, returning the end point that should be used in its place.
+    fn apply<T: PartialOrd>(self, value: Option<T>) -> Result<Option<T>, RangeError> {
+        match value {
+            Some(v) if is_nan(&v) => match self {
+                NanPolicy::Error => Err(RangeError::IncomparableEndpoints),
+                NanPolicy::TreatAsOpen => Ok(None),
+                NanPolicy::PanicInDebug => {
+                    debug_assert!(false, "NaN end point given to a range classification");
+                    Ok(Some(v))
+                },
+            },
+            _ => Ok(value),
+        }
+    }
+
+    fn apply_bound<T: PartialOrd>(self, bound: Bound<&T>) -> Result<Bound<&T>, RangeError> {
+        match bound {
+            Bound::Included(v) => Ok(self.apply(Some(v))?.map_or(Bound::Unbounded, Bound::Included)),
+            Bound::Excluded(v) => Ok(self.apply(Some(v))?.map_or(Bound::Unbounded, Bound::Excluded)),
+            Bound::Unbounded => Ok(Bound::Unbounded),
+        }
+    }
+}
+
+/// Like [`classify_any`], but with `policy` deciding what a NaN end point means.
+///
+/// ```
+/// # use range_overlap::{classify_any_with_nan_policy, NanPolicy, RangeError, RangeOverlap};
+/// let nan = f64::NAN;
+/// assert_eq!(
+///     classify_any_with_nan_policy(Some(1.0), Some(nan), Some(5.0), Some(8.0), false, NanPolicy::TreatAsOpen),
+///     Ok(RangeOverlap::AContainsB)
+/// );
+/// assert_eq!(
+///     classify_any_with_nan_policy(Some(1.0), Some(nan), Some(5.0), Some(8.0), false, NanPolicy::Error),
+///     Err(RangeError::IncomparableEndpoints)
+/// );
+/// ```
+pub fn classify_any_with_nan_policy<T: PartialOrd>(
+    a_start: Option<T>, a_end: Option<T>, b_start: Option<T>, b_end: Option<T>, inclusive: bool, policy: NanPolicy
+) -> Result<RangeOverlap, RangeError> {
+    Ok(classify_any(policy.apply(a_start)?, policy.apply(a_end)?, policy.apply(b_start)?, policy.apply(b_end)?, inclusive))
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// Like [`Interval::classify_against`], but with `policy` deciding what a NaN end point means.
+    /// With [`NanPolicy::TreatAsOpen`], a NaN bound is replaced by [`Bound::Unbounded`].
+    pub fn classify_against_with_nan_policy(&self, other: &Interval<T>, policy: NanPolicy) -> Result<RangeOverlap, RangeError> {
+        Ok(bounds::classify_bound_refs(
+            policy.apply_bound(self.start())?, policy.apply_bound(self.end())?,
+            policy.apply_bound(other.start())?, policy.apply_bound(other.end())?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAN: f64 = f64::NAN;
+
+    #[test]
+    fn test_error() {
+        let err = Err(RangeError::IncomparableEndpoints);
+        assert_eq!(classify_any_with_nan_policy(Some(NAN), Some(5.0), Some(1.0), Some(8.0), false, NanPolicy::Error), err);
+        assert_eq!(classify_any_with_nan_policy(None, None, None, Some(NAN), true, NanPolicy::Error), err);
+        assert_eq!(Interval::at_most(NAN).classify_against_with_nan_policy(&Interval::all(), NanPolicy::Error), err);
+        assert_eq!(
+            classify_any_with_nan_policy(Some(1.0), Some(5.0), Some(5.0), Some(8.0), true, NanPolicy::Error),
+            Ok(RangeOverlap::AEndsInB)
+        );
+    }
+
+    #[test]
+    fn test_treat_as_open() {
+        let open = NanPolicy::TreatAsOpen;
+        for inclusive in [false, true] {
+            assert_eq!(
+                classify_any_with_nan_policy(Some(NAN), Some(5.0), Some(1.0), Some(8.0), inclusive, open),
+                Ok(classify_any(None, Some(5.0), Some(1.0), Some(8.0), inclusive))
+            );
+            assert_eq!(
+                classify_any_with_nan_policy(Some(1.0), Some(5.0), Some(NAN), Some(NAN), inclusive, open),
+                Ok(RangeOverlap::AInsideB)
+            );
+        }
+
+        let a = Interval::from_bounds(Bound::Excluded(NAN), Bound::Excluded(5.0));
+        assert_eq!(a.classify_against_with_nan_policy(&Interval::excl(1.0, 8.0), open), Ok(RangeOverlap::AEndsInB));
+        assert_eq!(a.classify_against_with_nan_policy(&Interval::less_than(5.0), open), Ok(RangeOverlap::AEqualsB));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_panic_in_debug() {
+        let _ = classify_any_with_nan_policy(Some(NAN), Some(5.0), Some(1.0), Some(8.0), false, NanPolicy::PanicInDebug);
+    }
+
+    #[test]
+    fn test_panic_in_debug_without_nan() {
+        assert_eq!(
+            classify_any_with_nan_policy(Some(1.0), Some(5.0), Some(2.0), Some(8.0), false, NanPolicy::PanicInDebug),
+            Ok(RangeOverlap::AEndsInB)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Finding overlaps in a stream of intervals as they arrive, without keeping the whole stream.
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::Interval;
+
+/// A sweep over a stream of intervals that reports, as each one arrives, which of the intervals still
+/// *active* it overlaps. Intervals are numbered from `0` in the order they are pushed, like the indices
+/// [`find_conflicts`](crate::find_conflicts) gives, so the caller can keep whatever goes with them.
+///
+/// Only the active intervals are kept. The stream does not need to be exactly sorted: instead, the
+/// caller calls [`advance_to`](OnlineSweep::advance_to) with a *watermark* whenever it knows that no
+/// interval still to come starts before that value, and every active interval that ends before the
+/// watermark is evicted, since nothing still to come can overlap it. For a stream sorted by start, the
+/// watermark is just the start of each interval as it arrives; for one that may arrive a little out of
+/// order, it is however far behind the latest start intervals may still turn up. If an interval is pushed
+/// that does start before the watermark, its overlaps with evicted intervals are missed.
+///
+/// ```
+/// # use range_overlap::{Interval, OnlineSweep};
+/// let mut sweep = OnlineSweep::new();
+/// assert!(sweep.push(Interval::excl(0, 10)).is_empty());
+/// assert_eq!(sweep.push(Interval::excl(2, 4)), [0]);
+/// assert_eq!(sweep.push(Interval::excl(3, 12)), [0, 1]);
+///
+/// // Nothing that starts at 8 or later can overlap interval 1 any more
+/// assert_eq!(sweep.advance_to(&8), [1]);
+/// assert_eq!(sweep.push(Interval::excl(9, 11)), [0, 2]);
+/// assert_eq!(sweep.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OnlineSweep<T> {
+    /// The intervals that later ones may still overlap, with their numbers, in the order they were pushed
+    active: Vec<(usize, Interval<T>)>,
+    pushed: usize,
+}
+
+impl<T> OnlineSweep<T> {
+    /// Create a sweep with no intervals pushed yet.
+    pub fn new() -> Self {
+        Self { active: Vec::new(), pushed: 0 }
+    }
+
+    /// Returns `true` if no intervals are active.
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// The number of intervals that are active, i.e. have been pushed but not yet evicted.
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// The number of intervals pushed so far, which is also the number the next one will get.
+    pub fn pushed(&self) -> usize {
+        self.pushed
+    }
+
+    /// Iterate over the active intervals with their numbers, in the order they were pushed.
+    pub fn active(&self) -> impl Iterator<Item = (usize, &Interval<T>)> {
+        self.active.iter().map(|(n, iv)| (*n, iv))
+    }
+}
+
+impl<T> Default for OnlineSweep<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd> OnlineSweep<T> {
+    /// Add the next interval in the stream, returning the numbers of the active intervals it overlaps,
+    /// in the order they were pushed. The new interval is numbered [`pushed`](OnlineSweep::pushed) and
+    /// becomes active itself, unless it is empty, in which case it overlaps nothing.
+    pub fn push(&mut self, interval: Interval<T>) -> Vec<usize> {
+        let number = self.pushed;
+        self.pushed += 1;
+        if interval.is_empty() {
+            return Vec::new();
+        }
+        let overlaps = self.active.iter().filter(|(_, iv)| iv.has_overlap(&interval)).map(|(n, _)| *n).collect();
+        self.active.push((number, interval));
+        overlaps
+    }
+
+    /// Promise that no interval pushed from now on starts before `watermark`, and evict the active
+    /// intervals that end before it, returning their numbers in the order they were pushed. An interval
+    /// whose end cannot be compared with `watermark`, such as one ending at NaN, is never evicted.
+    pub fn advance_to(&mut self, watermark: &T) -> Vec<usize> {
+        let mut evicted = Vec::new();
+        self.active.retain(|(n, iv)| {
+            let expired = bounds::ends_before(iv.end(), Bound::Included(watermark)) == Some(true);
+            if expired {
+                evicted.push(*n);
+            }
+            !expired
+        });
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Lcg;
+    use crate::find_conflicts;
+
+    #[test]
+    fn test_matches_find_conflicts() {
+        let mut rng = Lcg::new(13579);
+        for _ in 0..200 {
+            // Roughly sorted: each start is a little either side of a rising baseline
+            let intervals: Vec<Interval<i32>> = (0..rng.below(30)).map(|i| {
+                let start = 2 * i + rng.below(9) - 4;
+                let end = start + rng.below(10) - 1;
+                match rng.below(6) {
+                    0 => Interval::at_least(start),
+                    1 => Interval::incl(start, end),
+                    2 => Interval::from_bounds(Bound::Excluded(start), Bound::Included(end)),
+                    _ => Interval::excl(start, end),
+                }
+            }).collect();
+            let start_value = |iv: &Interval<i32>| match iv.start() {
+                Bound::Included(&s) | Bound::Excluded(&s) => s,
+                Bound::Unbounded => unreachable!(),
+            };
+
+            let mut sweep = OnlineSweep::new();
+            let mut found = Vec::new();
+            for (j, interval) in intervals.iter().enumerate() {
+                // The tightest watermark there could be: the earliest start still to come
+                let watermark = intervals[j..].iter().map(start_value).min().expect("interval j is still to come");
+                for evicted in sweep.advance_to(&watermark) {
+                    assert_eq!(bounds::ends_before(intervals[evicted].end(), Bound::Included(&watermark)), Some(true));
+                }
+                assert_eq!(sweep.pushed(), j);
+                found.extend(sweep.push(*interval).into_iter().map(|i| (i, j)));
+                assert!(sweep.active().all(|(n, iv)| intervals[n] == *iv));
+            }
+            found.sort_unstable();
+            assert_eq!(found, find_conflicts(&intervals, None), "{intervals:?}");
+        }
+    }
+}
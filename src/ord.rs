@@ -0,0 +1,72 @@
+//! Classification for totally ordered end points, comparing each pair of end points at most once.
+use std::cmp::Ordering::{self, Equal, Greater, Less};
+
+use crate::RangeOverlap;
+
+/// Classify the kind of overlap between two fully closed ranges whose end points are totally ordered,
+/// with the ends considered inclusive if `inclusive` is `true` and exclusive otherwise. The answer is the
+/// same as [`incl_classify`](crate::incl_classify) or [`excl_classify`](crate::excl_classify) would give,
+/// including for empty and backwards ranges.
+///
+/// Those functions test one case after another with the `<`, `<=`, and `==` operators, so they may
+/// compare the same two end points several times. This calls [`Ord::cmp`] once for each of the (at most
+/// four) pairs of end points it needs and matches on the [`Ordering`]s, which saves work for types that
+/// are slow to compare, such as strings or tuples. For primitive integers, where a comparison is one
+/// instruction, the operator-based functions are faster, as the compiler can turn their tests into
+/// straight-line code; the `ord` benchmark compares the two.
+///
+/// ```
+/// # use range_overlap::{classify_ord, RangeOverlap};
+/// assert_eq!(classify_ord("apple", "cherry", "banana", "date", false), RangeOverlap::AEndsInB);
+/// assert_eq!(classify_ord(0, 5, 5, 10, false), RangeOverlap::None);
+/// assert_eq!(classify_ord(0, 5, 5, 10, true), RangeOverlap::AEndsInB);
+/// ```
+#[inline]
+pub fn classify_ord<T: Ord>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> RangeOverlap {
+    // Whether two ranges that touch at an end point overlap there
+    let touching = if inclusive { Equal } else { Less };
+    // Whether a range ending at an end point compared as `end_vs_start` with the other's start does not reach it
+    let short_of = |end_vs_start: Ordering| end_vs_start < touching.reverse();
+
+    match (a_start.cmp(&b_start), a_end.cmp(&b_end)) {
+        (Equal, Equal) => RangeOverlap::AEqualsB,
+        (Less | Equal, Greater | Equal) => RangeOverlap::AContainsB,
+        (Less, Less) if short_of(a_end.cmp(&b_start)) => RangeOverlap::None,
+        (Less, Less) => RangeOverlap::AEndsInB,
+        (Greater, Greater) if short_of(b_end.cmp(&a_start)) => RangeOverlap::None,
+        (Greater, Greater) => RangeOverlap::AStartsInB,
+        // A starts no earlier and ends no later than B, so is inside B unless either range is empty or
+        // backwards, in which case they may not meet at all
+        (Greater | Equal, Less | Equal) => {
+            if short_of(b_end.cmp(&a_start)) || short_of(a_end.cmp(&b_start)) {
+                RangeOverlap::None
+            } else {
+                RangeOverlap::AInsideB
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{excl_classify, incl_classify};
+
+    #[test]
+    fn test_matches_operators() {
+        // Every combination of small end points, including empty and backwards ranges
+        for a_start in 0..5 {
+            for a_end in 0..5 {
+                for b_start in 0..5 {
+                    for b_end in 0..5 {
+                        let ends = (a_start, a_end, b_start, b_end);
+                        assert_eq!(classify_ord(a_start, a_end, b_start, b_end, false), excl_classify(a_start, a_end, b_start, b_end), "{ends:?}");
+                        assert_eq!(classify_ord(a_start, a_end, b_start, b_end, true), incl_classify(a_start, a_end, b_start, b_end), "{ends:?}");
+                        let [a_start, a_end, b_start, b_end] = [a_start, a_end, b_start, b_end].map(|v| v.to_string());
+                        assert_eq!(classify_ord(&a_start, &a_end, &b_start, &b_end, false), excl_classify(&a_start, &a_end, &b_start, &b_end), "{ends:?}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Parallel versions of the all-pairs overlap searches, using `rayon`.
+use rayon::prelude::*;
+
+use crate::bounds;
+use crate::sweep::sorted_nonempty;
+use crate::{Interval, RangeOverlap, RangeOverlapMask};
+
+/// Like [`classify_matrix`](crate::classify_matrix), but classifying the rows in parallel across rayon's
+/// thread pool. The result is the same, in the same order, however the rows are shared out. Every pair is
+/// classified, so this pays off over the sequential sweep when many of the pairs overlap, or when there are
+/// enough rows to keep every thread busy. Only available with the `rayon` feature.
+///
+/// ```
+/// # use range_overlap::{par_classify_matrix, Interval, RangeOverlap};
+/// let a = [Interval::excl(0, 10), Interval::excl(20, 30)];
+/// let b = [Interval::excl(5, 25), Interval::excl(2, 4), Interval::excl(40, 50)];
+/// assert_eq!(par_classify_matrix(&a, &b), [
+///     [RangeOverlap::AEndsInB, RangeOverlap::AContainsB, RangeOverlap::None],
+///     [RangeOverlap::AStartsInB, RangeOverlap::None, RangeOverlap::None],
+/// ]);
+/// ```
+pub fn par_classify_matrix<T: PartialOrd + Sync>(a_list: &[Interval<T>], b_list: &[Interval<T>]) -> Vec<Vec<RangeOverlap>> {
+    a_list.par_iter().map(|a| b_list.iter().map(|b| a.classify_against(b)).collect()).collect()
+}
+
+/// Like [`find_conflicts`](crate::find_conflicts), but searching in parallel across rayon's thread pool.
+/// The intervals are sorted by start, then each one is checked, in parallel, against those starting after
+/// it, stopping at the first that starts after it ends. The pairs are sorted at the end, so the result is
+/// the same as [`find_conflicts`](crate::find_conflicts) gives, in the same order. Only available with the
+/// `rayon` feature.
+///
+/// ```
+/// # use range_overlap::{par_find_conflicts, Interval, RangeOverlapMask};
+/// let bookings = [Interval::excl(9, 12), Interval::excl(13, 14), Interval::excl(10, 11), Interval::excl(11, 13)];
+/// assert_eq!(par_find_conflicts(&bookings, None), [(0, 2), (0, 3)]);
+/// assert_eq!(par_find_conflicts(&bookings, Some(RangeOverlapMask::A_CONTAINS_B)), [(0, 2)]);
+/// ```
+pub fn par_find_conflicts<T: PartialOrd + Sync>(intervals: &[Interval<T>], mask: Option<RangeOverlapMask>) -> Vec<(usize, usize)> {
+    let order = sorted_nonempty(intervals);
+    let order = &order;
+    let mut conflicts: Vec<(usize, usize)> = (0..order.len()).into_par_iter().flat_map_iter(|p| {
+        let (index, first) = (order[p], &intervals[order[p]]);
+        // Everything after `first` in the order starts no earlier than it, so overlaps it unless it starts
+        // after `first` ends, and once one does, so do all the rest
+        order[p + 1..].iter()
+            .take_while(move |&&q| bounds::ends_before(first.end(), intervals[q].start()) == Some(false))
+            .map(move |&q| (index.min(q), index.max(q)))
+            .filter(move |&(i, j)| mask.is_none_or(|mask| intervals[i].classify_against(&intervals[j]).matches(mask)))
+    }).collect();
+    conflicts.par_sort_unstable();
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval};
+    use crate::{classify_matrix, find_conflicts};
+
+    fn sample_list(seed: u32, n: usize) -> Vec<Interval<i32>> {
+        let mut rng = Lcg::new(seed);
+        (0..n).map(|_| random_interval(&mut rng, 80, 15)).collect()
+    }
+
+    #[test]
+    fn test_matches_sequential() {
+        for seed in 0..100 {
+            let a = sample_list(seed, (seed % 40) as usize);
+            let b = sample_list(seed + 777, (seed % 17) as usize);
+            assert_eq!(par_classify_matrix(&a, &b), classify_matrix(&a, &b), "{a:?} against {b:?}");
+            for mask in [None, Some(RangeOverlapMask::A_CONTAINS_B), Some(RangeOverlapMask::A_ENDS_IN_B)] {
+                assert_eq!(par_find_conflicts(&a, mask), find_conflicts(&a, mask), "{a:?} with {mask:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,345 @@
+//! An immutable interval set whose updates share structure with earlier versions.
+use std::sync::Arc;
+
+use crate::{AllenRelation, Interval, IntervalSet};
+
+type Tree<T> = Option<Arc<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    interval: Interval<T>,
+    height: usize,
+    size: usize,
+    left: Tree<T>,
+    right: Tree<T>,
+}
+
+fn height<T>(tree: &Tree<T>) -> usize {
+    tree.as_ref().map_or(0, |n| n.height)
+}
+
+fn size<T>(tree: &Tree<T>) -> usize {
+    tree.as_ref().map_or(0, |n| n.size)
+}
+
+fn node<T>(left: Tree<T>, interval: Interval<T>, right: Tree<T>) -> Tree<T> {
+    let height = 1 + height(&left).max(height(&right));
+    let size = 1 + size(&left) + size(&right);
+    Some(Arc::new(Node { interval, height, size, left, right }))
+}
+
+/// Take apart a node, cloning its interval and sharing its children.
+fn expose<T: Clone>(tree: &Arc<Node<T>>) -> (Tree<T>, Interval<T>, Tree<T>) {
+    (tree.left.clone(), tree.interval.clone(), tree.right.clone())
+}
+
+fn rotate_left<T: Clone>(tree: Tree<T>) -> Tree<T> {
+    let (left, interval, right) = expose(tree.as_ref().expect("rotating an empty tree"));
+    let (right_left, right_interval, right_right) = expose(right.as_ref().expect("rotating left needs a right child"));
+    node(node(left, interval, right_left), right_interval, right_right)
+}
+
+fn rotate_right<T: Clone>(tree: Tree<T>) -> Tree<T> {
+    let (left, interval, right) = expose(tree.as_ref().expect("rotating an empty tree"));
+    let (left_left, left_interval, left_right) = expose(left.as_ref().expect("rotating right needs a left child"));
+    node(left_left, left_interval, node(left_right, interval, right))
+}
+
+/// Join `left`, which is taller than `right`, with `interval` and `right`, which all come after it.
+fn join_right<T: Clone>(left: &Arc<Node<T>>, interval: Interval<T>, right: Tree<T>) -> Tree<T> {
+    let (ll, lk, lr) = expose(left);
+    if height(&lr) <= height(&right) + 1 {
+        let joined = node(lr, interval, right);
+        if height(&joined) <= height(&ll) + 1 {
+            node(ll, lk, joined)
+        } else {
+            rotate_left(node(ll, lk, rotate_right(joined)))
+        }
+    } else {
+        let joined = join_right(lr.as_ref().unwrap(), interval, right);
+        let joined_height = height(&joined);
+        let tree = node(ll.clone(), lk, joined);
+        if joined_height <= height(&ll) + 1 { tree } else { rotate_left(tree) }
+    }
+}
+
+/// Join `left` with `interval` and `right`, which is taller than `left` and all comes after it.
+fn join_left<T: Clone>(left: Tree<T>, interval: Interval<T>, right: &Arc<Node<T>>) -> Tree<T> {
+    let (rl, rk, rr) = expose(right);
+    if height(&rl) <= height(&left) + 1 {
+        let joined = node(left, interval, rl);
+        if height(&joined) <= height(&rr) + 1 {
+            node(joined, rk, rr)
+        } else {
+            rotate_right(node(rotate_left(joined), rk, rr))
+        }
+    } else {
+        let joined = join_left(left, interval, rl.as_ref().unwrap());
+        let joined_height = height(&joined);
+        let tree = node(joined, rk, rr.clone());
+        if joined_height <= height(&rr) + 1 { tree } else { rotate_right(tree) }
+    }
+}
+
+/// A balanced tree of everything in `left`, then `interval`, then everything in `right`.
+fn join<T: Clone>(left: Tree<T>, interval: Interval<T>, right: Tree<T>) -> Tree<T> {
+    if height(&left) > height(&right) + 1 {
+        join_right(left.as_ref().unwrap(), interval, right)
+    } else if height(&right) > height(&left) + 1 {
+        join_left(left, interval, right.as_ref().unwrap())
+    } else {
+        node(left, interval, right)
+    }
+}
+
+/// Split `tree` into the intervals for which `in_left` is true and those after them. `in_left` must be
+/// true for every interval up to some point and false after it.
+fn split<T: Clone, F: Fn(&Interval<T>) -> bool>(tree: &Tree<T>, in_left: &F) -> (Tree<T>, Tree<T>) {
+    let Some(n) = tree else { return (None, None) };
+    let (left, interval, right) = expose(n);
+    if in_left(&interval) {
+        let (l, r) = split(&right, in_left);
+        (join(left, interval, l), r)
+    } else {
+        let (l, r) = split(&left, in_left);
+        (l, join(r, interval, right))
+    }
+}
+
+/// A balanced tree of everything in `left`, then everything in `right`.
+fn concat<T: Clone>(left: Tree<T>, right: Tree<T>) -> Tree<T> {
+    let Some(n) = &right else { return left };
+    let (rl, interval, rr) = expose(n);
+    join(concat(left, rl), interval, rr)
+}
+
+fn first<T>(tree: &Tree<T>) -> Option<&Interval<T>> {
+    let mut n = tree.as_ref()?;
+    while let Some(left) = &n.left {
+        n = left;
+    }
+    Some(&n.interval)
+}
+
+fn last<T>(tree: &Tree<T>) -> Option<&Interval<T>> {
+    let mut n = tree.as_ref()?;
+    while let Some(right) = &n.right {
+        n = right;
+    }
+    Some(&n.interval)
+}
+
+/// An immutable version of [`IntervalSet`]: [`insert`](PersistentIntervalSet::insert) and
+/// [`remove`](PersistentIntervalSet::remove) return a new set rather than changing this one. The new set
+/// shares all but `O(log n)` of its nodes with the old one, so keeping old versions is cheap, and cloning a
+/// set only copies a pointer. Sets can be shared between threads, letting readers hold a consistent
+/// snapshot while a writer builds newer versions.
+///
+/// As in an [`IntervalSet`], intervals that overlap or touch are merged, and empty intervals are never stored.
+///
+/// ```
+/// # use range_overlap::{Interval, PersistentIntervalSet};
+/// let monday = PersistentIntervalSet::new().insert(Interval::excl(9, 12)).insert(Interval::excl(13, 17));
+/// let with_lunch = monday.insert(Interval::excl(12, 13));
+/// assert_eq!(with_lunch.iter().collect::<Vec<_>>(), [&Interval::excl(9, 17)]);
+/// assert_eq!(monday.iter().collect::<Vec<_>>(), [&Interval::excl(9, 12), &Interval::excl(13, 17)]);
+/// ```
+#[derive(Debug)]
+pub struct PersistentIntervalSet<T> {
+    root: Tree<T>,
+}
+
+impl<T> Clone for PersistentIntervalSet<T> {
+    fn clone(&self) -> Self {
+        Self { root: self.root.clone() }
+    }
+}
+
+impl<T> Default for PersistentIntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PersistentIntervalSet<T> {
+    /// Create a set with no values in it.
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// Returns `true` if there are no values in this set.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// The number of disjoint intervals that make up this set.
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    /// Iterate over the intervals that make up this set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(&self.root);
+        iter
+    }
+}
+
+impl<T: PartialOrd + Clone> PersistentIntervalSet<T> {
+    /// A new set with the values in this one and in `interval`.
+    pub fn insert(&self, interval: Interval<T>) -> Self {
+        if interval.is_empty() {
+            return self.clone();
+        }
+
+        let (before, rest) = split(&self.root, &|iv| iv.allen_relation(&interval) == Some(AllenRelation::Before));
+        let (touching, after) = split(&rest, &|iv| iv.allen_relation(&interval) != Some(AllenRelation::After));
+        let mut merged = interval;
+        for iv in [first(&touching), last(&touching)].into_iter().flatten() {
+            merged = merged.hull(iv).expect("neither interval is empty");
+        }
+        Self { root: join(before, merged, after) }
+    }
+
+    /// A new set with the values in this one that are not in `interval`.
+    pub fn remove(&self, interval: &Interval<T>) -> Self {
+        if interval.is_empty() {
+            return self.clone();
+        }
+
+        let (before, rest) = split(&self.root, &|iv| matches!(iv.allen_relation(interval), Some(AllenRelation::Before | AllenRelation::Meets)));
+        let (overlapping, after) = split(&rest, &|iv| iv.has_overlap(interval));
+        // Only the first and last overlapping intervals can stick out past `interval`
+        let mut pieces = Vec::new();
+        if let Some(first) = first(&overlapping) {
+            pieces.extend(first.difference(interval).into_vec());
+            if size(&overlapping) > 1 {
+                pieces.extend(last(&overlapping).unwrap().difference(interval).into_vec());
+            }
+        }
+
+        let mut root = before;
+        for piece in pieces {
+            root = join(root, piece, None);
+        }
+        Self { root: concat(root, after) }
+    }
+
+    /// Returns `true` if `point` is in one of the intervals in this set.
+    pub fn contains_point(&self, point: &T) -> bool {
+        let point = Interval::point(point.clone());
+        let mut tree = &self.root;
+        while let Some(n) = tree {
+            tree = match n.interval.allen_relation(&point) {
+                Some(AllenRelation::Before | AllenRelation::Meets) => &n.right,
+                Some(AllenRelation::After | AllenRelation::MetBy) => &n.left,
+                Some(_) => return true,
+                None => return false,
+            };
+        }
+        false
+    }
+}
+
+/// An in-order walk of a tree, holding the nodes whose left subtrees have been visited but not themselves.
+struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left(&mut self, mut tree: &'a Tree<T>) {
+        while let Some(n) = tree {
+            self.stack.push(n);
+            tree = &n.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a Interval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.stack.pop()?;
+        self.push_left(&n.right);
+        Some(&n.interval)
+    }
+}
+
+impl<T: PartialOrd + Clone> From<&IntervalSet<T>> for PersistentIntervalSet<T> {
+    fn from(set: &IntervalSet<T>) -> Self {
+        let mut root = None;
+        for interval in set {
+            root = join(root, interval.clone(), None);
+        }
+        Self { root }
+    }
+}
+
+impl<T: PartialOrd + Clone> From<&PersistentIntervalSet<T>> for IntervalSet<T> {
+    fn from(set: &PersistentIntervalSet<T>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialOrd + Clone> FromIterator<Interval<T>> for PersistentIntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::from(&iter.into_iter().collect::<IntervalSet<T>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval};
+
+    /// Check the AVL balance and sizes of every node, returning the subtree's height
+    fn check_node(tree: &Tree<i32>) -> usize {
+        let Some(n) = tree else { return 0 };
+        let (left, right) = (check_node(&n.left), check_node(&n.right));
+        assert!(left.abs_diff(right) <= 1);
+        assert_eq!(n.height, 1 + left.max(right));
+        assert_eq!(n.size, 1 + size(&n.left) + size(&n.right));
+        n.height
+    }
+
+    #[test]
+    fn test_matches_interval_set() {
+        let mut rng = Lcg::new(3141);
+        let mut versions: Vec<(PersistentIntervalSet<i32>, IntervalSet<i32>)> = vec![(PersistentIntervalSet::new(), IntervalSet::new())];
+        for step in 0..2000 {
+            let interval = random_interval(&mut rng, 300, 12);
+            // Build on a recent version, so that several versions share nodes
+            let base = versions.len() - 1 - rng.below(versions.len().min(5) as u32) as usize;
+            let (persistent, mut expected) = versions[base].clone();
+            let updated = if rng.below(3) == 0 {
+                expected.remove(&interval);
+                persistent.remove(&interval)
+            } else {
+                expected.insert(interval);
+                persistent.insert(interval)
+            };
+
+            check_node(&updated.root);
+            assert_eq!(updated.len(), expected.len());
+            assert_eq!(updated.iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>(), "step {step}: {interval:?}");
+            for p in -2..=312 {
+                assert_eq!(updated.contains_point(&p), expected.contains_point(&p));
+            }
+            versions.push((updated, expected));
+        }
+
+        // Older versions are unchanged by later updates
+        for (persistent, expected) in &versions {
+            assert_eq!(&IntervalSet::from(persistent), expected);
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        let set: IntervalSet<i32> = (0..100).map(|i| Interval::excl(3 * i, 3 * i + 2)).collect();
+        let persistent = PersistentIntervalSet::from(&set);
+        check_node(&persistent.root);
+        assert_eq!(IntervalSet::from(&persistent), set);
+        assert_eq!(set.iter().cloned().collect::<PersistentIntervalSet<i32>>().len(), 100);
+    }
+}
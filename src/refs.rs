@@ -0,0 +1,135 @@
+//! Versions of the classification functions that borrow their end points.
+//!
+//! The functions at the root of the crate take end points by value. That is free for
+//! `Copy` types like integers, but for types such as `String` or large decimals it means
+//! cloning values that are only going to be compared. The functions here take references
+//! instead, so no ownership is needed. Every one of them is `#[inline]` and forwards to the by-value
+//! function with `&T` as the end point type, so for `Copy` primitives the compiler produces the same code
+//! either way; `benches/classify.rs` compares the two.
+use crate::{
+    classify_any, classify_any_nonempty, excl_classify, excl_classify_nonempty, has_excl_overlap, has_incl_overlap,
+    incl_classify, incl_classify_nonempty, open_range_contains_point, range_contains_point, RangeOverlap,
+};
+
+/// Like [`excl_classify`], but taking references to the end points.
+///
+/// ```
+/// # use range_overlap::{excl_classify_ref, RangeOverlap};
+/// let (a, b, c) = ("apple".to_string(), "mango".to_string(), "zucchini".to_string());
+/// assert_eq!(excl_classify_ref(&a, &b, &b, &c), RangeOverlap::None);
+/// ```
+#[inline]
+pub fn excl_classify_ref<T: PartialOrd + ?Sized>(a_start: &T, a_end: &T, b_start: &T, b_end: &T) -> RangeOverlap {
+    excl_classify(a_start, a_end, b_start, b_end)
+}
+
+/// Like [`incl_classify`], but taking references to the end points.
+#[inline]
+pub fn incl_classify_ref<T: PartialOrd + ?Sized>(a_start: &T, a_end: &T, b_start: &T, b_end: &T) -> RangeOverlap {
+    incl_classify(a_start, a_end, b_start, b_end)
+}
+
+/// Like [`classify_any`], but taking references to the end points. `Option<&T>` can be
+/// made from an `&Option<T>` with [`Option::as_ref`].
+#[inline]
+pub fn classify_any_ref<T: PartialOrd + ?Sized>(a_start: Option<&T>, a_end: Option<&T>, b_start: Option<&T>, b_end: Option<&T>, inclusive: bool) -> RangeOverlap {
+    classify_any(a_start, a_end, b_start, b_end, inclusive)
+}
+
+/// Like [`excl_classify_nonempty`], but taking references to the end points.
+#[inline]
+pub fn excl_classify_nonempty_ref<T: PartialOrd + ?Sized>(a_start: &T, a_end: &T, b_start: &T, b_end: &T) -> RangeOverlap {
+    excl_classify_nonempty(a_start, a_end, b_start, b_end)
+}
+
+/// Like [`incl_classify_nonempty`], but taking references to the end points.
+#[inline]
+pub fn incl_classify_nonempty_ref<T: PartialOrd + ?Sized>(a_start: &T, a_end: &T, b_start: &T, b_end: &T) -> RangeOverlap {
+    incl_classify_nonempty(a_start, a_end, b_start, b_end)
+}
+
+/// Like [`classify_any_nonempty`], but taking references to the end points.
+#[inline]
+pub fn classify_any_nonempty_ref<T: PartialOrd + ?Sized>(a_start: Option<&T>, a_end: Option<&T>, b_start: Option<&T>, b_end: Option<&T>, inclusive: bool) -> RangeOverlap {
+    classify_any_nonempty(a_start, a_end, b_start, b_end, inclusive)
+}
+
+/// Like [`range_contains_point`], but taking references to the end points and the point.
+///
+/// ```
+/// # use range_overlap::range_contains_point_ref;
+/// assert!(range_contains_point_ref("apple", "mango", "kiwi", false));
+/// ```
+#[inline]
+pub fn range_contains_point_ref<T: PartialOrd + ?Sized>(start: &T, end: &T, point: &T, inclusive: bool) -> bool {
+    range_contains_point(start, end, point, inclusive)
+}
+
+/// Like [`open_range_contains_point`], but taking references to the end points and the point.
+#[inline]
+pub fn open_range_contains_point_ref<T: PartialOrd + ?Sized>(start: Option<&T>, end: Option<&T>, point: &T, inclusive: bool) -> bool {
+    open_range_contains_point(start, end, point, inclusive)
+}
+
+/// Like [`has_excl_overlap`], but taking references to the end points.
+#[inline]
+pub fn has_excl_overlap_ref<T: PartialOrd + ?Sized>(a_start: &T, a_end: &T, b_start: &T, b_end: &T) -> bool {
+    has_excl_overlap(a_start, a_end, b_start, b_end)
+}
+
+/// Like [`has_incl_overlap`], but taking references to the end points.
+#[inline]
+pub fn has_incl_overlap_ref<T: PartialOrd + ?Sized>(a_start: &T, a_end: &T, b_start: &T, b_end: &T) -> bool {
+    has_incl_overlap(a_start, a_end, b_start, b_end)
+}
+
+/// Like [`has_open_excl_overlap`](crate::has_open_excl_overlap), but taking references to the end points.
+#[inline]
+pub fn has_open_excl_overlap_ref<T: PartialOrd + ?Sized>(a_start: Option<&T>, a_end: Option<&T>, b_start: Option<&T>, b_end: Option<&T>) -> bool {
+    classify_any_ref(a_start, a_end, b_start, b_end, false).has_overlap()
+}
+
+/// Like [`has_open_incl_overlap`](crate::has_open_incl_overlap), but taking references to the end points.
+#[inline]
+pub fn has_open_incl_overlap_ref<T: PartialOrd + ?Sized>(a_start: Option<&T>, a_end: Option<&T>, b_start: Option<&T>, b_end: Option<&T>) -> bool {
+    classify_any_ref(a_start, a_end, b_start, b_end, true).has_overlap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings() {
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(String::from);
+        assert_eq!(excl_classify_ref(&a, &c, &b, &d), RangeOverlap::AEndsInB);
+        assert_eq!(incl_classify_ref(&a, &b, &b, &d), RangeOverlap::AEndsInB);
+        assert_eq!(excl_classify_ref(&a, &b, &b, &d), RangeOverlap::None);
+        assert!(has_incl_overlap_ref(&a, &b, &b, &d));
+        assert!(!has_excl_overlap_ref(&a, &b, &b, &d));
+
+        // The end points are still usable afterwards
+        assert_eq!(a, "a");
+
+        let start = Some(b.clone());
+        assert_eq!(classify_any_ref(None, Some(&c), start.as_ref(), None, false), RangeOverlap::AEndsInB);
+        assert!(has_open_excl_overlap_ref(None, Some(&c), start.as_ref(), None));
+        assert!(!has_open_excl_overlap_ref(None, Some(&b), start.as_ref(), None));
+        assert!(has_open_incl_overlap_ref(None, Some(&b), start.as_ref(), None));
+
+        assert_eq!(excl_classify_nonempty_ref(&b, &b, &a, &d), RangeOverlap::None);
+        assert_eq!(incl_classify_nonempty_ref(&b, &b, &a, &d), RangeOverlap::AInsideB);
+        assert_eq!(classify_any_nonempty_ref(Some(&c), Some(&b), None, None, true), RangeOverlap::None);
+        assert!(range_contains_point_ref(&a, &c, &b, false));
+        assert!(!range_contains_point_ref(&a, &c, &c, false));
+        assert!(open_range_contains_point_ref(None, Some(&c), &c, true));
+        assert!(!open_range_contains_point_ref(start.as_ref(), None, &a, true));
+    }
+
+    #[test]
+    fn test_unsized() {
+        // `str` end points can be compared without allocating
+        assert_eq!(excl_classify_ref("a", "c", "b", "d"), RangeOverlap::AEndsInB);
+        assert_eq!(incl_classify_ref::<[i32]>(&[1, 2], &[1, 5], &[1, 3], &[2]), RangeOverlap::AEndsInB);
+    }
+}
@@ -0,0 +1,174 @@
+//! A map from non-overlapping segments to values, where overlapping insertions combine their values.
+use crate::{AllenRelation, Interval};
+
+/// A map from intervals to values, like [`IntervalMap`](crate::IntervalMap), but where inserting an
+/// interval that overlaps existing segments combines the values instead of choosing one. Existing
+/// segments are split where the new interval starts and ends, and the new value is folded into the value
+/// of each segment it covers, using a function given to [`SegmentMap::insert`]. This suits summing
+/// weights over overlapping intervals, keeping the highest priority, or collecting every value.
+///
+/// No two segments overlap, though they may touch. Segments are kept sorted, and empty intervals are
+/// never stored.
+///
+/// ```
+/// # use range_overlap::{Interval, SegmentMap};
+/// // Total load on a server from overlapping jobs
+/// let mut load = SegmentMap::new();
+/// load.insert(Interval::excl(0, 10), 2, |total, extra| *total += extra);
+/// load.insert(Interval::excl(5, 15), 3, |total, extra| *total += extra);
+/// let segments: Vec<_> = load.iter().collect();
+/// assert_eq!(segments, [(&Interval::excl(0, 5), &2), (&Interval::excl(5, 10), &5), (&Interval::excl(10, 15), &3)]);
+/// assert_eq!(load.get(&7), Some(&5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SegmentMap<T, V> {
+    segments: Vec<(Interval<T>, V)>,
+}
+
+impl<T, V> SegmentMap<T, V> {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self { segments: Vec::new() }
+    }
+
+    /// Returns `true` if there are no segments in this map.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The number of segments in this map.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Iterate over the segments in this map with their values, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Interval<T>, &V)> {
+        self.segments.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<T, V> Default for SegmentMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone, V> SegmentMap<T, V> {
+    /// The range of indices of segments that overlap `interval`.
+    fn overlapping_range(&self, interval: &Interval<T>) -> std::ops::Range<usize> {
+        let start = self.segments.partition_point(|(k, _)| matches!(k.allen_relation(interval), Some(AllenRelation::Before | AllenRelation::Meets)));
+        let end = start + self.segments[start..].partition_point(|(k, _)| k.has_overlap(interval));
+        start..end
+    }
+
+    /// The value of the segment containing `point`, if any.
+    pub fn get(&self, point: &T) -> Option<&V> {
+        let point = Interval::point(point.clone());
+        let range = self.overlapping_range(&point);
+        self.segments[range].first().map(|(_, v)| v)
+    }
+
+    /// The segments that overlap `interval`, in order.
+    pub fn get_overlapping(&self, interval: &Interval<T>) -> impl Iterator<Item = (&Interval<T>, &V)> {
+        let range = self.overlapping_range(interval);
+        self.segments[range].iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<T: PartialOrd + Clone, V: Clone> SegmentMap<T, V> {
+    /// Add `value` over `interval`. Parts of `interval` not already in the map become new segments with
+    /// `value`; for each part that overlaps an existing segment, `fold` is called with that segment's value
+    /// (split off from the rest of the segment if needed) and `value`, and updates it in place. Inserting
+    /// an empty interval does nothing.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, SegmentMap};
+    /// let mut owners = SegmentMap::new();
+    /// owners.insert(Interval::excl(0, 4), vec!["ann"], |names, new| names.extend_from_slice(new));
+    /// owners.insert(Interval::excl(2, 6), vec!["bo"], |names, new| names.extend_from_slice(new));
+    /// assert_eq!(owners.get(&3), Some(&vec!["ann", "bo"]));
+    /// assert_eq!(owners.get(&5), Some(&vec!["bo"]));
+    /// ```
+    pub fn insert<F: FnMut(&mut V, &V)>(&mut self, interval: Interval<T>, value: V, mut fold: F) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let range = self.overlapping_range(&interval);
+        let mut replacement = Vec::with_capacity(2 * range.len() + 1);
+        let mut remaining = Some(interval.clone());
+        for (key, old) in &self.segments[range.clone()] {
+            let (key_before, overlap, key_after) = key.split_by(&interval);
+            replacement.extend(key_before.map(|k| (k, old.clone())));
+            if let Some(current) = remaining.take() {
+                let (new_before, _, new_after) = current.split_by(key);
+                replacement.extend(new_before.map(|n| (n, value.clone())));
+                remaining = new_after;
+            }
+            if let Some(overlap) = overlap {
+                let mut combined = old.clone();
+                fold(&mut combined, &value);
+                replacement.push((overlap, combined));
+            }
+            replacement.extend(key_after.map(|k| (k, old.clone())));
+        }
+        replacement.extend(remaining.map(|r| (r, value)));
+        self.segments.splice(range, replacement);
+    }
+}
+
+impl<T, V> IntoIterator for SegmentMap<T, V> {
+    type Item = (Interval<T>, V);
+    type IntoIter = std::vec::IntoIter<(Interval<T>, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.segments.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, contains, sample_intervals, sample_points};
+
+    #[test]
+    fn test_folds_overlaps() {
+        let intervals = sample_intervals(5);
+        let points = sample_points(5);
+
+        // Collect the steps that covered each point, which also checks the order values are folded in
+        let mut map: SegmentMap<i32, Vec<usize>> = SegmentMap::new();
+        let mut expected: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+        let mut rng = Lcg::new(4321);
+        for step in 0..400 {
+            let interval = *rng.pick(&intervals);
+            map.insert(interval, vec![step], |steps, new| steps.extend_from_slice(new));
+            for (i, &p) in points.iter().enumerate() {
+                if contains(&interval, p) {
+                    expected[i].push(step);
+                }
+            }
+
+            for pair in map.segments.windows(2) {
+                assert!(!pair[0].0.has_overlap(&pair[1].0) && pair[0].0 < pair[1].0, "step {step}: {map:?}");
+            }
+            assert!(map.iter().all(|(k, _)| !k.is_empty()), "step {step}: {map:?}");
+            for (i, &p) in points.iter().enumerate() {
+                let found = map.iter().find(|(k, _)| contains(k, p)).map(|(_, v)| v.clone()).unwrap_or_default();
+                assert_eq!(found, expected[i], "step {step}: {map:?} at {p}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        let mut map = SegmentMap::new();
+        map.insert(Interval::incl(1, 5), 1, |a, b| *a = (*a).max(*b));
+        map.insert(Interval::excl(3, 8), 4, |a, b| *a = (*a).max(*b));
+        map.insert(Interval::excl(2, 4), 2, |a, b| *a = (*a).max(*b));
+        assert_eq!(map.get(&0), None);
+        assert_eq!(map.get(&2), Some(&2));
+        assert_eq!(map.get(&5), Some(&4));
+        assert_eq!(map.get_overlapping(&Interval::excl(0, 3)).map(|(_, v)| *v).collect::<Vec<_>>(), [1, 2]);
+    }
+}
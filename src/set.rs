@@ -0,0 +1,586 @@
+//! A collection of intervals kept sorted, disjoint, and coalesced.
+use std::cmp::Ordering;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+use crate::bounds;
+use crate::{AllenRelation, Interval, RangeOverlap};
+
+/// A set of values made up of intervals. The intervals are kept sorted, and any that overlap or
+/// [touch](Interval::is_adjacent_to) are merged, so each stored interval is separated from the
+/// next by a gap. Empty intervals are never stored.
+///
+/// ```
+/// # use range_overlap::{Interval, IntervalSet};
+/// let mut busy = IntervalSet::new();
+/// busy.insert(Interval::excl(9, 10));
+/// busy.insert(Interval::excl(13, 15));
+/// busy.insert(Interval::excl(10, 11));
+/// assert_eq!(busy.iter().collect::<Vec<_>>(), [&Interval::excl(9, 11), &Interval::excl(13, 15)]);
+///
+/// busy.remove(&Interval::excl(14, 20));
+/// assert!(busy.contains_point(&13));
+/// assert!(!busy.contains_point(&14));
+/// assert!(busy.contains_interval(&Interval::excl(9, 11)));
+/// assert!(!busy.contains_interval(&Interval::excl(9, 14)));
+/// ```
+///
+/// With the `smallvec` feature, up to four intervals are stored inline rather than on the heap, so the
+/// many small sets typical of per-record data (availability, opening hours) need no allocation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct IntervalSet<T> {
+    // Inline storage is still a list of intervals to anyone reading the schema
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<Interval<T>>"))]
+    intervals: Storage<T>,
+}
+
+#[cfg(not(feature = "smallvec"))]
+type Storage<T> = Vec<Interval<T>>;
+
+#[cfg(feature = "smallvec")]
+type Storage<T> = smallvec::SmallVec<[Interval<T>; 4]>;
+
+#[cfg(not(feature = "smallvec"))]
+type StorageIntoIter<T> = std::vec::IntoIter<Interval<T>>;
+
+#[cfg(feature = "smallvec")]
+type StorageIntoIter<T> = smallvec::IntoIter<[Interval<T>; 4]>;
+
+impl<T> IntervalSet<T> {
+    /// Create a set with no values in it.
+    pub fn new() -> Self {
+        Self { intervals: Storage::new() }
+    }
+
+    /// Returns `true` if there are no values in this set.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of disjoint intervals that make up this set.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Iterate over the intervals that make up this set, in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// The intervals that make up this set, in order.
+    pub fn as_slice(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone> IntervalSet<T> {
+    /// The index of the first stored interval that is not wholly before `interval`. If `touching` is
+    /// `false`, intervals that end where `interval` starts also count as before it.
+    fn first_not_before(&self, interval: &Interval<T>, touching: bool) -> usize {
+        self.intervals.partition_point(|iv| match iv.allen_relation(interval) {
+            Some(AllenRelation::Before) => true,
+            Some(AllenRelation::Meets) => !touching,
+            _ => false,
+        })
+    }
+
+    /// Add the values in `interval` to this set, merging it with any intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        self.absorb(interval, |_| {});
+    }
+
+    /// Add the values in `interval` to this set like [`IntervalSet::insert`], and return the intervals
+    /// already in the set that it was merged with, in order. These are replaced by the one merged interval,
+    /// even if one of them already contained `interval`. Inserting an empty interval merges nothing.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let mut holds: IntervalSet<_> = [Interval::excl(1, 2), Interval::excl(3, 4), Interval::excl(8, 9)].into_iter().collect();
+    /// let absorbed = holds.insert_merging(Interval::excl(0, 3));
+    /// assert_eq!(absorbed, [Interval::excl(1, 2), Interval::excl(3, 4)]);
+    /// assert_eq!(holds.as_slice(), [Interval::excl(0, 4), Interval::excl(8, 9)]);
+    /// ```
+    pub fn insert_merging(&mut self, interval: Interval<T>) -> Vec<Interval<T>> {
+        let mut absorbed = Vec::new();
+        self.absorb(interval, |iv| absorbed.push(iv));
+        absorbed
+    }
+
+    /// Insert `interval`, passing each stored interval it is merged with to `absorbed`, in order.
+    fn absorb<F: FnMut(Interval<T>)>(&mut self, interval: Interval<T>, mut absorbed: F) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let start = self.first_not_before(&interval, true);
+        let end = start + self.intervals[start..].partition_point(|iv| iv.allen_relation(&interval) != Some(AllenRelation::After));
+        let merged = self.intervals[start..end].iter().fold(interval, |merged, iv| merged.hull(iv).expect("neither interval is empty"));
+        if start == end {
+            self.intervals.insert(start, merged);
+            return;
+        }
+        // Reuse the first merged interval's slot, so the rest of the set only shifts once
+        absorbed(std::mem::replace(&mut self.intervals[start], merged));
+        self.intervals.drain(start + 1..end).for_each(absorbed);
+    }
+
+    /// Remove the values in `interval` from this set, trimming or splitting any intervals it overlaps.
+    pub fn remove(&mut self, interval: &Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let start = self.first_not_before(interval, false);
+        let end = start + self.intervals[start..].partition_point(|iv| iv.has_overlap(interval));
+        if start == end {
+            return;
+        }
+        // Only the first and last overlapping intervals can stick out past `interval`
+        let (before, _, _) = self.intervals[start].split_by(interval);
+        let (_, _, after) = self.intervals[end - 1].split_by(interval);
+        self.intervals.drain(start..end);
+        for piece in [after, before].into_iter().flatten() {
+            self.intervals.insert(start, piece);
+        }
+    }
+
+    /// Returns `true` if `point` is in one of the intervals in this set.
+    pub fn contains_point(&self, point: &T) -> bool {
+        self.stab(point).is_some()
+    }
+
+    /// The interval in this set that contains `point`, if any. The intervals in a set are disjoint, so
+    /// at most one can.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let set: IntervalSet<_> = [Interval::excl(1, 3), Interval::incl(5, 8)].into_iter().collect();
+    /// assert_eq!(set.stab(&8), Some(&Interval::incl(5, 8)));
+    /// assert_eq!(set.stab(&3), None);
+    /// ```
+    pub fn stab(&self, point: &T) -> Option<&Interval<T>> {
+        let point = Interval::point(point.clone());
+        self.intervals.get(self.first_not_before(&point, false)).filter(|iv| iv.has_overlap(&point))
+    }
+
+    /// Returns `true` if every value in `interval` is in this set. Since touching intervals are merged,
+    /// this means one interval in the set must contain all of it. An empty interval is always contained.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        if interval.is_empty() {
+            return true;
+        }
+
+        self.intervals.get(self.first_not_before(interval, false))
+            .is_some_and(|iv| matches!(iv.classify_against(interval), RangeOverlap::AContainsB | RangeOverlap::AEqualsB))
+    }
+
+    /// Returns `true` if any value in `interval` is in this set.
+    pub fn has_overlap(&self, interval: &Interval<T>) -> bool {
+        self.intervals.get(self.first_not_before(interval, false)).is_some_and(|iv| iv.has_overlap(interval))
+    }
+
+    /// The smallest interval containing every value in this set, or `None` if it is empty.
+    pub fn span(&self) -> Option<Interval<T>> {
+        let first = self.intervals.first()?;
+        let last = self.intervals.last()?;
+        Some(Interval::from_bounds(first.start().cloned(), last.end().cloned()))
+    }
+}
+
+impl<T: PartialOrd + Clone> IntervalSet<T> {
+    /// Build a set from non-empty intervals already sorted by start, merging any that overlap or touch.
+    fn from_sorted<I: IntoIterator<Item = Interval<T>>>(sorted: I) -> Self {
+        let mut intervals = Storage::new();
+        for interval in sorted {
+            match intervals.last().and_then(|last| last.merge_if_adjacent(&interval)) {
+                Some(merged) => *intervals.last_mut().unwrap() = merged,
+                None => intervals.push(interval),
+            }
+        }
+        Self { intervals }
+    }
+
+    /// The values in either this set or `other`.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let a: IntervalSet<_> = [Interval::excl(0, 2), Interval::excl(5, 7)].into_iter().collect();
+    /// let b: IntervalSet<_> = [Interval::excl(1, 3), Interval::excl(8, 9)].into_iter().collect();
+    /// assert_eq!(a.union(&b).as_slice(), [Interval::excl(0, 3), Interval::excl(5, 7), Interval::excl(8, 9)]);
+    /// assert_eq!((&a | &b), a.union(&b));
+    /// ```
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        let sorted = std::iter::from_fn(|| match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if bounds::cmp_starts(x.start(), y.start()) == Some(Ordering::Greater) => b.next(),
+            (Some(_), _) => a.next(),
+            (None, _) => b.next(),
+        });
+        Self::from_sorted(sorted.cloned())
+    }
+
+    /// The values in both this set and `other`.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let a: IntervalSet<_> = [Interval::excl(0, 2), Interval::excl(5, 7)].into_iter().collect();
+    /// let b = IntervalSet::from(Interval::excl(1, 6));
+    /// assert_eq!(a.intersection(&b).as_slice(), [Interval::excl(1, 2), Interval::excl(5, 6)]);
+    /// assert_eq!((&a & &b), a.intersection(&b));
+    /// ```
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut pieces = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            pieces.extend(a.intersect(b));
+            // Whichever interval ends first cannot overlap anything further in the other set
+            match bounds::cmp_ends(a.end(), b.end()) {
+                Some(Ordering::Less) => i += 1,
+                Some(Ordering::Greater) => j += 1,
+                _ => {
+                    i += 1;
+                    j += 1;
+                },
+            }
+        }
+        Self::from_sorted(pieces)
+    }
+
+    /// The values in this set that are not in `other`.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let day = IntervalSet::from(Interval::excl(9, 17));
+    /// let busy: IntervalSet<_> = [Interval::excl(10, 11), Interval::excl(13, 14)].into_iter().collect();
+    /// assert_eq!(day.difference(&busy).as_slice(), [Interval::excl(9, 10), Interval::excl(11, 13), Interval::excl(14, 17)]);
+    /// assert_eq!((&day - &busy), day.difference(&busy));
+    /// ```
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut pieces = Vec::new();
+        let mut first = 0;
+        for interval in &self.intervals {
+            // Skip removals wholly before this interval; they are before every later one too
+            while other.intervals.get(first).is_some_and(|r| matches!(r.allen_relation(interval), Some(AllenRelation::Before | AllenRelation::Meets))) {
+                first += 1;
+            }
+
+            let mut remaining = Some(interval.clone());
+            for removal in other.intervals[first..].iter() {
+                let Some(current) = remaining.take() else { break };
+                if !current.has_overlap(removal) {
+                    remaining = Some(current);
+                    break;
+                }
+                // Removals are in order, so anything left before this one is finished with
+                let (before, _, after) = current.split_by(removal);
+                pieces.extend(before);
+                remaining = after;
+            }
+            pieces.extend(remaining);
+        }
+        Self::from_sorted(pieces)
+    }
+
+    /// The values in exactly one of this set and `other`.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let a = IntervalSet::from(Interval::excl(0, 5));
+    /// let b = IntervalSet::from(Interval::excl(3, 9));
+    /// assert_eq!(a.symmetric_difference(&b).as_slice(), [Interval::excl(0, 3), Interval::excl(5, 9)]);
+    /// assert_eq!((&a ^ &b), a.symmetric_difference(&b));
+    /// ```
+    pub fn symmetric_difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// The values not in this set, limited to `domain` if one is given. Within a working day, for example,
+    /// the complement of the busy times is the free times.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let busy: IntervalSet<_> = [Interval::excl(10, 11), Interval::excl(13, 14)].into_iter().collect();
+    /// let free = busy.complement(Some(&Interval::excl(9, 17)));
+    /// assert_eq!(free.as_slice(), [Interval::excl(9, 10), Interval::excl(11, 13), Interval::excl(14, 17)]);
+    /// assert_eq!(busy.complement(None).as_slice(), [Interval::less_than(10), Interval::excl(11, 13), Interval::at_least(14)]);
+    /// ```
+    pub fn complement(&self, domain: Option<&Interval<T>>) -> IntervalSet<T> {
+        let domain = domain.cloned().unwrap_or_else(Interval::all);
+        IntervalSet::from(domain).difference(self)
+    }
+
+    /// The values in this set that are not in `interval`, as a new set.
+    pub fn subtract_interval(&self, interval: &Interval<T>) -> IntervalSet<T> {
+        Self::from_sorted(self.iter().flat_map(|iv| iv.difference(interval).into_vec()))
+    }
+}
+
+impl<T: PartialOrd + Clone> Interval<T> {
+    /// The values in this interval that are not in `other`, as an [`IntervalSet`]. This holds the same
+    /// intervals as [`Interval::difference`], but in a form that can be combined with other sets.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalSet};
+    /// let free = Interval::excl(9, 17).subtract(&Interval::excl(12, 13));
+    /// assert_eq!(free.as_slice(), [Interval::excl(9, 12), Interval::excl(13, 17)]);
+    /// assert_eq!(free.subtract_interval(&Interval::excl(16, 17)).as_slice(), [Interval::excl(9, 12), Interval::excl(13, 16)]);
+    /// ```
+    pub fn subtract(&self, other: &Interval<T>) -> IntervalSet<T> {
+        IntervalSet::from_sorted(self.difference(other).into_vec())
+    }
+}
+
+macro_rules! impl_set_op {
+    ($($op:ident, $method:ident => $set_method:ident);*) => {
+        $(
+            impl<T: PartialOrd + Clone> $op<&IntervalSet<T>> for &IntervalSet<T> {
+                type Output = IntervalSet<T>;
+
+                fn $method(self, rhs: &IntervalSet<T>) -> Self::Output {
+                    self.$set_method(rhs)
+                }
+            }
+
+            impl<T: PartialOrd + Clone> $op for IntervalSet<T> {
+                type Output = IntervalSet<T>;
+
+                fn $method(self, rhs: IntervalSet<T>) -> Self::Output {
+                    self.$set_method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_set_op!(
+    BitOr, bitor => union;
+    BitAnd, bitand => intersection;
+    Sub, sub => difference;
+    BitXor, bitxor => symmetric_difference
+);
+
+impl<T: PartialOrd + Clone> From<Interval<T>> for IntervalSet<T> {
+    fn from(value: Interval<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(value);
+        set
+    }
+}
+
+impl<T: PartialOrd + Clone> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PartialOrd + Clone> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T> IntoIterator for IntervalSet<T> {
+    type Item = Interval<T>;
+    type IntoIter = StorageIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, contains, sample_intervals, sample_points};
+    use std::ops::Bound;
+
+    fn check_invariants(set: &IntervalSet<i32>) {
+        for iv in set.iter() {
+            assert!(!iv.is_empty(), "{set:?}");
+        }
+        for pair in set.as_slice().windows(2) {
+            assert_eq!(pair[0].allen_relation(&pair[1]), Some(AllenRelation::Before), "{set:?}");
+        }
+        assert!(set.iter().skip(1).all(|iv| iv.start() != Bound::Unbounded), "{set:?}");
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let intervals = sample_intervals(6);
+        let points = sample_points(6);
+
+        // Apply a long, deterministic but scrambled sequence of inserts and removes, checking the set
+        // against which sample points should be in it after each one
+        let mut set = IntervalSet::new();
+        let mut expected = vec![false; points.len()];
+        let mut rng = Lcg::new(12345);
+        for step in 0..3000 {
+            let interval = rng.pick(&intervals);
+            // Remove less often than insert, so the set does not stay nearly empty
+            let insert = step % 3 != 0;
+            if insert {
+                let before = set.clone();
+                let absorbed = set.insert_merging(*interval);
+                let touching: Vec<_> = before.iter().filter(|iv| !interval.is_empty() && !matches!(iv.allen_relation(interval), Some(AllenRelation::Before | AllenRelation::After))).copied().collect();
+                assert_eq!(absorbed, touching, "step {step}: inserting {interval:?} into {before:?}");
+            } else {
+                set.remove(interval);
+            }
+            for (i, &p) in points.iter().enumerate() {
+                if contains(interval, p) {
+                    expected[i] = insert;
+                }
+            }
+
+            check_invariants(&set);
+            for (i, &p) in points.iter().enumerate() {
+                assert_eq!(set.iter().any(|iv| contains(iv, p)), expected[i], "step {step}: {set:?} at {p}");
+            }
+            for p in -1..7 {
+                assert_eq!(set.contains_point(&p), set.iter().any(|iv| iv.contains_point(&p)), "step {step}: {set:?} at {p}");
+            }
+        }
+    }
+
+    /// A handful of sets to combine, from the sample intervals
+    fn sample_sets() -> Vec<IntervalSet<i32>> {
+        let intervals = sample_intervals(6);
+        let mut sets = vec![IntervalSet::new(), IntervalSet::from(Interval::all())];
+        let mut rng = Lcg::new(6789);
+        for size in 1..40 {
+            let mut set = IntervalSet::new();
+            for _ in 0..(size % 5 + 1) {
+                set.insert(*rng.pick(&intervals));
+            }
+            sets.push(set);
+        }
+        sets
+    }
+
+    fn set_contains(set: &IntervalSet<i32>, point: f64) -> bool {
+        set.iter().any(|iv| contains(iv, point))
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let sets = sample_sets();
+        for a in &sets {
+            for b in &sets {
+                let union = a | b;
+                let intersection = a & b;
+                let difference = a - b;
+                let symmetric_difference = a ^ b;
+                for set in [&union, &intersection, &difference, &symmetric_difference] {
+                    check_invariants(set);
+                }
+
+                for p in sample_points(6) {
+                    let (in_a, in_b) = (set_contains(a, p), set_contains(b, p));
+                    assert_eq!(set_contains(&union, p), in_a || in_b, "{a:?} | {b:?} at {p}");
+                    assert_eq!(set_contains(&intersection, p), in_a && in_b, "{a:?} & {b:?} at {p}");
+                    assert_eq!(set_contains(&difference, p), in_a && !in_b, "{a:?} - {b:?} at {p}");
+                    assert_eq!(set_contains(&symmetric_difference, p), in_a != in_b, "{a:?} ^ {b:?} at {p}");
+                }
+
+                // Owned operands give the same results
+                assert_eq!(a.clone() | b.clone(), union);
+                assert_eq!(a.clone() - b.clone(), difference);
+            }
+        }
+    }
+
+    #[test]
+    fn test_complement() {
+        let domains = [None, Some(Interval::excl(1, 4)), Some(Interval::at_most(2)), Some(Interval::excl(3, 3))];
+        for set in sample_sets() {
+            for domain in &domains {
+                let complement = set.complement(domain.as_ref());
+                check_invariants(&complement);
+                for p in sample_points(6) {
+                    let in_domain = domain.as_ref().is_none_or(|d| contains(d, p));
+                    assert_eq!(set_contains(&complement, p), in_domain && !set_contains(&set, p), "{set:?} in {domain:?} at {p}");
+                }
+            }
+            assert_eq!(set.complement(None).complement(None), set);
+        }
+    }
+
+    #[test]
+    fn test_subtract() {
+        let intervals = sample_intervals(6);
+        for a in &intervals {
+            for b in &intervals {
+                let subtracted = a.subtract(b);
+                check_invariants(&subtracted);
+                assert_eq!(subtracted, IntervalSet::from(*a).subtract_interval(b), "{a:?} - {b:?}");
+                assert_eq!(subtracted.as_slice(), a.difference(b).into_vec(), "{a:?} - {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_contains_interval() {
+        let set: IntervalSet<i32> = [Interval::excl(0, 2), Interval::incl(3, 4)].into_iter().collect();
+        for interval in sample_intervals(6) {
+            let expected = sample_points(6).into_iter().all(|p| !contains(&interval, p) || set.iter().any(|iv| contains(iv, p)));
+            assert_eq!(set.contains_interval(&interval), expected, "{interval:?}");
+
+            let overlaps = sample_points(6).into_iter().any(|p| contains(&interval, p) && set.iter().any(|iv| contains(iv, p)));
+            assert_eq!(set.has_overlap(&interval), overlaps, "{interval:?}");
+        }
+    }
+
+    #[test]
+    fn test_coalescing() {
+        let set: IntervalSet<i32> = [Interval::excl(3, 5), Interval::excl(0, 1), Interval::excl(1, 3), Interval::incl(7, 8)].into_iter().collect();
+        assert_eq!(set.as_slice(), [Interval::excl(0, 5), Interval::incl(7, 8)]);
+        assert_eq!(set.span(), Some(Interval::incl(0, 8)));
+
+        let set: IntervalSet<i32> = [Interval::excl(5, 5), Interval::excl(3, 1)].into_iter().collect();
+        assert!(set.is_empty());
+        assert_eq!(set.span(), None);
+
+        let mut set = IntervalSet::from(Interval::all());
+        set.remove(&Interval::excl(1, 2));
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), [Interval::less_than(1), Interval::at_least(2)]);
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn test_small_sets_inline() {
+        let mut set = IntervalSet::new();
+        for start in [0, 10, 20, 30] {
+            set.insert(Interval::excl(start, start + 5));
+        }
+        set.insert(Interval::excl(2, 12));
+        set.remove(&Interval::excl(3, 11));
+        assert_eq!(set.len(), 4);
+        assert!(!set.intervals.spilled());
+        // Consuming an inline set iterates the inline storage, rather than moving it to a Vec first
+        let inline: smallvec::IntoIter<[Interval<i32>; 4]> = set.clone().into_iter();
+        assert_eq!(inline.collect::<Vec<_>>(), set.iter().copied().collect::<Vec<_>>());
+
+        set.insert(Interval::excl(40, 45));
+        assert!(set.intervals.spilled());
+        assert_eq!(set.into_iter().count(), 5);
+    }
+}
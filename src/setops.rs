@@ -0,0 +1,652 @@
+//! Set operations on ranges: the values two ranges have in common, the values in either, and so on.
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::{AllenRelation, Interval, RangeOverlap};
+
+/// The result of [`Interval::union`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum IntervalUnion<T> {
+    /// The two intervals overlap or touch, so their union is this single interval.
+    Merged(Interval<T>),
+
+    /// The two intervals have a gap between them, so their union is both of them, in the order given.
+    Disjoint(Interval<T>, Interval<T>),
+}
+
+/// Zero, one, or two intervals, as returned by [`Interval::difference`]. When there are two, they are
+/// in ascending order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum IntervalPieces<T> {
+    /// No values are left.
+    Empty,
+
+    /// The values left form a single interval.
+    One(Interval<T>),
+
+    /// The values left form two intervals, with a gap between them.
+    Two(Interval<T>, Interval<T>),
+}
+
+impl<T> IntervalPieces<T> {
+    fn from_options(first: Option<Interval<T>>, second: Option<Interval<T>>) -> Self {
+        match (first, second) {
+            (Some(a), Some(b)) => Self::Two(a, b),
+            (Some(a), None) | (None, Some(a)) => Self::One(a),
+            (None, None) => Self::Empty,
+        }
+    }
+
+    /// The intervals, in ascending order.
+    pub fn into_vec(self) -> Vec<Interval<T>> {
+        match self {
+            Self::Empty => vec![],
+            Self::One(a) => vec![a],
+            Self::Two(a, b) => vec![a, b],
+        }
+    }
+}
+
+/// One part of an interval that has been split, which may have no values.
+type Piece<T> = Option<Interval<T>>;
+
+/// Turn a start bound into the end bound of the values before it, or an end bound into the start
+/// bound of the values after it.
+fn flip<T>(bound: Bound<T>) -> Bound<T> {
+    match bound {
+        Bound::Included(v) => Bound::Excluded(v),
+        Bound::Excluded(v) => Bound::Included(v),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+impl<T: PartialOrd + Clone> Interval<T> {
+    /// The values before this interval starts, or `None` if it has an open start.
+    fn before(&self) -> Option<Interval<T>> {
+        match self.start() {
+            Bound::Unbounded => None,
+            start => Some(Interval::from_bounds(Bound::Unbounded, flip(start.cloned()))),
+        }
+    }
+
+    /// The values after this interval ends, or `None` if it has an open end.
+    fn after(&self) -> Option<Interval<T>> {
+        match self.end() {
+            Bound::Unbounded => None,
+            end => Some(Interval::from_bounds(flip(end.cloned()), Bound::Unbounded)),
+        }
+    }
+
+    /// The interval of values in both this interval and `other`, or `None` if they have no values
+    /// in common. Each end of the result takes its bound, including whether it is included, from
+    /// whichever interval is tighter at that end.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::incl(1, 5).intersect(&Interval::excl(3, 9)), Some(Interval::incl(3, 5)));
+    /// assert_eq!(Interval::at_least(3).intersect(&Interval::less_than(9)), Some(Interval::excl(3, 9)));
+    /// assert_eq!(Interval::excl(1, 5).intersect(&Interval::excl(5, 9)), None);
+    /// ```
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if !self.has_overlap(other) {
+            return None;
+        }
+
+        // Overlapping intervals' end points can all be compared, so neither of these will fail
+        let start = bounds::later_start(self.start(), other.start())?;
+        let end = bounds::earlier_end(self.end(), other.end())?;
+        Some(Interval::from_bounds(start.cloned(), end.cloned()))
+    }
+
+    /// Trim this interval so it lies entirely within `bounds`, or `None` if none of it does. This is the
+    /// same as [`Interval::intersect`], named for when one interval is a limit on the other.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// let retention = Interval::at_least(30);
+    /// assert_eq!(Interval::excl(10, 45).clamp_to(&retention), Some(Interval::excl(30, 45)));
+    /// assert_eq!(Interval::less_than(45).clamp_to(&retention), Some(Interval::excl(30, 45)));
+    /// assert_eq!(Interval::excl(10, 20).clamp_to(&retention), None);
+    /// ```
+    pub fn clamp_to(&self, bounds: &Interval<T>) -> Option<Interval<T>> {
+        self.intersect(bounds)
+    }
+
+    /// The values in either this interval or `other`. If they overlap or [touch](Interval::is_adjacent_to)
+    /// with no gap between them, this is a single interval; otherwise, it is both intervals. An empty
+    /// interval adds nothing to the union, so if either interval is empty the result is the other one.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalUnion};
+    /// assert_eq!(Interval::excl(1, 5).union(&Interval::excl(3, 9)), IntervalUnion::Merged(Interval::excl(1, 9)));
+    /// assert_eq!(Interval::excl(1, 5).union(&Interval::at_least(5)), IntervalUnion::Merged(Interval::at_least(1)));
+    /// assert_eq!(
+    ///     Interval::excl(1, 5).union(&Interval::excl(6, 9)),
+    ///     IntervalUnion::Disjoint(Interval::excl(1, 5), Interval::excl(6, 9))
+    /// );
+    /// ```
+    pub fn union(&self, other: &Interval<T>) -> IntervalUnion<T> {
+        if other.is_empty() {
+            return IntervalUnion::Merged(self.clone());
+        } else if self.is_empty() {
+            return IntervalUnion::Merged(other.clone());
+        }
+
+        match self.allen_relation(other) {
+            Some(AllenRelation::Before | AllenRelation::After) | None => IntervalUnion::Disjoint(self.clone(), other.clone()),
+            Some(_) => IntervalUnion::Merged(self.hull(other).expect("end points are comparable")),
+        }
+    }
+
+    /// The smallest interval containing both this interval and `other`, including any gap between them.
+    /// An open side of either interval stays open. Since an empty interval has no values to contain, if
+    /// either interval is empty the result is the other one. Returns `None` if the end points cannot be
+    /// compared.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(1, 3).hull(&Interval::incl(7, 9)), Some(Interval::incl(1, 9)));
+    /// assert_eq!(Interval::excl(1, 3).hull(&Interval::at_least(7)), Some(Interval::at_least(1)));
+    /// assert_eq!(Interval::excl(1, 3).hull(&Interval::excl(5, 5)), Some(Interval::excl(1, 3)));
+    /// ```
+    pub fn hull(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if other.is_empty() {
+            return Some(self.clone());
+        } else if self.is_empty() {
+            return Some(other.clone());
+        }
+
+        let start = bounds::earlier_start(self.start(), other.start())?;
+        let end = bounds::later_end(self.end(), other.end())?;
+        Some(Interval::from_bounds(start.cloned(), end.cloned()))
+    }
+
+    /// The smallest interval containing this interval and `other` if they [overlap](Interval::has_overlap),
+    /// otherwise `None`. Use this when coalescing intervals that must share a value to be combined.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(1, 5).merge_if_overlapping(&Interval::excl(3, 9)), Some(Interval::excl(1, 9)));
+    /// assert_eq!(Interval::excl(1, 5).merge_if_overlapping(&Interval::excl(5, 9)), None);
+    /// ```
+    pub fn merge_if_overlapping(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.has_overlap(other) { self.hull(other) } else { None }
+    }
+
+    /// The smallest interval containing this interval and `other` if they overlap or [touch](Interval::is_adjacent_to),
+    /// otherwise `None`. Unlike [`Interval::union`], an empty interval is never merged with anything.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(1, 5).merge_if_adjacent(&Interval::excl(5, 9)), Some(Interval::excl(1, 9)));
+    /// assert_eq!(Interval::excl(1, 5).merge_if_adjacent(&Interval::excl(6, 9)), None);
+    /// ```
+    pub fn merge_if_adjacent(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.has_overlap(other) || self.is_adjacent_to(other) { self.hull(other) } else { None }
+    }
+
+    /// The interval of values strictly between this interval and `other`, or `None` if they overlap,
+    /// touch, or either is empty. Whether each end of the gap is included is the opposite of the end it
+    /// borders, so that the gap and the two intervals together cover everything from one to the other.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use std::ops::Bound;
+    ///
+    /// assert_eq!(Interval::excl(1, 5).gap(&Interval::excl(7, 9)), Some(Interval::excl(5, 7)));
+    /// assert_eq!(
+    ///     Interval::incl(7, 9).gap(&Interval::incl(1, 5)),
+    ///     Some(Interval::from_bounds(Bound::Excluded(5), Bound::Excluded(7)))
+    /// );
+    /// assert_eq!(Interval::excl(1, 5).gap(&Interval::excl(5, 9)), None);
+    /// ```
+    pub fn gap(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let (first, second) = match self.allen_relation(other)? {
+            AllenRelation::Before => (self, other),
+            AllenRelation::After => (other, self),
+            _ => return None,
+        };
+        Some(Interval::from_bounds(flip(first.end().cloned()), flip(second.start().cloned())))
+    }
+
+    /// The values in this interval that are not in `other`. Removing the middle of an interval leaves
+    /// two pieces, one on either side.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalPieces};
+    /// let day = Interval::excl(9, 17);
+    /// assert_eq!(day.difference(&Interval::excl(12, 13)), IntervalPieces::Two(Interval::excl(9, 12), Interval::excl(13, 17)));
+    /// assert_eq!(day.difference(&Interval::excl(15, 20)), IntervalPieces::One(Interval::excl(9, 15)));
+    /// assert_eq!(day.difference(&Interval::all()), IntervalPieces::Empty);
+    /// ```
+    pub fn difference(&self, other: &Interval<T>) -> IntervalPieces<T> {
+        if self.is_empty() {
+            return IntervalPieces::Empty;
+        } else if other.is_empty() {
+            return IntervalPieces::One(self.clone());
+        }
+
+        let before = other.before().and_then(|b| self.intersect(&b));
+        let after = other.after().and_then(|a| self.intersect(&a));
+        IntervalPieces::from_options(before, after)
+    }
+
+    /// The values in exactly one of this interval and `other`. This is at most two intervals; where
+    /// the two inputs only [touch](Interval::is_adjacent_to), it is their union.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, IntervalPieces};
+    /// assert_eq!(
+    ///     Interval::excl(1, 5).symmetric_difference(&Interval::excl(3, 9)),
+    ///     IntervalPieces::Two(Interval::excl(1, 3), Interval::excl(5, 9))
+    /// );
+    /// assert_eq!(Interval::excl(1, 5).symmetric_difference(&Interval::excl(1, 9)), IntervalPieces::One(Interval::excl(5, 9)));
+    /// assert_eq!(Interval::excl(1, 5).symmetric_difference(&Interval::excl(5, 9)), IntervalPieces::One(Interval::excl(1, 9)));
+    /// ```
+    pub fn symmetric_difference(&self, other: &Interval<T>) -> IntervalPieces<T> {
+        let mut pieces = self.difference(other).into_vec();
+        pieces.extend(other.difference(self).into_vec());
+        // None of the pieces share values, so they can always be ordered
+        pieces.sort_by(|a, b| a.partial_cmp(b).expect("end points are comparable"));
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            match merged.last().map(|last| last.union(&piece)) {
+                Some(IntervalUnion::Merged(union)) => *merged.last_mut().unwrap() = union,
+                _ => merged.push(piece),
+            }
+        }
+
+        let mut merged = merged.into_iter();
+        IntervalPieces::from_options(merged.next(), merged.next())
+    }
+
+    /// Split this interval into the values before `point` and the values from `point` on, each `None` if
+    /// there are no such values. As with the crate's closed ranges, `point` itself goes in the second half.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::incl(1, 9).split_at(5), (Some(Interval::excl(1, 5)), Some(Interval::incl(5, 9))));
+    /// assert_eq!(Interval::incl(1, 9).split_at(12), (Some(Interval::incl(1, 9)), None));
+    /// assert_eq!(Interval::excl(1, 9).split_at(1), (None, Some(Interval::excl(1, 9))));
+    /// ```
+    pub fn split_at(&self, point: T) -> (Option<Interval<T>>, Option<Interval<T>>) {
+        (self.intersect(&Interval::less_than(point.clone())), self.intersect(&Interval::at_least(point)))
+    }
+
+    /// Split this interval into the part before `other`, the part overlapping `other`, and the part after
+    /// `other`, each `None` if there are no such values. If `other` is empty, nothing overlaps it and all
+    /// of this interval is returned as the first part.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// let day = Interval::excl(9, 17);
+    /// assert_eq!(
+    ///     day.split_by(&Interval::excl(12, 13)),
+    ///     (Some(Interval::excl(9, 12)), Some(Interval::excl(12, 13)), Some(Interval::excl(13, 17)))
+    /// );
+    /// assert_eq!(day.split_by(&Interval::at_least(15)), (Some(Interval::excl(9, 15)), Some(Interval::excl(15, 17)), None));
+    /// ```
+    pub fn split_by(&self, other: &Interval<T>) -> (Piece<T>, Piece<T>, Piece<T>) {
+        if other.is_empty() {
+            return ((!self.is_empty()).then(|| self.clone()), None, None);
+        }
+
+        let before = other.before().and_then(|b| self.intersect(&b));
+        let after = other.after().and_then(|a| self.intersect(&a));
+        (before, self.intersect(other), after)
+    }
+}
+
+impl RangeOverlap {
+    /// Given that this is how `a` overlaps `b`, the smallest interval containing both, or `None` if
+    /// they do not overlap. This only picks which end points to keep, so it is cheaper than
+    /// [`Interval::merge_if_overlapping`] when the classification is already known, but gives
+    /// a meaningless result if this was not the classification of `a` against `b`.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// let (a, b) = (Interval::excl(1, 5), Interval::excl(3, 9));
+    /// let overlap = a.classify_against(&b);
+    /// assert_eq!(overlap.merge(&a, &b), Some(Interval::excl(1, 9)));
+    /// assert_eq!(overlap.intersect(&a, &b), Some(Interval::excl(3, 5)));
+    /// ```
+    pub fn merge<T: Clone>(&self, a: &Interval<T>, b: &Interval<T>) -> Option<Interval<T>> {
+        match self {
+            RangeOverlap::AContainsB | RangeOverlap::AEqualsB => Some(a.clone()),
+            RangeOverlap::AInsideB => Some(b.clone()),
+            RangeOverlap::AEndsInB => Some(Interval::from_bounds(a.start().cloned(), b.end().cloned())),
+            RangeOverlap::AStartsInB => Some(Interval::from_bounds(b.start().cloned(), a.end().cloned())),
+            RangeOverlap::None => None,
+        }
+    }
+
+    /// Given that this is how `a` overlaps `b`, the interval of values in both, or `None` if they do
+    /// not overlap. Like [`RangeOverlap::merge`], this relies on the classification rather than
+    /// comparing end points again, so it must be the classification of `a` against `b`.
+    pub fn intersect<T: Clone>(&self, a: &Interval<T>, b: &Interval<T>) -> Option<Interval<T>> {
+        match self {
+            RangeOverlap::AContainsB => Some(b.clone()),
+            RangeOverlap::AInsideB | RangeOverlap::AEqualsB => Some(a.clone()),
+            RangeOverlap::AEndsInB => Some(Interval::from_bounds(b.start().cloned(), a.end().cloned())),
+            RangeOverlap::AStartsInB => Some(Interval::from_bounds(a.start().cloned(), b.end().cloned())),
+            RangeOverlap::None => None,
+        }
+    }
+}
+
+/// The range of values in both of two closed ranges, as a `(start, end)` pair, or `None` if they have
+/// no values in common. As elsewhere, the start of each range is included, `inclusive` says whether the
+/// ends are, and the result follows the same convention. For ranges with open sides or different kinds
+/// of bounds, use [`Interval::intersect`].
+///
+/// ```
+/// # use range_overlap::intersection;
+/// assert_eq!(intersection(1, 5, 3, 9, false), Some((3, 5)));
+/// assert_eq!(intersection(1, 5, 5, 9, false), None);
+/// assert_eq!(intersection(1, 5, 5, 9, true), Some((5, 5)));
+/// ```
+pub fn intersection<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<(T, T)> {
+    let a = Interval::new_unchecked(Some(&a_start), Some(&a_end), inclusive);
+    let b = Interval::new_unchecked(Some(&b_start), Some(&b_end), inclusive);
+    if !a.has_overlap(&b) {
+        return None;
+    }
+
+    let start = if a_start >= b_start { a_start } else { b_start };
+    let end = if a_end <= b_end { a_end } else { b_end };
+    Some((start, end))
+}
+
+/// Merge two closed ranges into one spanning both, as a `(start, end)` pair, if they have values in
+/// common; otherwise `None`. Ranges are given and returned as for [`intersection`].
+///
+/// ```
+/// # use range_overlap::merge_if_overlapping;
+/// assert_eq!(merge_if_overlapping(1, 5, 3, 9, false), Some((1, 9)));
+/// assert_eq!(merge_if_overlapping(1, 5, 5, 9, false), None);
+/// assert_eq!(merge_if_overlapping(1, 5, 5, 9, true), Some((1, 9)));
+/// ```
+pub fn merge_if_overlapping<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<(T, T)> {
+    let a = Interval::new_unchecked(Some(&a_start), Some(&a_end), inclusive);
+    let b = Interval::new_unchecked(Some(&b_start), Some(&b_end), inclusive);
+    if !a.has_overlap(&b) {
+        return None;
+    }
+    Some(closed_hull(a_start, a_end, b_start, b_end))
+}
+
+/// Like [`merge_if_overlapping`], but also merges ranges that touch without sharing a value, such as
+/// `[1, 5)` and `[5, 9)`.
+///
+/// ```
+/// # use range_overlap::merge_if_adjacent;
+/// assert_eq!(merge_if_adjacent(1, 5, 5, 9, false), Some((1, 9)));
+/// assert_eq!(merge_if_adjacent(1, 5, 6, 9, true), None);
+/// ```
+pub fn merge_if_adjacent<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> Option<(T, T)> {
+    let a = Interval::new_unchecked(Some(&a_start), Some(&a_end), inclusive);
+    let b = Interval::new_unchecked(Some(&b_start), Some(&b_end), inclusive);
+    if !a.has_overlap(&b) && !a.is_adjacent_to(&b) {
+        return None;
+    }
+    Some(closed_hull(a_start, a_end, b_start, b_end))
+}
+
+fn closed_hull<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> (T, T) {
+    let start = if a_start <= b_start { a_start } else { b_start };
+    let end = if a_end >= b_end { a_end } else { b_end };
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{contains, sample_intervals, sample_points};
+    use std::ops::Bound;
+
+    #[test]
+    fn test_intersect_membership() {
+        let intervals = sample_intervals(4);
+        for a in &intervals {
+            for b in &intervals {
+                let result = a.intersect(b);
+                for p in sample_points(4) {
+                    let expected = contains(a, p) && contains(b, p);
+                    let actual = result.as_ref().is_some_and(|r| contains(r, p));
+                    assert_eq!(actual, expected, "{a:?} & {b:?} at {p}");
+                }
+                assert!(result.is_none_or(|r| !r.is_empty()), "{a:?} & {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_clamp_to() {
+        let intervals = sample_intervals::<i32>(4);
+        for a in &intervals {
+            for b in &intervals {
+                let clamped = a.clamp_to(b);
+                assert_eq!(clamped, a.intersect(b));
+                if let Some(clamped) = clamped {
+                    assert!(matches!(clamped.classify_against(b), RangeOverlap::AInsideB | RangeOverlap::AEqualsB), "{a:?} clamped to {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersection_closed() {
+        for a_start in 0..4 {
+            for a_end in 0..4 {
+                for b_start in 0..4 {
+                    for b_end in 0..4 {
+                        let a = Interval::excl(a_start, a_end);
+                        let b = Interval::excl(b_start, b_end);
+                        let expected = a.intersect(&b).map(|r| r.into_bounds());
+                        let actual = intersection(a_start, a_end, b_start, b_end, false).map(|(s, e)| (Bound::Included(s), Bound::Excluded(e)));
+                        assert_eq!(actual, expected);
+
+                        let a = Interval::incl(a_start, a_end);
+                        let b = Interval::incl(b_start, b_end);
+                        let expected = a.intersect(&b).map(|r| r.into_bounds());
+                        let actual = intersection(a_start, a_end, b_start, b_end, true).map(|(s, e)| (Bound::Included(s), Bound::Included(e)));
+                        assert_eq!(actual, expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_union_membership() {
+        let intervals = sample_intervals(4);
+        for a in &intervals {
+            for b in &intervals {
+                let result = a.union(b);
+                for p in sample_points(4) {
+                    let expected = contains(a, p) || contains(b, p);
+                    let actual = match &result {
+                        IntervalUnion::Merged(r) => contains(r, p),
+                        IntervalUnion::Disjoint(x, y) => contains(x, p) || contains(y, p),
+                    };
+                    assert_eq!(actual, expected, "{a:?} | {b:?} at {p}");
+                }
+
+                // Only ranges with a gap between them stay separate
+                if let IntervalUnion::Disjoint(..) = result {
+                    assert!(!a.has_overlap(b) && !a.is_adjacent_to(b), "{a:?} | {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_difference_membership() {
+        let intervals = sample_intervals(4);
+        for a in &intervals {
+            for b in &intervals {
+                let pieces = a.difference(b).into_vec();
+                for p in sample_points(4) {
+                    let expected = contains(a, p) && !contains(b, p);
+                    let count = pieces.iter().filter(|r| contains(r, p)).count();
+                    assert_eq!(count, usize::from(expected), "{a:?} - {b:?} at {p}");
+                }
+                assert!(pieces.iter().all(|r| !r.is_empty()), "{a:?} - {b:?}");
+                if let [first, second] = pieces.as_slice() {
+                    assert!(first < second);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_symmetric_difference_membership() {
+        let intervals = sample_intervals(4);
+        for a in &intervals {
+            for b in &intervals {
+                let pieces = a.symmetric_difference(b).into_vec();
+                for p in sample_points(4) {
+                    let expected = contains(a, p) != contains(b, p);
+                    let count = pieces.iter().filter(|r| contains(r, p)).count();
+                    assert_eq!(count, usize::from(expected), "{a:?} ^ {b:?} at {p}");
+                }
+                assert_eq!(b.symmetric_difference(a).into_vec(), pieces);
+                if let [first, second] = pieces.as_slice() {
+                    assert!(!first.is_adjacent_to(second));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hull() {
+        let intervals = sample_intervals(4);
+        for a in &intervals {
+            for b in &intervals {
+                let hull = a.hull(b).unwrap();
+                let points = sample_points(4);
+                let in_either: Vec<f64> = points.iter().copied().filter(|&p| contains(a, p) || contains(b, p)).collect();
+                for &p in &points {
+                    // Everything between the first and last point in either interval is in the hull, and nothing else
+                    let expected = in_either.first().is_some_and(|&lo| lo <= p) && in_either.last().is_some_and(|&hi| p <= hi);
+                    assert_eq!(contains(&hull, p), expected, "hull of {a:?}, {b:?} at {p}");
+                }
+                if let IntervalUnion::Merged(union) = a.union(b) {
+                    assert_eq!(union, hull);
+                }
+            }
+        }
+
+        // NaN end points mean there are no values in the interval to contain
+        assert_eq!(Interval::excl(1.0, 2.0).hull(&Interval::excl(f64::NAN, 5.0)), Some(Interval::excl(1.0, 2.0)));
+    }
+
+    #[test]
+    fn test_split_at() {
+        for a in sample_intervals(4) {
+            for point in 0..4 {
+                let (first, second) = a.split_at(point);
+                for p in sample_points(4) {
+                    let in_first = first.as_ref().is_some_and(|r| contains(r, p));
+                    let in_second = second.as_ref().is_some_and(|r| contains(r, p));
+                    assert_eq!(in_first, contains(&a, p) && p < point as f64, "{a:?} split at {point}, {p}");
+                    assert_eq!(in_second, contains(&a, p) && p >= point as f64, "{a:?} split at {point}, {p}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_by() {
+        let intervals = sample_intervals(4);
+        for a in &intervals {
+            for b in &intervals {
+                let (before, overlap, after) = a.split_by(b);
+                assert_eq!(overlap, a.intersect(b));
+                let in_b: Vec<f64> = sample_points(4).into_iter().filter(|&p| contains(b, p)).collect();
+                for p in sample_points(4) {
+                    let in_a_only = contains(a, p) && !contains(b, p);
+                    let is_before = in_b.first().is_none_or(|&lo| p < lo);
+                    let is_after = in_b.last().is_some_and(|&hi| p > hi);
+                    assert_eq!(before.as_ref().is_some_and(|r| contains(r, p)), in_a_only && is_before, "{a:?} split by {b:?} at {p}");
+                    assert_eq!(after.as_ref().is_some_and(|r| contains(r, p)), in_a_only && is_after, "{a:?} split by {b:?} at {p}");
+                }
+                assert!([before, after].into_iter().flatten().all(|r| !r.is_empty()), "{a:?} split by {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_if() {
+        let intervals = sample_intervals::<i32>(4);
+        for a in &intervals {
+            for b in &intervals {
+                let overlapping = a.merge_if_overlapping(b);
+                let adjacent = a.merge_if_adjacent(b);
+                assert_eq!(overlapping.is_some(), a.has_overlap(b), "{a:?}, {b:?}");
+                assert_eq!(adjacent.is_some(), a.has_overlap(b) || a.is_adjacent_to(b), "{a:?}, {b:?}");
+                for merged in [overlapping, adjacent].into_iter().flatten() {
+                    assert_eq!(IntervalUnion::Merged(merged), a.union(b), "{a:?}, {b:?}");
+                }
+            }
+        }
+
+        for a_start in 0..4 {
+            for a_end in 0..4 {
+                for b_start in 0..4 {
+                    for b_end in 0..4 {
+                        for inclusive in [false, true] {
+                            let a = Interval::new_unchecked(Some(a_start), Some(a_end), inclusive);
+                            let b = Interval::new_unchecked(Some(b_start), Some(b_end), inclusive);
+                            let as_pair = |r: Interval<i32>| match r.into_bounds() {
+                                (Bound::Included(s), Bound::Included(e) | Bound::Excluded(e)) => (s, e),
+                                bounds => panic!("unexpected bounds {bounds:?}"),
+                            };
+                            assert_eq!(merge_if_overlapping(a_start, a_end, b_start, b_end, inclusive), a.merge_if_overlapping(&b).map(as_pair));
+                            assert_eq!(merge_if_adjacent(a_start, a_end, b_start, b_end, inclusive), a.merge_if_adjacent(&b).map(as_pair));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_combine_with_classification() {
+        let intervals = sample_intervals::<i32>(4);
+        for a in &intervals {
+            for b in &intervals {
+                let overlap = a.classify_against(b);
+                assert_eq!(overlap.merge(a, b), a.merge_if_overlapping(b), "{a:?}, {b:?}");
+                assert_eq!(overlap.intersect(a, b), a.intersect(b), "{a:?}, {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_gap() {
+        let intervals = sample_intervals(4);
+        for a in &intervals {
+            for b in &intervals {
+                let gap = a.gap(b);
+                assert_eq!(gap, b.gap(a));
+                let Some(gap) = gap else {
+                    assert!(a.is_empty() || b.is_empty() || a.has_overlap(b) || a.is_adjacent_to(b), "{a:?}, {b:?}");
+                    continue;
+                };
+
+                // The gap shares no values with either interval, and together the three cover the hull
+                assert!(!gap.is_empty() && !gap.has_overlap(a) && !gap.has_overlap(b), "{a:?}, {b:?}: {gap:?}");
+                let hull = a.hull(b).unwrap();
+                for p in sample_points(4) {
+                    assert_eq!(contains(&hull, p), contains(a, p) || contains(&gap, p) || contains(b, p), "{a:?}, {b:?} at {p}");
+                }
+
+                assert!(a.is_adjacent_to(&gap) && gap.is_adjacent_to(b), "{a:?}, {b:?}: {gap:?}");
+            }
+        }
+    }
+}
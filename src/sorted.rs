@@ -0,0 +1,66 @@
+//! Queries against plain slices of sorted, disjoint intervals.
+use crate::{AllenRelation, Interval};
+
+/// The intervals in `intervals` that overlap `target`, found by binary search in `O(log n)` time. The
+/// intervals must be sorted, non-empty, and disjoint (though they may touch), as in
+/// [`IntervalSet::as_slice`](crate::IntervalSet::as_slice); then the ones overlapping `target` are all next
+/// to each other, and are returned as a sub-slice. This is for code that already keeps such a `Vec` and
+/// does not need a whole [`IntervalSet`](crate::IntervalSet) or [`IntervalTree`](crate::IntervalTree). If
+/// the intervals are not sorted and disjoint, the result is some run of them, but may not be the right one.
+///
+/// ```
+/// # use range_overlap::{query_sorted, Interval};
+/// let shifts = vec![Interval::excl(0, 8), Interval::excl(8, 16), Interval::excl(16, 24)];
+/// assert_eq!(query_sorted(&shifts, &Interval::excl(6, 10)), [Interval::excl(0, 8), Interval::excl(8, 16)]);
+/// assert_eq!(query_sorted(&shifts, &Interval::incl(16, 16)), [Interval::excl(16, 24)]);
+/// assert!(query_sorted(&shifts, &Interval::at_least(24)).is_empty());
+/// ```
+pub fn query_sorted<'a, T: PartialOrd>(intervals: &'a [Interval<T>], target: &Interval<T>) -> &'a [Interval<T>] {
+    if target.is_empty() {
+        return &[];
+    }
+    let start = intervals.partition_point(|iv| matches!(iv.allen_relation(target), Some(AllenRelation::Before | AllenRelation::Meets)));
+    let end = start + intervals[start..].partition_point(|iv| iv.has_overlap(target));
+    &intervals[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Lcg;
+    use std::ops::Bound;
+
+    #[test]
+    fn test_matches_filter() {
+        let mut rng = Lcg::new(8642);
+        let bound = |value: i32, included: bool| if included { Bound::Included(value) } else { Bound::Excluded(value) };
+        for _ in 0..200 {
+            // Cut the line at increasing points and keep some of the pieces, so that neighbors may touch
+            let mut intervals = Vec::new();
+            let mut start = if rng.below(4) == 0 { Bound::Unbounded } else { bound(rng.below(5), true) };
+            let mut at = 5;
+            while at < 60 {
+                at += rng.below(5);
+                let included = rng.below(2) == 0;
+                let end = if at >= 60 { Bound::Unbounded } else { bound(at, included) };
+                let interval = Interval::from_bounds(start, end);
+                if rng.below(3) != 0 && !interval.is_empty() {
+                    intervals.push(interval);
+                }
+                start = bound(at, !included);
+            }
+
+            for _ in 0..20 {
+                let from = rng.below(70) - 5;
+                let target = match rng.below(6) {
+                    0 => Interval::at_most(from),
+                    1 => Interval::greater_than(from),
+                    2 => Interval::point(from),
+                    _ => Interval::excl(from, from + rng.below(15) - 2),
+                };
+                let expected: Vec<_> = intervals.iter().filter(|iv| iv.has_overlap(&target)).copied().collect();
+                assert_eq!(query_sorted(&intervals, &target), expected, "{target:?} in {intervals:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Lazy versions of the collection algorithms for intervals that arrive already sorted, which hold only
+//! the interval in progress and so never allocate.
+use std::ops::Bound;
+
+use crate::{Interval, MergePolicy};
+
+/// An iterator that merges a sorted stream of intervals as it goes, created by [`merge_sorted`].
+#[derive(Debug, Clone)]
+pub struct MergeSorted<T, I> {
+    intervals: I,
+    policy: MergePolicy,
+    /// The merged interval being built, which later intervals may still extend
+    current: Option<Interval<T>>,
+}
+
+impl<T: PartialOrd + Clone, I: Iterator<Item = Interval<T>>> Iterator for MergeSorted<T, I> {
+    type Item = Interval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for interval in self.intervals.by_ref() {
+            if interval.is_empty() {
+                continue;
+            }
+            let Some(current) = self.current.take() else {
+                self.current = Some(interval);
+                continue;
+            };
+            match self.policy.merge(&current, &interval) {
+                Some(combined) => self.current = Some(combined),
+                None => {
+                    self.current = Some(interval);
+                    return Some(current);
+                },
+            }
+        }
+        self.current.take()
+    }
+}
+
+/// Merge a stream of intervals sorted by start, like [`merge_intervals`](crate::merge_intervals), but
+/// lazily: each merged interval is yielded as soon as an interval that cannot join it arrives. Only that
+/// one interval is held at a time, so this never allocates, and works on streams of any length. Empty
+/// intervals are skipped. If the stream is not sorted, each yielded interval is still the union of a run
+/// of the intervals, but runs that overlap may not all be merged.
+///
+/// ```
+/// # use range_overlap::{merge_sorted, Interval, MergePolicy};
+/// let outages = [Interval::excl(1, 3), Interval::excl(2, 5), Interval::excl(5, 9), Interval::excl(12, 14)];
+/// let merged: Vec<_> = merge_sorted(outages, MergePolicy::OverlapOrTouch).collect();
+/// assert_eq!(merged, [Interval::excl(1, 9), Interval::excl(12, 14)]);
+/// ```
+pub fn merge_sorted<T, I: IntoIterator<Item = Interval<T>>>(intervals: I, policy: MergePolicy) -> MergeSorted<T, I::IntoIter> {
+    MergeSorted { intervals: intervals.into_iter(), policy, current: None }
+}
+
+/// An iterator over the gaps between a sorted stream of intervals, created by [`gaps_sorted`].
+#[derive(Debug, Clone)]
+pub struct SortedGaps<T, I> {
+    merged: MergeSorted<T, I>,
+    /// Where the intervals read so far end
+    last_end: Option<Bound<T>>,
+}
+
+/// The bound on the other side of the same value, e.g. where a gap starts after an interval ends.
+fn flip<T>(bound: Bound<T>) -> Bound<T> {
+    match bound {
+        Bound::Included(v) => Bound::Excluded(v),
+        Bound::Excluded(v) => Bound::Included(v),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+impl<T: PartialOrd + Clone, I: Iterator<Item = Interval<T>>> Iterator for SortedGaps<T, I> {
+    type Item = Interval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (start, end) = self.merged.next()?.into_bounds();
+            let Some(last_end) = self.last_end.replace(end) else { continue };
+            // An unbounded end is never followed by anything when the stream is sorted
+            if last_end == Bound::Unbounded {
+                continue;
+            }
+            let gap = Interval::from_bounds(flip(last_end), flip(start));
+            if !gap.is_empty() {
+                return Some(gap);
+            }
+        }
+    }
+}
+
+/// The gaps between a stream of intervals sorted by start, like [`gaps`](crate::gaps) with no window, but
+/// yielded lazily as the stream is read, without allocating. If the stream is not sorted, some of the
+/// gaps yielded may be covered by intervals that come later.
+///
+/// ```
+/// # use range_overlap::{gaps_sorted, Interval};
+/// let readings = [Interval::excl(0, 10), Interval::excl(8, 15), Interval::excl(25, 40), Interval::incl(40, 50)];
+/// assert_eq!(gaps_sorted(readings).collect::<Vec<_>>(), [Interval::excl(15, 25)]);
+/// ```
+pub fn gaps_sorted<T, I: IntoIterator<Item = Interval<T>>>(intervals: I) -> SortedGaps<T, I::IntoIter> {
+    SortedGaps { merged: merge_sorted(intervals, MergePolicy::OverlapOrTouch), last_end: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval};
+    use crate::{gaps, merge_intervals};
+
+    fn sorted_collection(seed: u32, n: usize) -> Vec<Interval<i32>> {
+        let mut rng = Lcg::new(seed);
+        let mut intervals: Vec<Interval<i32>> = (0..n).map(|_| random_interval(&mut rng, 40, 6)).collect();
+        intervals.sort();
+        intervals
+    }
+
+    #[test]
+    fn test_matches_collected() {
+        for seed in 0..300 {
+            let intervals = sorted_collection(seed, (seed % 15) as usize);
+            for policy in [MergePolicy::OverlapOnly, MergePolicy::OverlapOrTouch] {
+                let merged: Vec<_> = merge_sorted(intervals.iter().copied(), policy).collect();
+                assert_eq!(merged, merge_intervals(intervals.iter().copied(), policy), "{intervals:?}");
+            }
+            let found: Vec<_> = gaps_sorted(intervals.iter().copied()).collect();
+            assert_eq!(found, gaps(intervals.iter().copied(), None), "{intervals:?}");
+        }
+    }
+}
@@ -0,0 +1,910 @@
+//! Operations on whole collections of intervals, done by sorting them and sweeping from start to end.
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::ops::{Add, Bound, Sub};
+
+use crate::bounds;
+use crate::{CoverageGapError, Interval, IntervalSet, RangeOverlap, RangeOverlapMask};
+
+/// Sort the non-empty intervals in `intervals` by start, returning their indices.
+pub(crate) fn sorted_nonempty<T: PartialOrd>(intervals: &[Interval<T>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intervals.len()).filter(|&i| !intervals[i].is_empty()).collect();
+    // Non-empty intervals can always be compared
+    order.sort_by(|&a, &b| intervals[a].partial_cmp(&intervals[b]).unwrap_or(Ordering::Equal));
+    order
+}
+
+/// Group `intervals` into clusters connected by overlap: two intervals are in the same cluster if they
+/// overlap, or if there is a chain of overlapping intervals from one to the other. Returns the indices
+/// of the intervals in each cluster, in ascending order, with the clusters ordered by where they start.
+/// Intervals that only touch are not connected. An empty interval overlaps nothing, so it is always in a
+/// cluster on its own; these come last.
+///
+/// ```
+/// # use range_overlap::{cluster_overlapping, Interval};
+/// let alarms = [Interval::excl(10, 20), Interval::excl(50, 60), Interval::excl(15, 30), Interval::excl(25, 40), Interval::excl(40, 45)];
+/// assert_eq!(cluster_overlapping(&alarms), [vec![0, 2, 3], vec![4], vec![1]]);
+/// ```
+pub fn cluster_overlapping<T: PartialOrd + Clone>(intervals: &[Interval<T>]) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut hull: Option<Interval<T>> = None;
+    for i in sorted_nonempty(intervals) {
+        let interval = &intervals[i];
+        match hull.as_ref().and_then(|h| h.merge_if_overlapping(interval)) {
+            Some(merged) => {
+                hull = Some(merged);
+                clusters.last_mut().unwrap().push(i);
+            },
+            None => {
+                hull = Some(interval.clone());
+                clusters.push(vec![i]);
+            },
+        }
+    }
+
+    for cluster in clusters.iter_mut() {
+        cluster.sort_unstable();
+    }
+    clusters.extend((0..intervals.len()).filter(|&i| intervals[i].is_empty()).map(|i| vec![i]));
+    clusters
+}
+
+/// Which intervals [`merge_intervals`] combines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum MergePolicy {
+    /// Only combine intervals that [overlap](Interval::has_overlap), so `[1, 5)` and `[5, 9)` stay separate.
+    OverlapOnly,
+
+    /// Also combine intervals that [touch](Interval::is_adjacent_to), so `[1, 5)` and `[5, 9)` become `[1, 9)`.
+    OverlapOrTouch,
+}
+
+/// Coalesce `intervals` into the fewest disjoint intervals covering the same values, sorted by start.
+/// `policy` says whether intervals that touch without overlapping are combined; with
+/// [`MergePolicy::OverlapOrTouch`] the result is the same as collecting into an [`IntervalSet`](crate::IntervalSet).
+/// Empty intervals cover nothing, so they are dropped.
+///
+/// ```
+/// # use range_overlap::{merge_intervals, Interval, MergePolicy};
+/// let outages = [Interval::excl(5, 9), Interval::excl(1, 3), Interval::excl(2, 5), Interval::excl(12, 14)];
+/// assert_eq!(merge_intervals(outages, MergePolicy::OverlapOnly), [Interval::excl(1, 5), Interval::excl(5, 9), Interval::excl(12, 14)]);
+/// assert_eq!(merge_intervals(outages, MergePolicy::OverlapOrTouch), [Interval::excl(1, 9), Interval::excl(12, 14)]);
+/// ```
+pub fn merge_intervals<T: PartialOrd + Clone, I: IntoIterator<Item = Interval<T>>>(intervals: I, policy: MergePolicy) -> Vec<Interval<T>> {
+    let mut intervals: Vec<Interval<T>> = intervals.into_iter().collect();
+    merge_intervals_in_place(&mut intervals, policy);
+    intervals
+}
+
+impl MergePolicy {
+    /// `a` and `b` combined into one interval, if this policy combines them.
+    pub(crate) fn merge<T: PartialOrd + Clone>(self, a: &Interval<T>, b: &Interval<T>) -> Option<Interval<T>> {
+        match self {
+            MergePolicy::OverlapOnly => a.merge_if_overlapping(b),
+            MergePolicy::OverlapOrTouch => a.merge_if_adjacent(b),
+        }
+    }
+}
+
+/// Like [`merge_intervals`], but merging the intervals in `intervals` where they are, so that no memory
+/// is allocated: the vector is sorted in place and shortened to the merged intervals. Reusing one vector
+/// this way suits code that must not allocate once it is running.
+///
+/// ```
+/// # use range_overlap::{merge_intervals_in_place, Interval, MergePolicy};
+/// let mut outages = vec![Interval::excl(5, 9), Interval::excl(1, 3), Interval::excl(2, 5), Interval::excl(12, 14)];
+/// merge_intervals_in_place(&mut outages, MergePolicy::OverlapOrTouch);
+/// assert_eq!(outages, [Interval::excl(1, 9), Interval::excl(12, 14)]);
+/// ```
+pub fn merge_intervals_in_place<T: PartialOrd + Clone>(intervals: &mut Vec<Interval<T>>, policy: MergePolicy) {
+    intervals.retain(|iv| !iv.is_empty());
+    // Non-empty intervals can always be compared, and an unstable sort needs no buffer
+    intervals.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    // The first `kept` intervals are merged; each one after is either folded into the last of those or
+    // moved up to follow it
+    let mut kept: usize = 0;
+    for i in 0..intervals.len() {
+        match kept.checked_sub(1).and_then(|last| policy.merge(&intervals[last], &intervals[i])) {
+            Some(combined) => intervals[kept - 1] = combined,
+            None => {
+                intervals.swap(kept, i);
+                kept += 1;
+            },
+        }
+    }
+    intervals.truncate(kept);
+}
+
+/// The values in `within` that no interval in `intervals` covers, as disjoint intervals sorted by
+/// start. If `within` is `None`, the window is the span of `intervals`, from the earliest start to the
+/// latest end, so only the gaps between intervals are returned.
+///
+/// ```
+/// # use range_overlap::{gaps, Interval};
+/// let readings = [Interval::excl(0, 10), Interval::excl(25, 40), Interval::excl(8, 15)];
+/// assert_eq!(gaps(readings, None), [Interval::excl(15, 25)]);
+/// assert_eq!(gaps(readings, Some(Interval::excl(-5, 50))), [Interval::excl(-5, 0), Interval::excl(15, 25), Interval::excl(40, 50)]);
+/// ```
+pub fn gaps<T: PartialOrd + Clone, I: IntoIterator<Item = Interval<T>>>(intervals: I, within: Option<Interval<T>>) -> Vec<Interval<T>> {
+    let covered: IntervalSet<T> = intervals.into_iter().collect();
+    let Some(window) = within.or_else(|| covered.span()) else {
+        return Vec::new();
+    };
+    covered.complement(Some(&window)).into_iter().collect()
+}
+
+/// Returns `true` if every value in `target` is in at least one of `intervals`. Coverage can be split
+/// across several intervals, as long as there is no gap between them. An empty target is always covered.
+///
+/// ```
+/// # use range_overlap::{is_fully_covered, Interval};
+/// let shifts = [Interval::excl(0, 8), Interval::excl(8, 16), Interval::excl(15, 24)];
+/// assert!(is_fully_covered(&Interval::excl(0, 24), shifts));
+/// assert!(!is_fully_covered(&Interval::incl(0, 24), shifts));
+/// ```
+pub fn is_fully_covered<T: PartialOrd + Clone, I: IntoIterator<Item = Interval<T>>>(target: &Interval<T>, intervals: I) -> bool {
+    intervals.into_iter().collect::<IntervalSet<T>>().contains_interval(target)
+}
+
+/// The first stretch of `target` that none of `intervals` cover, or `None` if [`is_fully_covered`] is `true`.
+/// Use [`gaps`] to find every such stretch.
+///
+/// ```
+/// # use range_overlap::{first_uncovered, Interval};
+/// let shifts = [Interval::excl(0, 8), Interval::excl(10, 16), Interval::excl(15, 24)];
+/// assert_eq!(first_uncovered(&Interval::excl(0, 24), shifts), Some(Interval::excl(8, 10)));
+/// assert_eq!(first_uncovered(&Interval::incl(10, 20), shifts), None);
+/// ```
+pub fn first_uncovered<T: PartialOrd + Clone, I: IntoIterator<Item = Interval<T>>>(target: &Interval<T>, intervals: I) -> Option<Interval<T>> {
+    let covered: IntervalSet<T> = intervals.into_iter().collect();
+    covered.complement(Some(target)).into_iter().next()
+}
+
+/// The fewest of `candidates` whose union covers all of `target`, as indices into `candidates` sorted by
+/// where the intervals start. This uses the greedy algorithm: starting from the beginning of the target,
+/// repeatedly pick the candidate covering the current position that reaches furthest. If the candidates
+/// cannot cover the target, the error gives the first part of the target that none of them cover.
+///
+/// ```
+/// # use range_overlap::{minimal_cover, Interval};
+/// let stations = [Interval::excl(0, 4), Interval::excl(1, 6), Interval::excl(3, 9), Interval::excl(5, 10), Interval::excl(8, 12)];
+/// assert_eq!(minimal_cover(&Interval::excl(0, 12), &stations), Ok(vec![0, 2, 4]));
+/// assert_eq!(minimal_cover(&Interval::excl(0, 15), &stations).unwrap_err().gap, Interval::excl(12, 15));
+/// ```
+pub fn minimal_cover<T: PartialOrd + Clone>(target: &Interval<T>, candidates: &[Interval<T>]) -> Result<Vec<usize>, CoverageGapError<T>> {
+    if target.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let order = sorted_nonempty(candidates);
+    let target_end = Cut::from_end(target.end().cloned());
+    // Every value of the target below this cut is covered
+    let mut covered_to = Cut::from_start(target.start().cloned());
+    let mut chosen = Vec::new();
+    let mut next = 0;
+    while covered_to.partial_cmp(&target_end) == Some(Ordering::Less) {
+        let mut furthest: Option<(usize, Cut<T>)> = None;
+        while let Some(&i) = order.get(next) {
+            if Cut::from_start(candidates[i].start().cloned()).partial_cmp(&covered_to) == Some(Ordering::Greater) {
+                break;
+            }
+            let end = Cut::from_end(candidates[i].end().cloned());
+            if furthest.as_ref().is_none_or(|(_, f)| end.partial_cmp(f) == Some(Ordering::Greater)) {
+                furthest = Some((i, end));
+            }
+            next += 1;
+        }
+
+        match furthest {
+            Some((i, end)) if end.partial_cmp(&covered_to) == Some(Ordering::Greater) => {
+                chosen.push(i);
+                covered_to = end;
+            },
+            _ => {
+                let gap = first_uncovered(target, candidates.iter().cloned()).expect("greedy cover only fails if part of the target is uncovered");
+                return Err(CoverageGapError { gap });
+            },
+        }
+    }
+    Ok(chosen)
+}
+
+/// Assign each of `intervals` to a track, numbered from 0, so that no two intervals on the same track
+/// overlap, using as few tracks as possible. Returns the track for each interval, in the same order as
+/// `intervals`. The number of tracks used is the [maximum overlap depth](max_overlap_depth), since
+/// intervals are placed in order of start on the lowest-numbered track that is free. Empty intervals
+/// overlap nothing, so they are put on track 0.
+///
+/// ```
+/// # use range_overlap::{assign_tracks, Interval};
+/// let tasks = [Interval::excl(0, 4), Interval::excl(2, 6), Interval::excl(4, 8), Interval::excl(6, 9)];
+/// assert_eq!(assign_tracks(&tasks), [0, 1, 0, 1]);
+/// ```
+pub fn assign_tracks<T: PartialOrd + Clone>(intervals: &[Interval<T>]) -> Vec<usize> {
+    let mut tracks = vec![0; intervals.len()];
+    // Where the last interval placed on each track ends
+    let mut track_ends: Vec<Cut<T>> = Vec::new();
+    for i in sorted_nonempty(intervals) {
+        let start = Cut::from_start(intervals[i].start().cloned());
+        let end = Cut::from_end(intervals[i].end().cloned());
+        // Intervals are placed in order of start, so one only overlaps a track if it starts before that track's end
+        match track_ends.iter().position(|e| e.partial_cmp(&start) != Some(Ordering::Greater)) {
+            Some(track) => {
+                tracks[i] = track;
+                track_ends[track] = end;
+            },
+            None => {
+                tracks[i] = track_ends.len();
+                track_ends.push(end);
+            },
+        }
+    }
+    tracks
+}
+
+/// The indices of a largest set of `intervals` in which no two overlap, ordered along the number line.
+/// This is the activity-selection algorithm: repeatedly pick the interval that ends earliest among those
+/// that do not overlap any picked so far. Intervals that only touch do not overlap, so both can be picked.
+/// Empty intervals are never picked.
+///
+/// ```
+/// # use range_overlap::{max_disjoint_subset, Interval};
+/// let bookings = [Interval::excl(1, 4), Interval::excl(3, 5), Interval::excl(0, 6), Interval::excl(5, 7), Interval::excl(4, 9)];
+/// assert_eq!(max_disjoint_subset(&bookings), [0, 3]);
+/// ```
+pub fn max_disjoint_subset<T: PartialOrd + Clone>(intervals: &[Interval<T>]) -> Vec<usize> {
+    let mut by_end = sorted_nonempty(intervals);
+    by_end.sort_by(|&a, &b| bounds::cmp_ends(intervals[a].end(), intervals[b].end()).unwrap_or(Ordering::Equal));
+
+    let mut chosen: Vec<usize> = Vec::new();
+    let mut last_end: Option<Cut<T>> = None;
+    for i in by_end {
+        let start = Cut::from_start(intervals[i].start().cloned());
+        if last_end.as_ref().is_none_or(|end| end.partial_cmp(&start) != Some(Ordering::Greater)) {
+            chosen.push(i);
+            last_end = Some(Cut::from_end(intervals[i].end().cloned()));
+        }
+    }
+    chosen
+}
+
+/// The total length of the values covered by `intervals`, counting values covered by more than one
+/// interval only once. Returns `None` if any interval has an open side, since then the covered length is
+/// infinite, and the default value (zero) if there are no intervals.
+///
+/// ```
+/// # use range_overlap::{covered_length, Interval};
+/// let incidents = [Interval::excl(1.0, 3.0), Interval::excl(2.0, 4.5), Interval::excl(8.0, 9.0)];
+/// assert_eq!(covered_length(incidents), Some(4.5));
+/// assert_eq!(covered_length([Interval::excl(1, 3), Interval::at_least(10)]), None);
+/// ```
+pub fn covered_length<T: PartialOrd + Clone + Sub, I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Option<T::Output>
+where
+    T::Output: Default + Add<Output = T::Output>,
+{
+    merge_intervals(intervals, MergePolicy::OverlapOnly).iter().try_fold(T::Output::default(), |total, interval| Some(total + interval.length()?))
+}
+
+/// A position on the number line between values, where intervals start and end: below or above a
+/// value, or beyond all values on one side.
+#[derive(Debug, Clone)]
+enum Cut<T> {
+    NegInf,
+    Below(T),
+    Above(T),
+    PosInf,
+}
+
+impl<T: PartialOrd> Cut<T> {
+    fn from_start(start: Bound<T>) -> Self {
+        match start {
+            Bound::Included(v) => Cut::Below(v),
+            Bound::Excluded(v) => Cut::Above(v),
+            Bound::Unbounded => Cut::NegInf,
+        }
+    }
+
+    fn from_end(end: Bound<T>) -> Self {
+        match end {
+            Bound::Included(v) => Cut::Above(v),
+            Bound::Excluded(v) => Cut::Below(v),
+            Bound::Unbounded => Cut::PosInf,
+        }
+    }
+
+    /// The bound for an interval starting at this cut.
+    fn into_start(self) -> Bound<T> {
+        match self {
+            Cut::Below(v) => Bound::Included(v),
+            Cut::Above(v) => Bound::Excluded(v),
+            Cut::NegInf | Cut::PosInf => Bound::Unbounded,
+        }
+    }
+
+    /// The bound for an interval ending at this cut.
+    fn into_end(self) -> Bound<T> {
+        match self {
+            Cut::Below(v) => Bound::Excluded(v),
+            Cut::Above(v) => Bound::Included(v),
+            Cut::NegInf | Cut::PosInf => Bound::Unbounded,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Cut::NegInf => 0,
+            Cut::Below(_) => 1,
+            Cut::Above(_) => 2,
+            Cut::PosInf => 3,
+        }
+    }
+
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Cut::Below(a) | Cut::Above(a), Cut::Below(b) | Cut::Above(b)) => match a.partial_cmp(b)? {
+                Ordering::Equal => Some(self.rank().cmp(&other.rank())),
+                ord => Some(ord),
+            },
+            _ => Some(self.rank().cmp(&other.rank())),
+        }
+    }
+}
+
+/// Where one of the intervals given to [`interval_events`] starts or ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum IntervalEvent<T> {
+    /// The interval at `index` starts at `bound`.
+    Start { index: usize, bound: Bound<T> },
+
+    /// The interval at `index` ends at `bound`.
+    End { index: usize, bound: Bound<T> },
+}
+
+impl<T> IntervalEvent<T> {
+    /// The position of the interval that starts or ends here, in the collection given to [`interval_events`].
+    pub fn index(&self) -> usize {
+        match self {
+            IntervalEvent::Start { index, .. } | IntervalEvent::End { index, .. } => *index,
+        }
+    }
+
+    /// The bound of the interval that starts or ends here.
+    pub fn bound(&self) -> Bound<&T> {
+        match self {
+            IntervalEvent::Start { bound, .. } | IntervalEvent::End { bound, .. } => bound.as_ref(),
+        }
+    }
+
+    /// Returns `true` if this is an [`IntervalEvent::Start`].
+    pub fn is_start(&self) -> bool {
+        matches!(self, IntervalEvent::Start { .. })
+    }
+}
+
+impl<T: PartialOrd> IntervalEvent<T> {
+    fn into_cut(self) -> Cut<T> {
+        match self {
+            IntervalEvent::Start { bound, .. } => Cut::from_start(bound),
+            IntervalEvent::End { bound, .. } => Cut::from_end(bound),
+        }
+    }
+
+    fn cut(&self) -> Cut<&T> {
+        match self {
+            IntervalEvent::Start { bound, .. } => Cut::from_start(bound.as_ref()),
+            IntervalEvent::End { bound, .. } => Cut::from_end(bound.as_ref()),
+        }
+    }
+}
+
+/// The starts and ends of the non-empty intervals in `intervals`, sorted in the order a sweep from low to
+/// high values passes them, as the basis for sweep algorithms. Each event records which interval it came
+/// from by its position in `intervals`.
+///
+/// Events at the same value are ordered by which side of the value they fall on: an excluded end or
+/// included start is just below the value, while an included end or excluded start is just above it.
+/// Where an end and a start fall at the same place, the end comes first, so the number of intervals that
+/// have started but not ended is always how many overlap at that point in the sweep.
+///
+/// ```
+/// # use std::ops::Bound;
+/// # use range_overlap::{interval_events, Interval, IntervalEvent};
+/// let events = interval_events([Interval::incl(1, 5), Interval::excl(5, 9), Interval::excl(0, 1)]);
+/// assert_eq!(events, [
+///     IntervalEvent::Start { index: 2, bound: Bound::Included(0) },
+///     IntervalEvent::End { index: 2, bound: Bound::Excluded(1) },
+///     IntervalEvent::Start { index: 0, bound: Bound::Included(1) },
+///     IntervalEvent::Start { index: 1, bound: Bound::Included(5) },
+///     IntervalEvent::End { index: 0, bound: Bound::Included(5) },
+///     IntervalEvent::End { index: 1, bound: Bound::Excluded(9) },
+/// ]);
+/// ```
+pub fn interval_events<T: PartialOrd, I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Vec<IntervalEvent<T>> {
+    let mut events = Vec::new();
+    for (index, interval) in intervals.into_iter().enumerate().filter(|(_, iv)| !iv.is_empty()) {
+        let (start, end) = interval.into_bounds();
+        events.push(IntervalEvent::Start { index, bound: start });
+        events.push(IntervalEvent::End { index, bound: end });
+    }
+    // The end points of non-empty intervals can always be compared
+    events.sort_by(|a, b| a.cut().partial_cmp(&b.cut()).unwrap_or(Ordering::Equal).then_with(|| a.is_start().cmp(&b.is_start())));
+    events
+}
+
+/// Classify every interval in `a_list` against every interval in `b_list`, so that `matrix[i][j]` is
+/// `a_list[i].classify_against(&b_list[j])`. Rather than comparing every pair, this sweeps over both lists
+/// together and only classifies pairs that overlap, leaving the rest as [`RangeOverlap::None`], so it is
+/// much faster when most pairs are disjoint.
+///
+/// ```
+/// # use range_overlap::{classify_matrix, Interval, RangeOverlap};
+/// let a = [Interval::excl(0, 10), Interval::excl(20, 30)];
+/// let b = [Interval::excl(5, 25), Interval::excl(2, 4), Interval::excl(40, 50)];
+/// assert_eq!(classify_matrix(&a, &b), [
+///     [RangeOverlap::AEndsInB, RangeOverlap::AContainsB, RangeOverlap::None],
+///     [RangeOverlap::AStartsInB, RangeOverlap::None, RangeOverlap::None],
+/// ]);
+/// ```
+pub fn classify_matrix<T: PartialOrd + Clone>(a_list: &[Interval<T>], b_list: &[Interval<T>]) -> Vec<Vec<RangeOverlap>> {
+    let mut matrix = vec![vec![RangeOverlap::None; b_list.len()]; a_list.len()];
+    let n_a = a_list.len();
+    // The intervals from each list that the sweep is currently inside
+    let mut active_a: Vec<usize> = Vec::new();
+    let mut active_b: Vec<usize> = Vec::new();
+    for event in interval_events(a_list.iter().chain(b_list).cloned()) {
+        let (index, is_a) = if event.index() < n_a { (event.index(), true) } else { (event.index() - n_a, false) };
+        let (own, other) = if is_a { (&mut active_a, &active_b) } else { (&mut active_b, &active_a) };
+        if !event.is_start() {
+            own.retain(|&i| i != index);
+            continue;
+        }
+
+        // Ends come before starts at the same place, so the intervals still active are exactly those that
+        // overlap the one starting here
+        for &j in other.iter() {
+            let (i, j) = if is_a { (index, j) } else { (j, index) };
+            matrix[i][j] = a_list[i].classify_against(&b_list[j]);
+        }
+        own.push(index);
+    }
+    matrix
+}
+
+/// Every pair of intervals in `intervals` that overlap, as `(i, j)` indices with `i < j`, sorted. If `mask`
+/// is given, only pairs where `intervals[i]` overlaps `intervals[j]` in one of the ways it contains are
+/// returned. Like [`classify_matrix`], this sweeps over the intervals rather than comparing every pair.
+///
+/// ```
+/// # use range_overlap::{find_conflicts, Interval, RangeOverlapMask};
+/// let bookings = [Interval::excl(9, 12), Interval::excl(13, 14), Interval::excl(10, 11), Interval::excl(11, 13)];
+/// assert_eq!(find_conflicts(&bookings, None), [(0, 2), (0, 3)]);
+/// assert_eq!(find_conflicts(&bookings, Some(RangeOverlapMask::A_CONTAINS_B)), [(0, 2)]);
+/// ```
+pub fn find_conflicts<T: PartialOrd + Clone>(intervals: &[Interval<T>], mask: Option<RangeOverlapMask>) -> Vec<(usize, usize)> {
+    let mut conflicts = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    for event in interval_events(intervals.iter().cloned()) {
+        let index = event.index();
+        if !event.is_start() {
+            active.retain(|&i| i != index);
+            continue;
+        }
+        for &other in &active {
+            let (i, j) = (index.min(other), index.max(other));
+            if mask.is_none_or(|mask| intervals[i].classify_against(&intervals[j]).matches(mask)) {
+                conflicts.push((i, j));
+            }
+        }
+        active.push(index);
+    }
+    conflicts.sort_unstable();
+    conflicts
+}
+
+/// The cuts where the non-empty intervals in `intervals` start and end, sorted, each with `+1` for a
+/// start or `-1` for an end.
+fn depth_events<T: PartialOrd, I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Vec<(Cut<T>, isize)> {
+    interval_events(intervals).into_iter().map(|e| {
+        let change = if e.is_start() { 1 } else { -1 };
+        (e.into_cut(), change)
+    }).collect()
+}
+
+/// An iterator over the stretches of constant overlap depth in a collection of intervals, created by
+/// [`depth_profile`].
+#[derive(Debug, Clone)]
+pub struct DepthProfile<T> {
+    events: Peekable<std::vec::IntoIter<(Cut<T>, isize)>>,
+    depth: isize,
+    /// Where the current stretch started, if the sweep has reached the first interval
+    start: Option<Cut<T>>,
+}
+
+impl<T: PartialOrd + Clone> Iterator for DepthProfile<T> {
+    type Item = (Interval<T>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (cut, change) = self.events.next()?;
+            let mut depth = self.depth + change;
+            while let Some((_, change)) = self.events.next_if(|(next, _)| next.partial_cmp(&cut) == Some(Ordering::Equal)) {
+                depth += change;
+            }
+            // Intervals that end where others start do not change the depth
+            if depth == self.depth {
+                continue;
+            }
+
+            let segment_depth = std::mem::replace(&mut self.depth, depth);
+            if let Some(start) = self.start.replace(cut.clone()) {
+                return Some((Interval::from_bounds(start.into_start(), cut.into_end()), segment_depth as usize));
+            }
+        }
+    }
+}
+
+/// Sweep over `intervals`, yielding each stretch over which the number of overlapping intervals stays the
+/// same, with that number. The stretches are in order, touch each other, and cover everything from the
+/// earliest start to the latest end, including any gaps (with a depth of 0). Consecutive stretches always
+/// have different depths. Empty intervals are ignored.
+///
+/// ```
+/// # use range_overlap::{depth_profile, Interval};
+/// let reads = [Interval::excl(0, 10), Interval::excl(5, 15), Interval::excl(20, 25)];
+/// let profile: Vec<_> = depth_profile(reads).collect();
+/// assert_eq!(profile, [
+///     (Interval::excl(0, 5), 1),
+///     (Interval::excl(5, 10), 2),
+///     (Interval::excl(10, 15), 1),
+///     (Interval::excl(15, 20), 0),
+///     (Interval::excl(20, 25), 1),
+/// ]);
+/// ```
+pub fn depth_profile<T: PartialOrd + Clone, I: IntoIterator<Item = Interval<T>>>(intervals: I) -> DepthProfile<T> {
+    DepthProfile { events: depth_events(intervals).into_iter().peekable(), depth: 0, start: None }
+}
+
+/// The largest number of intervals in `intervals` that overlap at once, and the first interval over
+/// which that many overlap. Returns `None` if there are no non-empty intervals.
+///
+/// ```
+/// # use range_overlap::{max_overlap_depth, Interval};
+/// let meetings = [Interval::excl(9, 11), Interval::excl(10, 12), Interval::excl(13, 15), Interval::excl(10, 14)];
+/// assert_eq!(max_overlap_depth(meetings), Some((3, Interval::excl(10, 11))));
+/// ```
+pub fn max_overlap_depth<T: PartialOrd + Clone, I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Option<(usize, Interval<T>)> {
+    let mut best: Option<(Interval<T>, usize)> = None;
+    for (interval, depth) in depth_profile(intervals) {
+        if best.as_ref().is_none_or(|(_, best_depth)| depth > *best_depth) {
+            best = Some((interval, depth));
+        }
+    }
+    best.map(|(interval, depth)| (depth, interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, contains, random_interval};
+    use std::ops::Bound;
+
+    /// A scrambled but repeatable collection of small intervals of every kind, including empty ones
+    fn sample_collection(seed: u32, n: usize) -> Vec<Interval<i32>> {
+        let mut rng = Lcg::new(seed);
+        (0..n).map(|_| random_interval(&mut rng, 20, 5)).collect()
+    }
+
+    #[test]
+    fn test_cluster_overlapping() {
+        for seed in 0..200 {
+            let intervals = sample_collection(seed, (seed % 12) as usize);
+            let clusters = cluster_overlapping(&intervals);
+
+            // Every interval is in exactly one cluster
+            let mut all: Vec<usize> = clusters.iter().flatten().copied().collect();
+            all.sort();
+            assert_eq!(all, (0..intervals.len()).collect::<Vec<_>>());
+
+            // Intervals that overlap are in the same cluster, and each cluster is connected
+            let cluster_of = |i: usize| clusters.iter().position(|c| c.contains(&i)).unwrap();
+            for i in 0..intervals.len() {
+                for j in 0..intervals.len() {
+                    if intervals[i].has_overlap(&intervals[j]) {
+                        assert_eq!(cluster_of(i), cluster_of(j), "{intervals:?}");
+                    }
+                }
+            }
+            for cluster in &clusters {
+                let mut reached = vec![cluster[0]];
+                while let Some(&next) = cluster.iter().find(|&&i| !reached.contains(&i) && reached.iter().any(|&r| intervals[r].has_overlap(&intervals[i]))) {
+                    reached.push(next);
+                }
+                assert_eq!(reached.len(), cluster.len(), "{intervals:?}: {clusters:?}");
+                assert!(cluster.windows(2).all(|w| w[0] < w[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_intervals() {
+        let points: Vec<f64> = (-2..=50).map(|i| i as f64 * 0.5).collect();
+        for seed in 0..200 {
+            let intervals = sample_collection(seed, (seed % 12) as usize);
+            for policy in [MergePolicy::OverlapOnly, MergePolicy::OverlapOrTouch] {
+                let merged = merge_intervals(intervals.iter().copied(), policy);
+                for &p in &points {
+                    assert_eq!(merged.iter().any(|m| contains(m, p)), intervals.iter().any(|iv| contains(iv, p)), "{intervals:?} at {p}");
+                }
+                for pair in merged.windows(2) {
+                    assert!(pair[0] < pair[1], "{merged:?}");
+                    assert!(!pair[0].has_overlap(&pair[1]), "{merged:?}");
+                    if policy == MergePolicy::OverlapOrTouch {
+                        assert!(!pair[0].is_adjacent_to(&pair[1]), "{merged:?}");
+                    }
+                }
+                assert!(merged.iter().all(|m| !m.is_empty()));
+            }
+
+            let set: IntervalSet<i32> = intervals.iter().copied().collect();
+            assert_eq!(merge_intervals(intervals.iter().copied(), MergePolicy::OverlapOrTouch), set.as_slice());
+            // Merging in place reuses the vector's memory
+            let mut in_place = intervals.clone();
+            let (capacity, pointer) = (in_place.capacity(), in_place.as_ptr());
+            merge_intervals_in_place(&mut in_place, MergePolicy::OverlapOrTouch);
+            assert_eq!(in_place, set.as_slice());
+            assert_eq!((in_place.capacity(), in_place.as_ptr()), (capacity, pointer));
+            // Each merged interval covers exactly one cluster
+            assert_eq!(merge_intervals(intervals.iter().copied(), MergePolicy::OverlapOnly).len(), cluster_overlapping(&intervals).iter().filter(|c| !intervals[c[0]].is_empty()).count());
+        }
+    }
+
+    #[test]
+    fn test_gaps() {
+        let points: Vec<f64> = (-2..=50).map(|i| i as f64 * 0.5).collect();
+        for seed in 0..200 {
+            let intervals = sample_collection(seed, (seed % 12) as usize);
+            let span = IntervalSet::from_iter(intervals.iter().copied()).span();
+            for within in [None, Some(Interval::excl(3, 17)), Some(Interval::incl(-1, 25)), Some(Interval::at_least(10))] {
+                let found = gaps(intervals.iter().copied(), within);
+                let window = within.or(span);
+                for &p in &points {
+                    let in_window = window.is_some_and(|w| contains(&w, p));
+                    let expected = in_window && !intervals.iter().any(|iv| contains(iv, p));
+                    assert_eq!(found.iter().any(|g| contains(g, p)), expected, "{intervals:?} in {within:?} at {p}");
+                }
+                assert!(found.windows(2).all(|w| w[0] < w[1] && !w[0].has_overlap(&w[1])), "{found:?}");
+            }
+        }
+        assert!(gaps(Vec::<Interval<i32>>::new(), None).is_empty());
+        assert_eq!(gaps(Vec::new(), Some(Interval::excl(1, 2))), [Interval::excl(1, 2)]);
+    }
+
+    #[test]
+    fn test_covered_length() {
+        for seed in 0..200 {
+            let intervals = sample_collection(seed, (seed % 12) as usize);
+            // End points are whole numbers, so each unit step is either wholly covered or not at all, and
+            // counts once if its midpoint is covered
+            let expected = if intervals.iter().any(|iv| !iv.is_empty() && iv.length().is_none()) {
+                None
+            } else {
+                Some((-5..30).filter(|&i| intervals.iter().any(|iv| contains(iv, i as f64 + 0.5))).count() as i32)
+            };
+            assert_eq!(covered_length(intervals.iter().copied()), expected, "{intervals:?}");
+        }
+        assert_eq!(covered_length(Vec::<Interval<f64>>::new()), Some(0.0));
+        assert_eq!(covered_length([Interval::excl(f64::NAN, 1.0), Interval::incl(2.0, 2.0)]), Some(0.0));
+    }
+
+    #[test]
+    fn test_max_overlap_depth() {
+        let points: Vec<f64> = (-12..=60).map(|i| i as f64 * 0.5).collect();
+        for seed in 0..300 {
+            let intervals = sample_collection(seed, (seed % 12) as usize);
+            let depth_at = |p: f64| intervals.iter().filter(|iv| contains(iv, p)).count();
+            let max = points.iter().map(|&p| depth_at(p)).max().unwrap();
+            match max_overlap_depth(intervals.iter().copied()) {
+                None => assert_eq!(max, 0, "{intervals:?}"),
+                Some((depth, at)) => {
+                    assert_eq!(depth, max, "{intervals:?}");
+                    assert!(!at.is_empty());
+                    // The depth is reached throughout the interval, and not just beyond either end of it
+                    for &p in &points {
+                        if contains(&at, p) {
+                            assert_eq!(depth_at(p), max, "{intervals:?}: {at:?} at {p}");
+                        }
+                    }
+                    let first = points.iter().position(|&p| depth_at(p) == max).unwrap();
+                    assert!(contains(&at, points[first]), "{intervals:?}: {at:?}");
+                    let last = points[first..].iter().position(|&p| depth_at(p) != max).map_or(points.len(), |i| i + first);
+                    if last < points.len() {
+                        assert!(!contains(&at, points[last]), "{intervals:?}: {at:?}");
+                    }
+                },
+            }
+        }
+        assert_eq!(max_overlap_depth([Interval::excl(1, 5), Interval::excl(5, 9)]), Some((1, Interval::excl(1, 9))));
+        assert_eq!(max_overlap_depth([Interval::incl(1, 5), Interval::incl(5, 9)]), Some((2, Interval::incl(5, 5))));
+        assert_eq!(max_overlap_depth([Interval::all(), Interval::greater_than(3)]), Some((2, Interval::greater_than(3))));
+        assert_eq!(max_overlap_depth([Interval::excl(1, 1)]), None);
+    }
+
+    #[test]
+    fn test_depth_profile() {
+        let points: Vec<f64> = (-12..=60).map(|i| i as f64 * 0.5).collect();
+        for seed in 0..300 {
+            let intervals = sample_collection(seed, (seed % 12) as usize);
+            let profile: Vec<(Interval<i32>, usize)> = depth_profile(intervals.iter().copied()).collect();
+            for pair in profile.windows(2) {
+                assert!(pair[0].0.is_adjacent_to(&pair[1].0) && pair[0].0 < pair[1].0, "{profile:?}");
+                assert_ne!(pair[0].1, pair[1].1, "{profile:?}");
+            }
+            let span = IntervalSet::from_iter(intervals.iter().copied()).span();
+            for &p in &points {
+                let in_profile: Vec<usize> = profile.iter().filter(|(iv, _)| contains(iv, p)).map(|(_, d)| *d).collect();
+                if span.is_some_and(|s| contains(&s, p)) {
+                    assert_eq!(in_profile, [intervals.iter().filter(|iv| contains(iv, p)).count()], "{intervals:?} at {p}");
+                } else {
+                    assert!(in_profile.is_empty(), "{intervals:?} at {p}");
+                }
+            }
+        }
+        assert_eq!(depth_profile([Interval::incl(1, 5), Interval::excl(5, 9)]).collect::<Vec<_>>(), [(Interval::excl(1, 5), 1), (Interval::incl(5, 5), 2), (Interval::from_bounds(Bound::Excluded(5), Bound::Excluded(9)), 1)]);
+        assert_eq!(depth_profile(Vec::<Interval<f64>>::new()).count(), 0);
+    }
+
+    #[test]
+    fn test_full_coverage() {
+        let points: Vec<f64> = (-2..=50).map(|i| i as f64 * 0.5).collect();
+        for seed in 0..200 {
+            let intervals = sample_collection(seed, (seed % 8) as usize);
+            for target in [Interval::excl(3, 9), Interval::incl(5, 6), Interval::greater_than(2), Interval::excl(4, 4)] {
+                let uncovered: Vec<f64> = points.iter().copied().filter(|&p| contains(&target, p) && !intervals.iter().any(|iv| contains(iv, p))).collect();
+                assert_eq!(is_fully_covered(&target, intervals.iter().copied()), uncovered.is_empty(), "{target:?} by {intervals:?}");
+                match first_uncovered(&target, intervals.iter().copied()) {
+                    None => assert!(uncovered.is_empty(), "{target:?} by {intervals:?}"),
+                    Some(gap) => assert_eq!(gap, gaps(intervals.iter().copied(), Some(target))[0]),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimal_cover() {
+        for seed in 0..300 {
+            let candidates = sample_collection(seed, (seed % 9) as usize);
+            for target in [Interval::excl(3, 9), Interval::incl(5, 6), Interval::greater_than(2), Interval::all(), Interval::excl(4, 4)] {
+                // The size of the smallest subset that covers the target, by trying every subset
+                let smallest = (0u32..1 << candidates.len())
+                    .filter(|mask| is_fully_covered(&target, (0..candidates.len()).filter(|i| mask & (1 << i) != 0).map(|i| candidates[i])))
+                    .map(|mask| mask.count_ones() as usize)
+                    .min();
+                match minimal_cover(&target, &candidates) {
+                    Ok(chosen) => {
+                        assert_eq!(Some(chosen.len()), smallest, "{target:?} by {candidates:?}");
+                        assert!(is_fully_covered(&target, chosen.iter().map(|&i| candidates[i])), "{target:?} by {candidates:?}");
+                        assert!(chosen.windows(2).all(|w| candidates[w[0]] <= candidates[w[1]]));
+                    },
+                    Err(err) => {
+                        assert_eq!(smallest, None, "{target:?} by {candidates:?}");
+                        assert_eq!(Some(err.gap), first_uncovered(&target, candidates.iter().copied()));
+                    },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_tracks() {
+        for seed in 0..300 {
+            let intervals = sample_collection(seed, (seed % 14) as usize);
+            let tracks = assign_tracks(&intervals);
+            assert_eq!(tracks.len(), intervals.len());
+            for i in 0..intervals.len() {
+                for j in i + 1..intervals.len() {
+                    if tracks[i] == tracks[j] {
+                        assert!(!intervals[i].has_overlap(&intervals[j]), "{intervals:?}: {tracks:?}");
+                    }
+                }
+            }
+            let n_tracks = tracks.iter().max().map_or(0, |t| t + 1);
+            let depth = max_overlap_depth(intervals.iter().copied()).map_or(0, |(d, _)| d);
+            assert_eq!(n_tracks, depth.max(intervals.len().min(1)), "{intervals:?}: {tracks:?}");
+        }
+    }
+
+    #[test]
+    fn test_max_disjoint_subset() {
+        for seed in 0..300 {
+            let intervals = sample_collection(seed, (seed % 11) as usize);
+            let chosen = max_disjoint_subset(&intervals);
+            for (n, &i) in chosen.iter().enumerate() {
+                assert!(!intervals[i].is_empty());
+                assert!(chosen[n + 1..].iter().all(|&j| !intervals[i].has_overlap(&intervals[j]) && intervals[i] < intervals[j]), "{intervals:?}: {chosen:?}");
+            }
+
+            let disjoint = |mask: u32| {
+                let members: Vec<usize> = (0..intervals.len()).filter(|i| mask & (1 << i) != 0).collect();
+                members.iter().all(|&i| !intervals[i].is_empty() && members.iter().all(|&j| i == j || !intervals[i].has_overlap(&intervals[j])))
+            };
+            let largest = (0u32..1 << intervals.len()).filter(|&mask| disjoint(mask)).map(|mask| mask.count_ones() as usize).max().unwrap();
+            assert_eq!(chosen.len(), largest, "{intervals:?}: {chosen:?}");
+        }
+    }
+
+    #[test]
+    fn test_interval_events() {
+        // Whether a sweep reaches `point` before it passes `event`
+        let before = |event: &IntervalEvent<i32>, point: f64| match (event.bound(), event.is_start()) {
+            (Bound::Included(&b), true) | (Bound::Excluded(&b), false) => point < b as f64,
+            (Bound::Excluded(&b), true) | (Bound::Included(&b), false) => point <= b as f64,
+            (Bound::Unbounded, is_start) => !is_start,
+        };
+
+        for seed in 0..300 {
+            let intervals = sample_collection(seed, (seed % 12) as usize);
+            let events = interval_events(intervals.iter().copied());
+            assert_eq!(events.len(), 2 * intervals.iter().filter(|iv| !iv.is_empty()).count());
+            for pair in events.windows(2) {
+                let same_place = pair[0].cut().partial_cmp(&pair[1].cut()) == Some(Ordering::Equal);
+                assert!(!(same_place && pair[0].is_start() && !pair[1].is_start()), "{events:?}");
+            }
+
+            // After each event, the intervals that have started but not ended are exactly those containing
+            // the values between it and the next event
+            let mut open: Vec<usize> = Vec::new();
+            for (n, event) in events.iter().enumerate() {
+                if event.is_start() {
+                    open.push(event.index());
+                } else {
+                    assert!(open.contains(&event.index()), "{intervals:?}: {events:?}");
+                    open.retain(|&i| i != event.index());
+                }
+                let Some(next) = events.get(n + 1) else { continue };
+                for p in (-10..60).map(|i| i as f64 * 0.5).filter(|&p| !before(event, p) && before(next, p)) {
+                    let mut found = open.clone();
+                    found.sort();
+                    let expected: Vec<usize> = (0..intervals.len()).filter(|&i| contains(&intervals[i], p)).collect();
+                    assert_eq!(found, expected, "{intervals:?}: {events:?} at {p}");
+                }
+            }
+            assert!(open.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_classify_matrix() {
+        for seed in 0..200 {
+            let a_list = sample_collection(seed, (seed % 10) as usize);
+            let b_list = sample_collection(seed + 1000, (seed % 7) as usize);
+            let matrix = classify_matrix(&a_list, &b_list);
+            let expected: Vec<Vec<RangeOverlap>> = a_list.iter().map(|a| b_list.iter().map(|b| a.classify_against(b)).collect()).collect();
+            assert_eq!(matrix, expected, "{a_list:?} vs {b_list:?}");
+        }
+        assert!(classify_matrix::<f64>(&[], &[Interval::all()]).is_empty());
+        assert_eq!(classify_matrix::<f64>(&[Interval::all()], &[]), [Vec::<RangeOverlap>::new()]);
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let masks = [None, Some(RangeOverlapMask::A_CONTAINS_B | RangeOverlapMask::A_EQUALS_B), Some(RangeOverlapMask::A_STARTS_IN_B), Some(RangeOverlapMask::NO_OVERLAP)];
+        for seed in 0..200 {
+            let intervals = sample_collection(seed, (seed % 16) as usize);
+            for mask in masks {
+                let mut expected = Vec::new();
+                for i in 0..intervals.len() {
+                    for j in i + 1..intervals.len() {
+                        let relation = intervals[i].classify_against(&intervals[j]);
+                        if relation.has_overlap() && mask.is_none_or(|m| relation.matches(m)) {
+                            expected.push((i, j));
+                        }
+                    }
+                }
+                assert_eq!(find_conflicts(&intervals, mask), expected, "{intervals:?} with {mask:?}");
+            }
+        }
+    }
+}
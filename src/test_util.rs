@@ -0,0 +1,77 @@
+//! Fixtures shared by the unit tests: a repeatable source of scrambled numbers, and a catalogue of small
+//! intervals of every kind with points to check them at.
+use std::ops::Bound;
+
+use crate::Interval;
+
+/// A linear congruential generator, so tests can scramble their inputs repeatably without a dependency.
+pub struct Lcg(u32);
+
+impl Lcg {
+    pub fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+
+    /// The next number, dropping the low bits, which repeat with a short period.
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 = self.0.wrapping_mul(1103515245).wrapping_add(12345);
+        self.0 >> 8
+    }
+
+    /// A number from `0` up to, but not including, `n`.
+    pub fn below(&mut self, n: u32) -> i32 {
+        (self.next_u32() % n) as i32
+    }
+
+    /// One of `items`, which must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next_u32() as usize % items.len()]
+    }
+}
+
+/// A scrambled interval starting below `max_start` and running up to `max_len - 2` past its start. One in four
+/// is open on one side, and a finite interval may be empty or backwards by one.
+pub fn random_interval(rng: &mut Lcg, max_start: u32, max_len: u32) -> Interval<i32> {
+    let start = rng.below(max_start);
+    let end = start + rng.below(max_len) - 1;
+    match rng.below(16) {
+        0 => Interval::at_least(start),
+        1 => Interval::greater_than(start),
+        2 => Interval::at_most(start),
+        3 => Interval::less_than(start),
+        4 => Interval::incl(start, end),
+        5 => Interval::from_bounds(Bound::Excluded(start), Bound::Included(end)),
+        6 => Interval::from_bounds(Bound::Excluded(start), Bound::Excluded(end)),
+        _ => Interval::excl(start, end),
+    }
+}
+
+/// Every interval with finite ends in `0..n`, including empty and backwards ones, plus the open intervals.
+pub fn sample_intervals<T: From<u8> + Copy>(n: u8) -> Vec<Interval<T>> {
+    let mut intervals = vec![Interval::all()];
+    for start in (0..n).map(T::from) {
+        intervals.push(Interval::at_least(start));
+        intervals.push(Interval::greater_than(start));
+        intervals.push(Interval::at_most(start));
+        intervals.push(Interval::less_than(start));
+        for end in (0..n).map(T::from) {
+            for s in [Bound::Included(start), Bound::Excluded(start)] {
+                for e in [Bound::Included(end), Bound::Excluded(end)] {
+                    intervals.push(Interval::from_bounds(s, e));
+                }
+            }
+        }
+    }
+    intervals
+}
+
+/// Points to check membership at for [`sample_intervals`]: every end point, and halfway between each pair,
+/// from one below the smallest to one above the largest.
+pub fn sample_points(n: u8) -> Vec<f64> {
+    (-2..=2 * i32::from(n)).map(|i| i as f64 * 0.5).collect()
+}
+
+/// Whether `point`, which may be between two integers, is in `interval`.
+pub fn contains(interval: &Interval<i32>, point: f64) -> bool {
+    Interval::from_bounds(interval.start().map(|&v| v as f64), interval.end().map(|&v| v as f64)).contains_point(&point)
+}
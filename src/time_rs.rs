@@ -0,0 +1,194 @@
+//! Conveniences for intervals of dates and times from the `time` crate, matching those for `chrono`.
+//!
+//! The constructors are named differently from the `chrono` ones, so that a call like
+//! `Interval::from_dates(..)` is never ambiguous when both features are on.
+use std::ops::{Bound, Sub};
+
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset, Weekday};
+
+use crate::Interval;
+
+/// How long `a` and `b` overlap for, which is zero if they do not overlap at all.
+fn overlap_duration<T: PartialOrd + Clone + Sub<Output = Duration>>(a: &Interval<T>, b: &Interval<T>) -> Option<Duration> {
+    match a.intersect(b) {
+        Some(both) => both.length(),
+        None => Some(Duration::ZERO),
+    }
+}
+
+impl Interval<Date> {
+    /// The times covered by the days in this interval, from midnight at the start of its first day to
+    /// midnight at the end of its last. Only available with the `time` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use time::macros::{date, datetime};
+    ///
+    /// let times = Interval::incl(date!(2024-03-01), date!(2024-03-03)).to_datetimes();
+    /// assert_eq!(times, Interval::excl(datetime!(2024-03-01 0:00), datetime!(2024-03-04 0:00)));
+    /// ```
+    pub fn to_datetimes(&self) -> Interval<PrimitiveDateTime> {
+        // The day after the last date the time crate can represent can't be written as a midnight, so
+        // those bounds stop at the last time it can represent instead
+        let start = match self.start() {
+            Bound::Included(&first) => Bound::Included(first.midnight()),
+            Bound::Excluded(&before) => before.next_day().map_or(Bound::Excluded(PrimitiveDateTime::MAX), |first| Bound::Included(first.midnight())),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end = match self.end() {
+            Bound::Included(&last) => last.next_day().map_or(Bound::Included(PrimitiveDateTime::MAX), |after| Bound::Excluded(after.midnight())),
+            Bound::Excluded(&after) => Bound::Excluded(after.midnight()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        Interval::from_bounds(start, end)
+    }
+
+    /// How much time the days in this interval and `other` have in common, counting each day as
+    /// [`to_datetimes`](Interval::to_datetimes) does. The result is zero if they have no days in common,
+    /// and `None` if the days in common go on forever. Only available with the `time` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use time::{macros::date, Duration};
+    ///
+    /// let trip = Interval::incl(date!(2024-07-01), date!(2024-07-10));
+    /// assert_eq!(trip.overlap_duration(&Interval::incl(date!(2024-07-09), date!(2024-07-20))), Some(Duration::days(2)));
+    /// ```
+    pub fn overlap_duration(&self, other: &Interval<Date>) -> Option<Duration> {
+        overlap_duration(&self.to_datetimes(), &other.to_datetimes())
+    }
+
+    /// The days of ISO 8601 week `week` of `year`, Monday to Sunday, or `None` if the year has no such
+    /// week. The first week of a year is the one with its first Thursday in it, so it can start in the
+    /// December before. Only available with the `time` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use time::macros::date;
+    ///
+    /// assert_eq!(Interval::iso_week(2025, 1), Some(Interval::incl(date!(2024-12-30), date!(2025-01-05))));
+    /// assert_eq!(Interval::iso_week(2025, 53), None);
+    /// ```
+    pub fn iso_week(year: i32, week: u8) -> Option<Self> {
+        let monday = Date::from_iso_week_date(year, week, Weekday::Monday).ok()?;
+        let sunday = Date::from_iso_week_date(year, week, Weekday::Sunday).ok()?;
+        Some(Interval::incl(monday, sunday))
+    }
+
+    /// The days of `month` in `year`, from the 1st to the last, or `None` if the month is outside the
+    /// dates the time crate can represent. Only available with the `time` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use time::{macros::date, Month};
+    ///
+    /// assert_eq!(Interval::month(2024, Month::February), Some(Interval::incl(date!(2024-02-01), date!(2024-02-29))));
+    /// ```
+    pub fn month(year: i32, month: Month) -> Option<Self> {
+        let first = Date::from_calendar_date(year, month, 1).ok()?;
+        let next_year = if month == Month::December { year.checked_add(1)? } else { year };
+        let last = match Date::from_calendar_date(next_year, month.next(), 1) {
+            Ok(next_first) => next_first.previous_day()?,
+            // The last month the time crate can represent
+            Err(_) => Date::from_calendar_date(year, month, 31).ok()?,
+        };
+        Some(Interval::incl(first, last))
+    }
+}
+
+impl Interval<PrimitiveDateTime> {
+    /// The times on the days from `first` to `last`, both included: from midnight at the start of
+    /// `first` to midnight at the end of `last`. Only available with the `time` feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use time::macros::{date, datetime};
+    ///
+    /// let week = Interval::from_date_range(date!(2024-07-01), date!(2024-07-07));
+    /// assert!(week.contains_point(&datetime!(2024-07-07 18:30)));
+    /// assert!(!week.contains_point(&datetime!(2024-07-08 0:00)));
+    /// ```
+    pub fn from_date_range(first: Date, last: Date) -> Self {
+        Interval::incl(first, last).to_datetimes()
+    }
+
+    /// How long this interval and `other` overlap for: zero if they do not overlap, and `None` if the
+    /// overlap goes on forever. Only available with the `time` feature.
+    pub fn overlap_duration(&self, other: &Interval<PrimitiveDateTime>) -> Option<Duration> {
+        overlap_duration(self, other)
+    }
+}
+
+impl Interval<OffsetDateTime> {
+    /// The times on the days from `first` to `last` at UTC offset `offset`, both included: from midnight
+    /// at the start of `first` to midnight at the end of `last`, local time. Only available with the `time`
+    /// feature.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// use time::{macros::{date, datetime, offset}, Duration};
+    ///
+    /// let stay = Interval::from_date_range_at(offset!(+9), date!(2024-07-01), date!(2024-07-03));
+    /// assert!(stay.contains_point(&datetime!(2024-07-03 23:00 +9)));
+    /// assert_eq!(stay.length(), Some(Duration::days(3)));
+    /// ```
+    pub fn from_date_range_at(offset: UtcOffset, first: Date, last: Date) -> Self {
+        let (start, end) = Interval::<PrimitiveDateTime>::from_date_range(first, last).into_bounds();
+        Interval::from_bounds(start.map(|t| t.assume_offset(offset)), end.map(|t| t.assume_offset(offset)))
+    }
+
+    /// How long this interval and `other` overlap for: zero if they do not overlap, and `None` if the
+    /// overlap goes on forever. Only available with the `time` feature.
+    pub fn overlap_duration(&self, other: &Interval<OffsetDateTime>) -> Option<Duration> {
+        overlap_duration(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime, offset};
+
+    #[test]
+    fn test_to_datetimes() {
+        let (first, last) = (date!(2024-02-28), date!(2024-03-01));
+        assert_eq!(Interval::incl(first, last).to_datetimes(), Interval::excl(datetime!(2024-02-28 0:00), datetime!(2024-03-02 0:00)));
+        assert_eq!(Interval::excl(first, last).to_datetimes(), Interval::excl(datetime!(2024-02-28 0:00), datetime!(2024-03-01 0:00)));
+        assert_eq!(Interval::greater_than(first).to_datetimes(), Interval::at_least(datetime!(2024-02-29 0:00)));
+        assert_eq!(Interval::incl(Date::MAX, Date::MAX).to_datetimes(), Interval::incl(Date::MAX.midnight(), PrimitiveDateTime::MAX));
+        assert_eq!(Interval::from_date_range(first, last), Interval::incl(first, last).to_datetimes());
+    }
+
+    #[test]
+    fn test_overlap_duration() {
+        let days = Interval::incl(date!(2024-03-01), date!(2024-03-10));
+        assert_eq!(days.overlap_duration(&Interval::excl(date!(2024-03-05), date!(2024-03-08))), Some(Duration::days(3)));
+        assert_eq!(days.overlap_duration(&Interval::greater_than(date!(2024-03-10))), Some(Duration::ZERO));
+        assert_eq!(Interval::at_least(date!(2024-03-01)).overlap_duration(&Interval::all()), None);
+
+        let evening = Interval::excl(datetime!(2024-03-01 18:00), datetime!(2024-03-02 2:00));
+        assert_eq!(Interval::from_date_range(date!(2024-03-01), date!(2024-03-01)).overlap_duration(&evening), Some(Duration::hours(6)));
+
+        // The same day at different offsets overlaps by the difference in offsets less than a day
+        let utc = Interval::from_date_range_at(UtcOffset::UTC, date!(2024-03-01), date!(2024-03-01));
+        let east = Interval::from_date_range_at(offset!(+3), date!(2024-03-01), date!(2024-03-01));
+        assert_eq!(utc.overlap_duration(&east), Some(Duration::hours(21)));
+    }
+
+    #[test]
+    fn test_calendar_helpers() {
+        // Every day of 2020 to 2026 is in exactly one ISO week and one month
+        let mut day = date!(2020-01-01);
+        while day.year() < 2027 {
+            let (year, week, _) = day.to_iso_week_date();
+            let weeks = (1..=53).filter(|&w| Interval::iso_week(year, w).is_some_and(|iv| iv.contains_point(&day))).collect::<Vec<_>>();
+            assert_eq!(weeks, [week], "{day}");
+            let month = Interval::month(day.year(), day.month()).unwrap();
+            assert!(month.contains_point(&day), "{day}");
+            assert_eq!(month.overlap_duration(&month), Some(Duration::days(day.month().length(day.year()) as i64)));
+            day = day.next_day().unwrap();
+        }
+        assert_eq!(Interval::month(2023, Month::December), Some(Interval::incl(date!(2023-12-01), date!(2023-12-31))));
+        assert!(Interval::month(Date::MAX.year(), Month::December).is_some());
+    }
+}
@@ -0,0 +1,95 @@
+//! Overlaps between spans of `std::time` points, given as `(start, end)` tuples.
+use std::time::{Duration, Instant, SystemTime};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for Instant {}
+impl sealed::Sealed for SystemTime {}
+
+/// A point in time from `std::time`: [`Instant`] or [`SystemTime`]. This trait is sealed, so it cannot
+/// be implemented outside this crate.
+pub trait TimePoint: sealed::Sealed + Ord + Copy {
+    /// How long after this point `later` is, or zero if it is not after it.
+    fn duration_until(self, later: Self) -> Duration;
+}
+
+impl TimePoint for Instant {
+    fn duration_until(self, later: Self) -> Duration {
+        later.saturating_duration_since(self)
+    }
+}
+
+impl TimePoint for SystemTime {
+    fn duration_until(self, later: Self) -> Duration {
+        later.duration_since(self).unwrap_or(Duration::ZERO)
+    }
+}
+
+/// How long the spans `a` and `b`, each given as `(start, end)`, overlap for, as a [`Duration`]. Each span
+/// holds the times from its start up to, but not including, its end. Spans that do not overlap, or whose
+/// end is not after their start, give zero, so there is no subtracting or checking signs to be done.
+///
+/// ```
+/// # use range_overlap::span_overlap;
+/// use std::time::{Duration, Instant};
+///
+/// let t0 = Instant::now();
+/// let request = (t0, t0 + Duration::from_millis(300));
+/// let gc_pause = (t0 + Duration::from_millis(250), t0 + Duration::from_millis(400));
+/// assert_eq!(span_overlap(request, gc_pause), Duration::from_millis(50));
+/// assert_eq!(span_overlap(request, (t0 + Duration::from_secs(1), t0 + Duration::from_secs(2))), Duration::ZERO);
+/// ```
+pub fn span_overlap<T: TimePoint>(a: (T, T), b: (T, T)) -> Duration {
+    let start = a.0.max(b.0);
+    let end = a.1.min(b.1);
+    start.duration_until(end)
+}
+
+/// Returns `true` if the spans `a` and `b`, each given as `(start, end)`, share any time, i.e. if
+/// [`span_overlap`] would be more than zero. Spans that only touch, with one ending where the other
+/// starts, do not overlap, and nor does a span whose end is not after its start.
+///
+/// ```
+/// # use range_overlap::has_span_overlap;
+/// use std::time::{Duration, SystemTime};
+///
+/// let noon = SystemTime::UNIX_EPOCH + Duration::from_secs(12 * 3600);
+/// let hour = Duration::from_secs(3600);
+/// assert!(has_span_overlap((noon, noon + 2 * hour), (noon + hour, noon + 3 * hour)));
+/// assert!(!has_span_overlap((noon, noon + hour), (noon + hour, noon + 2 * hour)));
+/// ```
+pub fn has_span_overlap<T: TimePoint>(a: (T, T), b: (T, T)) -> bool {
+    a.0.max(b.0) < a.1.min(b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interval;
+
+    #[test]
+    fn test_matches_intervals() {
+        let t0 = Instant::now();
+        let epoch = SystemTime::UNIX_EPOCH;
+        let secs = Duration::from_secs;
+        // Every pair of spans with ends drawn from 0..5 seconds, including empty and backwards ones
+        for a_start in 0..5 {
+            for a_end in 0..5 {
+                for b_start in 0..5 {
+                    for b_end in 0..5 {
+                        let (a, b) = ((a_start, a_end), (b_start, b_end));
+                        let overlap = Interval::excl(a_start, a_end).intersect(&Interval::excl(b_start, b_end)).map_or(0, |iv| iv.length().unwrap());
+                        let instants = |(s, e): (u64, u64)| (t0 + secs(s), t0 + secs(e));
+                        assert_eq!(span_overlap(instants(a), instants(b)), secs(overlap), "{a:?} {b:?}");
+                        assert_eq!(has_span_overlap(instants(a), instants(b)), overlap > 0, "{a:?} {b:?}");
+                        let times = |(s, e): (u64, u64)| (epoch + secs(s), epoch + secs(e));
+                        assert_eq!(span_overlap(times(a), times(b)), secs(overlap), "{a:?} {b:?}");
+                        assert_eq!(has_span_overlap(times(a), times(b)), overlap > 0, "{a:?} {b:?}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Moving and resizing intervals with numeric end points. Open sides stay open.
+use std::ops::{Add, Mul, Sub};
+
+use crate::Interval;
+
+/// Shift an interval later by `rhs`.
+///
+/// ```
+/// # use range_overlap::Interval;
+/// assert_eq!(Interval::excl(1, 5) + 10, Interval::excl(11, 15));
+/// assert_eq!(Interval::at_most(2.5) + 0.5, Interval::at_most(3.0));
+/// ```
+impl<T: Clone + Add<Output = T>> Add<T> for Interval<T> {
+    type Output = Interval<T>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        let (start, end) = self.into_bounds();
+        Interval::from_bounds(start.map(|s| s + rhs.clone()), end.map(|e| e + rhs))
+    }
+}
+
+/// Shift an interval earlier by `rhs`.
+///
+/// ```
+/// # use range_overlap::Interval;
+/// assert_eq!(Interval::excl(11, 15) - 10, Interval::excl(1, 5));
+/// assert_eq!(Interval::<i32>::all() - 10, Interval::all());
+/// ```
+impl<T: Clone + Sub<Output = T>> Sub<T> for Interval<T> {
+    type Output = Interval<T>;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        let (start, end) = self.into_bounds();
+        Interval::from_bounds(start.map(|s| s - rhs.clone()), end.map(|e| e - rhs))
+    }
+}
+
+impl<T: Clone + Add<Output = T> + Sub<Output = T>> Interval<T> {
+    /// Stretch this interval away from `center` by `factor`, so that each end point `x` moves to
+    /// `center + (x - center) * factor`. A negative factor also mirrors the interval about `center`,
+    /// so its start and end (and whether each is included) swap places.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(2.0, 4.0).scale_about(3.0, 2.0), Interval::excl(1.0, 5.0));
+    /// assert_eq!(Interval::excl(10, 20).scale_about(0, 3), Interval::excl(30, 60));
+    /// assert_eq!(Interval::at_least(4).scale_about(3, 2), Interval::at_least(5));
+    ///
+    /// use std::ops::Bound;
+    /// assert_eq!(Interval::excl(1, 2).scale_about(0, -1), Interval::from_bounds(Bound::Excluded(-2), Bound::Included(-1)));
+    /// ```
+    pub fn scale_about<F: PartialOrd + Default + Copy>(&self, center: T, factor: F) -> Interval<T>
+    where
+        T: Mul<F, Output = T>,
+    {
+        let scale = |x: &T| center.clone() + (x.clone() - center.clone()) * factor;
+        let start = self.start().map(scale);
+        let end = self.end().map(scale);
+        if factor < F::default() {
+            Interval::from_bounds(end, start)
+        } else {
+            Interval::from_bounds(start, end)
+        }
+    }
+}
+
+impl<T: Clone + Add<Output = T> + Sub<Output = T>> Interval<T> {
+    /// Move the start of this interval earlier and the end later by `delta`, e.g. to add a buffer
+    /// around it before checking for overlap. Open sides stay open, and whether each end is included
+    /// does not change.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(10, 20).expanded(5), Interval::excl(5, 25));
+    /// assert_eq!(Interval::at_most(1.0).expanded(0.5), Interval::at_most(1.5));
+    /// ```
+    pub fn expanded(&self, delta: T) -> Interval<T> {
+        let start = self.start().map(|s| s.clone() - delta.clone());
+        let end = self.end().map(|e| e.clone() + delta);
+        Interval::from_bounds(start, end)
+    }
+
+    /// Move the start of this interval later and the end earlier by `delta`, the opposite of
+    /// [`Interval::expanded`]. Returns `None` if that leaves no values in the interval.
+    ///
+    /// ```
+    /// # use range_overlap::Interval;
+    /// assert_eq!(Interval::excl(10, 20).shrunk(5), None);
+    /// assert_eq!(Interval::incl(10, 20).shrunk(5), Some(Interval::point(15)));
+    /// assert_eq!(Interval::excl(10, 20).shrunk(2), Some(Interval::excl(12, 18)));
+    /// assert_eq!(Interval::greater_than(10).shrunk(100), Some(Interval::greater_than(110)));
+    /// ```
+    pub fn shrunk(&self, delta: T) -> Option<Interval<T>>
+    where
+        T: PartialOrd,
+    {
+        let start = self.start().map(|s| s.clone() + delta.clone());
+        let end = self.end().map(|e| e.clone() - delta);
+        let shrunk = Interval::from_bounds(start, end);
+        (!shrunk.is_empty()).then_some(shrunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Bound;
+
+    #[test]
+    fn test_shift() {
+        for start in [Bound::Included(1), Bound::Excluded(1), Bound::Unbounded] {
+            for end in [Bound::Included(5), Bound::Excluded(5), Bound::Unbounded] {
+                let a = Interval::from_bounds(start, end);
+                let shifted = a + 3;
+                for p in -2..10 {
+                    assert_eq!(shifted.contains_point(&(p + 3)), a.contains_point(&p), "{a:?} + 3 at {p}");
+                }
+                assert_eq!(shifted - 3, a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_expand_and_shrink() {
+        for start in [Bound::Included(1), Bound::Excluded(1), Bound::Unbounded] {
+            for end in [Bound::Included(5), Bound::Excluded(5), Bound::Unbounded] {
+                let a = Interval::from_bounds(start, end);
+                for delta in 0..4 {
+                    let expanded = a.expanded(delta);
+                    let shrunk = a.shrunk(delta);
+                    for p in -5..12 {
+                        // A point is in the expanded interval if it is within `delta` of one in the
+                        // original, and in the shrunk interval if everything within `delta` of it is
+                        let near = || p - delta..=p + delta;
+                        assert_eq!(expanded.contains_point(&p), near().any(|q| a.contains_point(&q)), "{a:?} expanded by {delta} at {p}");
+                        assert_eq!(shrunk.is_some_and(|r| r.contains_point(&p)), near().all(|q| a.contains_point(&q)), "{a:?} shrunk by {delta} at {p}");
+                    }
+                    assert_eq!(expanded.shrunk(delta), Some(a));
+                }
+            }
+        }
+
+        assert_eq!(Interval::excl(1, 5).shrunk(3), None);
+        assert_eq!(Interval::excl(1.0, 5.0).shrunk(2.0), None);
+    }
+
+    #[test]
+    fn test_scale_about() {
+        for start in [Bound::Included(1), Bound::Excluded(1), Bound::Unbounded] {
+            for end in [Bound::Included(5), Bound::Excluded(5), Bound::Unbounded] {
+                let a = Interval::from_bounds(start, end);
+                for center in -2..8 {
+                    for factor in [-3, -1, 1, 2] {
+                        let scaled = a.scale_about(center, factor);
+                        for p in -2..10 {
+                            let moved = center + (p - center) * factor;
+                            assert_eq!(scaled.contains_point(&moved), a.contains_point(&p), "{a:?} scaled by {factor} about {center} at {p}");
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(Interval::excl(1.0, 5.0).scale_about(2.0, 0.5), Interval::excl(1.5, 3.5));
+        assert_eq!(Interval::excl(1, 5).scale_about(2, 0), Interval::excl(2, 2));
+    }
+}
@@ -0,0 +1,298 @@
+//! An interval tree, for finding which of many stored intervals overlap a point or another interval
+//! without checking every one.
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::bounds;
+use crate::Interval;
+
+#[derive(Debug, Clone)]
+struct Node<T, V> {
+    interval: Interval<T>,
+    value: V,
+    /// The latest end of any interval in this subtree
+    max_end: Bound<T>,
+    height: usize,
+    left: Option<Box<Node<T, V>>>,
+    right: Option<Box<Node<T, V>>>,
+}
+
+fn height<T, V>(node: &Option<Box<Node<T, V>>>) -> usize {
+    node.as_ref().map_or(0, |n| n.height)
+}
+
+impl<T: PartialOrd + Clone, V> Node<T, V> {
+    fn leaf(interval: Interval<T>, value: V) -> Box<Self> {
+        let max_end = interval.end().cloned();
+        Box::new(Self { interval, value, max_end, height: 1, left: None, right: None })
+    }
+
+    /// Recompute this node's height and maximum end from its children.
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        let mut max_end = self.interval.end();
+        for child in [&self.left, &self.right].into_iter().flatten() {
+            // Stored intervals are never empty, so their end points can always be compared
+            max_end = bounds::later_end(max_end, child.max_end.as_ref()).unwrap_or(max_end);
+        }
+        self.max_end = max_end.cloned();
+    }
+
+    fn rotate_left(mut self: Box<Self>) -> Box<Self> {
+        let mut right = self.right.take().expect("rotating left needs a right child");
+        self.right = right.left.take();
+        self.update();
+        right.left = Some(self);
+        right.update();
+        right
+    }
+
+    fn rotate_right(mut self: Box<Self>) -> Box<Self> {
+        let mut left = self.left.take().expect("rotating right needs a left child");
+        self.left = left.right.take();
+        self.update();
+        left.right = Some(self);
+        left.update();
+        left
+    }
+
+    /// Restore the AVL balance of this node after one of its subtrees grew by one level.
+    fn rebalance(mut self: Box<Self>) -> Box<Self> {
+        self.update();
+        let (left, right) = (height(&self.left), height(&self.right));
+        if left > right + 1 {
+            let child = self.left.take().unwrap();
+            self.left = Some(if height(&child.left) < height(&child.right) { child.rotate_left() } else { child });
+            self.rotate_right()
+        } else if right > left + 1 {
+            let child = self.right.take().unwrap();
+            self.right = Some(if height(&child.right) < height(&child.left) { child.rotate_right() } else { child });
+            self.rotate_left()
+        } else {
+            self
+        }
+    }
+
+    fn insert(node: Option<Box<Self>>, new: Box<Self>) -> Box<Self> {
+        let Some(mut node) = node else { return new };
+        if new.interval.partial_cmp(&node.interval) == Some(Ordering::Less) {
+            node.left = Some(Self::insert(node.left.take(), new));
+        } else {
+            node.right = Some(Self::insert(node.right.take(), new));
+        }
+        node.rebalance()
+    }
+
+    fn find_overlapping<'a>(&'a self, query: &Interval<T>, found: &mut Vec<(&'a Interval<T>, &'a V)>) {
+        // Nothing in this subtree ends late enough to reach the query
+        if bounds::ends_before(self.max_end.as_ref(), query.start()) != Some(false) {
+            return;
+        }
+        if let Some(left) = &self.left {
+            left.find_overlapping(query, found);
+        }
+        if self.interval.has_overlap(query) {
+            found.push((&self.interval, &self.value));
+        }
+        // Everything to the right starts no earlier than this node, so if the query ends before
+        // this node starts, it ends before all of them too
+        if bounds::ends_before(query.end(), self.interval.start()) == Some(false) {
+            if let Some(right) = &self.right {
+                right.find_overlapping(query, found);
+            }
+        }
+    }
+
+    fn collect<'a>(&'a self, found: &mut Vec<(&'a Interval<T>, &'a V)>) {
+        if let Some(left) = &self.left {
+            left.collect(found);
+        }
+        found.push((&self.interval, &self.value));
+        if let Some(right) = &self.right {
+            right.collect(found);
+        }
+    }
+}
+
+/// A collection of intervals, each with a value, that can quickly find the intervals overlapping a
+/// point or another interval. Unlike [`IntervalMap`](crate::IntervalMap), the stored intervals may
+/// overlap each other, and the same interval can be stored more than once.
+///
+/// This is a balanced binary search tree ordered by interval, where each node also records the latest
+/// end of any interval below it. A query then only has to visit the parts of the tree that can hold
+/// overlapping intervals, taking `O(log n + k)` time to find `k` matches among `n` intervals, rather than
+/// the `O(n)` of classifying every stored interval.
+///
+/// ```
+/// # use range_overlap::{Interval, IntervalTree};
+/// let mut log_files = IntervalTree::new();
+/// log_files.insert(Interval::excl(0, 100), "a.log");
+/// log_files.insert(Interval::excl(50, 150), "b.log");
+/// log_files.insert(Interval::excl(200, 300), "c.log");
+///
+/// let at_75: Vec<_> = log_files.query_point(&75).into_iter().map(|(_, name)| *name).collect();
+/// assert_eq!(at_75, ["a.log", "b.log"]);
+/// let names: Vec<_> = log_files.query_overlapping(&Interval::excl(120, 250)).into_iter().map(|(_, name)| *name).collect();
+/// assert_eq!(names, ["b.log", "c.log"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T, V> {
+    root: Option<Box<Node<T, V>>>,
+    len: usize,
+}
+
+impl<T, V> IntervalTree<T, V> {
+    /// Create an empty tree.
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// Returns `true` if no intervals are stored in this tree.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of intervals stored in this tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T, V> Default for IntervalTree<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Clone, V> IntervalTree<T, V> {
+    /// Store `interval` with `value`. An empty interval (including one whose end points cannot be
+    /// compared) would never be found by a query, so it is not stored.
+    pub fn insert(&mut self, interval: Interval<T>, value: V) {
+        if interval.is_empty() {
+            return;
+        }
+        self.root = Some(Node::insert(self.root.take(), Node::leaf(interval, value)));
+        self.len += 1;
+    }
+
+    /// The stored intervals that overlap `interval`, with their values, ordered by interval.
+    pub fn query_overlapping(&self, interval: &Interval<T>) -> Vec<(&Interval<T>, &V)> {
+        let mut found = Vec::new();
+        if let Some(root) = &self.root {
+            root.find_overlapping(interval, &mut found);
+        }
+        found
+    }
+
+    /// The stored intervals that contain `point`, with their values, ordered by interval.
+    pub fn query_point(&self, point: &T) -> Vec<(&Interval<T>, &V)> {
+        self.query_overlapping(&Interval::point(point.clone()))
+    }
+
+    /// The stored intervals that contain `point`, ordered by interval: the same as [`query_point`](Self::query_point).
+    pub fn stab(&self, point: &T) -> Vec<(&Interval<T>, &V)> {
+        self.query_point(point)
+    }
+
+    /// All of the stored intervals with their values, ordered by interval.
+    pub fn to_vec(&self) -> Vec<(&Interval<T>, &V)> {
+        let mut found = Vec::with_capacity(self.len);
+        if let Some(root) = &self.root {
+            root.collect(&mut found);
+        }
+        found
+    }
+}
+
+impl<T: PartialOrd + Clone, V> FromIterator<(Interval<T>, V)> for IntervalTree<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, V)>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        for (interval, value) in iter {
+            tree.insert(interval, value);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Lcg, random_interval, sample_intervals};
+
+    /// Check the AVL balance and maximum end of every node, returning the subtree's height
+    fn check_node(node: &Option<Box<Node<i32, usize>>>) -> usize {
+        let Some(node) = node else { return 0 };
+        let (left, right) = (check_node(&node.left), check_node(&node.right));
+        assert!(left.abs_diff(right) <= 1);
+        assert_eq!(node.height, 1 + left.max(right));
+
+        let mut ends = vec![node.interval.end()];
+        ends.extend([&node.left, &node.right].into_iter().flatten().map(|c| c.max_end.as_ref()));
+        assert!(ends.iter().all(|&e| bounds::cmp_ends(e, node.max_end.as_ref()) != Some(Ordering::Greater)));
+        assert!(ends.contains(&node.max_end.as_ref()));
+        node.height
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let mut intervals = Vec::new();
+        let mut rng = Lcg::new(97531);
+        for _ in 0..500 {
+            intervals.push(random_interval(&mut rng, 200, 20));
+        }
+
+        let tree: IntervalTree<i32, usize> = intervals.iter().copied().zip(0..).collect();
+        let height = check_node(&tree.root);
+        assert!(height <= 2 * (tree.len() as f64).log2().ceil() as usize, "height {height} for {} intervals", tree.len());
+
+        let stored: Vec<(Interval<i32>, usize)> = intervals.iter().copied().zip(0..).filter(|(iv, _)| !iv.is_empty()).collect();
+        assert_eq!(tree.len(), stored.len());
+        let mut expected_all = stored.clone();
+        expected_all.sort_by_key(|(iv, _)| *iv);
+        assert_eq!(tree.to_vec().into_iter().map(|(iv, _)| *iv).collect::<Vec<_>>(), expected_all.iter().map(|(iv, _)| *iv).collect::<Vec<_>>());
+
+        let sorted = |mut v: Vec<usize>| {
+            v.sort();
+            v
+        };
+        for start in -5..230 {
+            let expected: Vec<usize> = stored.iter().filter(|(iv, _)| iv.contains_point(&start)).map(|(_, v)| *v).collect();
+            let found = tree.query_point(&start).into_iter().map(|(_, &v)| v).collect();
+            assert_eq!(sorted(found), sorted(expected), "at {start}");
+
+            for query in [Interval::excl(start, start + 7), Interval::incl(start, start), Interval::greater_than(start), Interval::at_most(start)] {
+                let expected: Vec<usize> = stored.iter().filter(|(iv, _)| iv.has_overlap(&query)).map(|(_, v)| *v).collect();
+                let found: Vec<(&Interval<i32>, &usize)> = tree.query_overlapping(&query);
+                assert!(found.windows(2).all(|w| w[0].0 <= w[1].0));
+                assert_eq!(sorted(found.into_iter().map(|(_, &v)| v).collect()), sorted(expected), "{query:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_intervals() {
+        let mut tree = IntervalTree::new();
+        tree.insert(Interval::excl(5, 5), 'a');
+        tree.insert(Interval::incl(6, 5), 'b');
+        assert!(tree.is_empty());
+        assert!(tree.query_overlapping(&Interval::all()).is_empty());
+
+        let mut tree = IntervalTree::new();
+        tree.insert(Interval::excl(f64::NAN, 5.0), 'a');
+        tree.insert(Interval::excl(1.0, 5.0), 'b');
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.query_point(&2.0), [(&Interval::excl(1.0, 5.0), &'b')]);
+    }
+
+    #[test]
+    fn test_stab() {
+        let stored: Vec<(Interval<i32>, usize)> = sample_intervals(5).into_iter().zip(0..).collect();
+        let tree: IntervalTree<i32, usize> = stored.iter().copied().collect();
+        for point in -1..=5 {
+            let expected: Vec<usize> = stored.iter().filter(|(iv, _)| iv.contains_point(&point)).map(|(_, v)| *v).collect();
+            let mut found: Vec<usize> = tree.stab(&point).into_iter().map(|(_, &v)| v).collect();
+            found.sort();
+            assert_eq!(found, expected, "at {point}");
+        }
+    }
+}
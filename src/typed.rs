@@ -0,0 +1,293 @@
+//! Intervals whose inclusivity is part of their type.
+//!
+//! [`crate::Interval`] decides whether each end is included at runtime, which means nothing
+//! stops one part of a program building `[start, end)` intervals and another building
+//! `[start, end]` intervals, then comparing the two. The [`Interval`] in this module instead
+//! takes two marker types, one for each end, so intervals following different conventions
+//! are different types and cannot be classified against each other by accident:
+//!
+//! ```
+//! use range_overlap::RangeOverlap;
+//! use range_overlap::typed::{ExclInterval, InclInterval};
+//!
+//! let a = ExclInterval::new(1, 5);
+//! let b = ExclInterval::new(5, 10);
+//! assert_eq!(a.classify_against(&b), RangeOverlap::None);
+//!
+//! ```
+//!
+//! Comparing intervals with different conventions does not compile:
+//!
+//! ```compile_fail
+//! # use range_overlap::typed::{ExclInterval, InclInterval};
+//! let a = ExclInterval::new(1, 5);
+//! let c = InclInterval::new(5, 10);
+//! a.classify_against(&c);
+//! ```
+//!
+//! If you do need to mix conventions, convert to the runtime [`crate::Interval`] with
+//! [`Interval::to_dynamic`] or `From`.
+use std::marker::PhantomData;
+use std::ops::{Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::bounds;
+use crate::RangeOverlap;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Implemented by the marker types that describe one end of an [`Interval`].
+/// This trait is sealed and cannot be implemented outside this crate.
+pub trait BoundKind: sealed::Sealed {
+    #[doc(hidden)]
+    fn as_bound<T>(value: Option<T>) -> Bound<T>;
+}
+
+/// Implemented by the marker types for ends that have a value, i.e. [`Inclusive`] and [`Exclusive`].
+pub trait FiniteBound: BoundKind {}
+
+/// Marker for an end whose value is part of the interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Inclusive;
+
+/// Marker for an end whose value is not part of the interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Exclusive;
+
+/// Marker for an end with no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Open;
+
+impl sealed::Sealed for Inclusive {}
+impl sealed::Sealed for Exclusive {}
+impl sealed::Sealed for Open {}
+
+impl BoundKind for Inclusive {
+    fn as_bound<T>(value: Option<T>) -> Bound<T> {
+        value.map_or(Bound::Unbounded, Bound::Included)
+    }
+}
+
+impl BoundKind for Exclusive {
+    fn as_bound<T>(value: Option<T>) -> Bound<T> {
+        value.map_or(Bound::Unbounded, Bound::Excluded)
+    }
+}
+
+impl BoundKind for Open {
+    fn as_bound<T>(_value: Option<T>) -> Bound<T> {
+        Bound::Unbounded
+    }
+}
+
+impl FiniteBound for Inclusive {}
+impl FiniteBound for Exclusive {}
+
+/// An interval with start kind `S` and end kind `E`, each one of [`Inclusive`], [`Exclusive`], or [`Open`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval<T, S: BoundKind, E: BoundKind> {
+    // These are always `Some` for finite kinds and `None` for `Open`
+    start: Option<T>,
+    end: Option<T>,
+    kinds: PhantomData<(S, E)>,
+}
+
+/// An interval including both of its ends, like `start..=end`.
+pub type InclInterval<T> = Interval<T, Inclusive, Inclusive>;
+
+/// An interval including its start but not its end, like `start..end`.
+pub type ExclInterval<T> = Interval<T, Inclusive, Exclusive>;
+
+impl<T, S: FiniteBound, E: FiniteBound> Interval<T, S, E> {
+    /// Create an interval with both ends finite.
+    pub fn new(start: T, end: T) -> Self {
+        Self { start: Some(start), end: Some(end), kinds: PhantomData }
+    }
+}
+
+impl<T, E: FiniteBound> Interval<T, Open, E> {
+    /// Create an interval with no lower limit.
+    pub fn up_to(end: T) -> Self {
+        Self { start: None, end: Some(end), kinds: PhantomData }
+    }
+}
+
+impl<T, S: FiniteBound> Interval<T, S, Open> {
+    /// Create an interval with no upper limit.
+    pub fn starting_at(start: T) -> Self {
+        Self { start: Some(start), end: None, kinds: PhantomData }
+    }
+}
+
+impl<T> Interval<T, Open, Open> {
+    /// Create an interval with no limits on either side.
+    pub fn unbounded() -> Self {
+        Self { start: None, end: None, kinds: PhantomData }
+    }
+}
+
+impl<T, S: FiniteBound, E: BoundKind> Interval<T, S, E> {
+    /// The value of the start of the interval.
+    pub fn start(&self) -> &T {
+        self.start.as_ref().expect("finite start kinds always store a value")
+    }
+}
+
+impl<T, S: BoundKind, E: FiniteBound> Interval<T, S, E> {
+    /// The value of the end of the interval.
+    pub fn end(&self) -> &T {
+        self.end.as_ref().expect("finite end kinds always store a value")
+    }
+}
+
+impl<T, S: BoundKind, E: BoundKind> Interval<T, S, E> {
+    /// Convert into a [`crate::Interval`], which tracks inclusivity at runtime.
+    pub fn to_dynamic(self) -> crate::Interval<T> {
+        crate::Interval::from_bounds(S::as_bound(self.start), E::as_bound(self.end))
+    }
+}
+
+impl<T: PartialOrd, S: BoundKind, E: BoundKind> Interval<T, S, E> {
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`). Both must be the same type.
+    pub fn classify_against(&self, other: &Self) -> RangeOverlap {
+        bounds::classify_bound_refs(self.start_bound(), self.end_bound(), other.start_bound(), other.end_bound())
+    }
+
+    /// Returns `true` if this interval and `other` share any points.
+    pub fn has_overlap(&self, other: &Self) -> bool {
+        self.classify_against(other).has_overlap()
+    }
+
+    /// Returns `true` if `point` lies within this interval.
+    pub fn contains_point(&self, point: &T) -> bool {
+        bounds::contains_point_ref(self.start_bound(), self.end_bound(), point)
+    }
+}
+
+impl<T, S: BoundKind, E: BoundKind> RangeBounds<T> for Interval<T, S, E> {
+    fn start_bound(&self) -> Bound<&T> {
+        S::as_bound(self.start.as_ref())
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        E::as_bound(self.end.as_ref())
+    }
+}
+
+impl<T, S: BoundKind, E: BoundKind> From<Interval<T, S, E>> for crate::Interval<T> {
+    fn from(value: Interval<T, S, E>) -> Self {
+        value.to_dynamic()
+    }
+}
+
+impl<T> From<Range<T>> for ExclInterval<T> {
+    fn from(value: Range<T>) -> Self {
+        Self::new(value.start, value.end)
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for InclInterval<T> {
+    fn from(value: RangeInclusive<T>) -> Self {
+        let (start, end) = value.into_inner();
+        Self::new(start, end)
+    }
+}
+
+impl<T> From<RangeFrom<T>> for Interval<T, Inclusive, Open> {
+    fn from(value: RangeFrom<T>) -> Self {
+        Self::starting_at(value.start)
+    }
+}
+
+impl<T> From<RangeTo<T>> for Interval<T, Open, Exclusive> {
+    fn from(value: RangeTo<T>) -> Self {
+        Self::up_to(value.end)
+    }
+}
+
+impl<T> From<RangeToInclusive<T>> for Interval<T, Open, Inclusive> {
+    fn from(value: RangeToInclusive<T>) -> Self {
+        Self::up_to(value.end)
+    }
+}
+
+impl<T> From<RangeFull> for Interval<T, Open, Open> {
+    fn from(_value: RangeFull) -> Self {
+        Self::unbounded()
+    }
+}
+
+impl<T> From<ExclInterval<T>> for Range<T> {
+    fn from(value: ExclInterval<T>) -> Self {
+        let (start, end) = value.into_values();
+        start..end
+    }
+}
+
+impl<T> From<InclInterval<T>> for RangeInclusive<T> {
+    fn from(value: InclInterval<T>) -> Self {
+        let (start, end) = value.into_values();
+        start..=end
+    }
+}
+
+impl<T, S: BoundKind, E: BoundKind> Interval<T, S, E> {
+    fn into_values(self) -> (T, T) {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => unreachable!("only called for intervals with two finite ends"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(ExclInterval::new(1, 5).classify_against(&ExclInterval::new(5, 10)), RangeOverlap::None);
+        assert_eq!(InclInterval::new(1, 5).classify_against(&InclInterval::new(5, 10)), RangeOverlap::AEndsInB);
+
+        let a: Interval<_, Exclusive, Inclusive> = Interval::new(1, 5);
+        let b: Interval<_, Exclusive, Inclusive> = Interval::new(5, 10);
+        assert!(!a.has_overlap(&b));
+        assert!(!a.contains_point(&1));
+        assert!(a.contains_point(&5));
+
+        let c: Interval<_, Open, Exclusive> = Interval::up_to(5);
+        let d: Interval<_, Open, Exclusive> = Interval::up_to(10);
+        assert_eq!(c.classify_against(&d), RangeOverlap::AInsideB);
+
+        let e: Interval<i32, Open, Open> = Interval::unbounded();
+        assert!(e.contains_point(&i32::MIN));
+    }
+
+    #[test]
+    fn test_std_range_conversions() {
+        assert_eq!(ExclInterval::from(1..5), ExclInterval::new(1, 5));
+        assert_eq!(InclInterval::from(1..=5), InclInterval::new(1, 5));
+        assert_eq!(Interval::from(1..), Interval::<_, Inclusive, Open>::starting_at(1));
+        assert_eq!(Interval::from(..5), Interval::<_, Open, Exclusive>::up_to(5));
+        assert_eq!(Interval::from(..=5), Interval::<_, Open, Inclusive>::up_to(5));
+        assert_eq!(Interval::<i32, _, _>::from(..), Interval::unbounded());
+
+        assert_eq!(Range::from(ExclInterval::new(1, 5)), 1..5);
+        assert_eq!(RangeInclusive::from(InclInterval::new(1, 5)), 1..=5);
+    }
+
+    #[test]
+    fn test_to_dynamic() {
+        assert_eq!(ExclInterval::new(1, 5).to_dynamic(), crate::Interval::excl(1, 5));
+        assert_eq!(crate::Interval::from(InclInterval::new(1, 5)), crate::Interval::incl(1, 5));
+
+        let a: Interval<_, Inclusive, Open> = Interval::starting_at(3);
+        assert_eq!(a.to_dynamic(), crate::Interval::new(Some(3), None, false).unwrap());
+        assert_eq!(a.start(), &3);
+
+        // Once dynamic, different conventions can be compared
+        let b = InclInterval::new(5, 10).to_dynamic();
+        assert_eq!(ExclInterval::new(1, 5).to_dynamic().classify_against(&b), RangeOverlap::None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Classifying ranges whose end points may have been given in descending order.
+use crate::{Interval, Normalized, RangeOverlap};
+
+/// The result of [`classify_unordered`] or [`Interval::classify_unordered`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UnorderedOverlap {
+    /// How the two ranges overlap, after putting each one in ascending order.
+    pub overlap: RangeOverlap,
+
+    /// `true` if range `A` was given with its start after its end.
+    pub a_reversed: bool,
+
+    /// `true` if range `B` was given with its start after its end.
+    pub b_reversed: bool,
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// Classify how this interval (as `A`) overlaps `other` (as `B`), first swapping the ends of
+    /// either interval whose start is after its end, as [`Interval::normalized`] does.
+    ///
+    /// ```
+    /// # use range_overlap::{Interval, RangeOverlap};
+    /// let result = Interval::excl(5, 1).classify_unordered(&Interval::excl(3, 9));
+    /// assert_eq!(result.overlap, RangeOverlap::AEndsInB);
+    /// assert!(result.a_reversed && !result.b_reversed);
+    /// ```
+    pub fn classify_unordered(&self, other: &Interval<T>) -> UnorderedOverlap {
+        // Normalize copies of the bounds rather than the intervals, so nothing has to be cloned
+        let (a, a_norm) = Interval::from_bounds(self.start(), self.end()).normalized();
+        let (b, b_norm) = Interval::from_bounds(other.start(), other.end()).normalized();
+        UnorderedOverlap {
+            overlap: a.classify_against(&b),
+            a_reversed: a_norm == Normalized::Swapped,
+            b_reversed: b_norm == Normalized::Swapped,
+        }
+    }
+}
+
+/// Classify the kind of overlap between two closed ranges, either of which may have been given in
+/// descending order (e.g. from a slider that can be dragged in either direction). Each range is put
+/// in ascending order before classifying; whether an end is included stays with that end, so with
+/// `inclusive = false`, `[5, 1)` is treated as `[1, 5)`.
+///
+/// ```
+/// # use range_overlap::{classify_unordered, RangeOverlap};
+/// let result = classify_unordered(8, 2, 1, 5, true);
+/// assert_eq!(result.overlap, RangeOverlap::AStartsInB);
+/// assert!(result.a_reversed);
+/// assert!(!result.b_reversed);
+/// ```
+pub fn classify_unordered<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T, inclusive: bool) -> UnorderedOverlap {
+    let a = Interval::new_unchecked(Some(a_start), Some(a_end), inclusive);
+    let b = Interval::new_unchecked(Some(b_start), Some(b_end), inclusive);
+    a.classify_unordered(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{excl_classify, incl_classify};
+
+    #[test]
+    fn test_matches_ordered() {
+        for a_start in 0..5 {
+            for a_end in 0..5 {
+                for b_start in 0..5 {
+                    for b_end in 0..5 {
+                        let (a_lo, a_hi) = (a_start.min(a_end), a_start.max(a_end));
+                        let (b_lo, b_hi) = (b_start.min(b_end), b_start.max(b_end));
+                        let expected = UnorderedOverlap {
+                            overlap: incl_classify(a_lo, a_hi, b_lo, b_hi),
+                            a_reversed: a_start > a_end,
+                            b_reversed: b_start > b_end,
+                        };
+                        assert_eq!(classify_unordered(a_start, a_end, b_start, b_end, true), expected);
+
+                        // The ordered classifiers don't treat empty ranges as never overlapping
+                        if a_lo != a_hi && b_lo != b_hi {
+                            let expected = UnorderedOverlap { overlap: excl_classify(a_lo, a_hi, b_lo, b_hi), ..expected };
+                            assert_eq!(classify_unordered(a_start, a_end, b_start, b_end, false), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_sides() {
+        let result = Interval::at_least(5).classify_unordered(&Interval::excl(9, 1));
+        assert_eq!(result.overlap, RangeOverlap::AStartsInB);
+        assert!(!result.a_reversed && result.b_reversed);
+    }
+}